@@ -0,0 +1,77 @@
+//! Measures how long it takes to scan all processes, both of the running system and of the test fixtures.
+//!
+//! Run with `cargo bench --bench scan`, optionally followed by `-- <iterations>`.
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use process_data::{procfs::ProcfsRoot, scanner::ProcessScanner, ProcessData};
+
+const DEFAULT_ITERATIONS: u32 = 50;
+
+fn measure<F: FnMut() -> usize>(name: &str, iterations: u32, mut f: F) {
+    // warm up the page cache and the scanner's caches
+    let processes = f();
+
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        std::hint::black_box(f());
+        let elapsed = start.elapsed();
+
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+    }
+
+    println!(
+        "{name:<40} {processes:>6} processes  {:>10.3} ms/scan (fastest {:.3} ms)",
+        total.as_secs_f64() * 1000.0 / f64::from(iterations),
+        fastest.as_secs_f64() * 1000.0,
+    );
+}
+
+fn bench_procfs(label: &str, procfs: &ProcfsRoot, iterations: u32) {
+    measure(
+        &format!("{label}: all_process_data_from"),
+        iterations,
+        || {
+            ProcessData::all_process_data_from(procfs)
+                .map(|data| data.len())
+                .unwrap_or_default()
+        },
+    );
+
+    let mut single_threaded = ProcessScanner::with_workers(procfs.clone(), 1);
+    measure(
+        &format!("{label}: reused scanner, 1 worker"),
+        iterations,
+        || {
+            single_threaded
+                .scan()
+                .map(|data| data.len())
+                .unwrap_or_default()
+        },
+    );
+
+    let mut scanner = ProcessScanner::new(procfs.clone());
+    measure(&format!("{label}: reused scanner"), iterations, || {
+        scanner.scan().map(|data| data.len()).unwrap_or_default()
+    });
+}
+
+fn main() {
+    let iterations = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(DEFAULT_ITERATIONS);
+
+    bench_procfs("/proc", &ProcfsRoot::default(), iterations);
+
+    let fixtures =
+        ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"));
+    bench_procfs("fixtures", &fixtures, iterations);
+}
@@ -0,0 +1,344 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pressure::{Pressure, PressureResource},
+    unix_as_millis,
+};
+
+const DEFAULT_CGROUPFS_ROOT: &str = "/sys/fs/cgroup";
+
+/// The root of a cgroups v2 hierarchy that cgroup statistics can be collected from.
+///
+/// By default this is `/sys/fs/cgroup`, but it can also point to a captured fixture tree that mimics its layout.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CgroupfsRoot {
+    path: PathBuf,
+}
+
+impl Default for CgroupfsRoot {
+    fn default() -> Self {
+        Self::new(DEFAULT_CGROUPFS_ROOT)
+    }
+}
+
+impl CgroupfsRoot {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the directory of the cgroup with the given cgroups v2 path (e.g. `/system.slice/foo.service`) within
+    /// this cgroupfs root.
+    pub fn cgroup_path<S: AsRef<str>>(&self, cgroup: S) -> PathBuf {
+        self.path.join(cgroup.as_ref().trim_start_matches('/'))
+    }
+}
+
+/// A systemd unit (a `.service` or a `.scope`) that processes run in, derived from their cgroups v2 path.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemdUnit {
+    /// Name of the unit, e.g. `sshd.service`
+    pub name: String,
+    /// Name of the slice the unit belongs to, e.g. `system.slice`
+    pub slice: Option<String>,
+    /// cgroups v2 path of the unit, e.g. `/system.slice/sshd.service`
+    pub cgroup: String,
+}
+
+impl SystemdUnit {
+    /// Finds the unit that a process with the given cgroups v2 path runs in. Units of user managers (like
+    /// `user@1000.service`) contain units themselves, so the innermost unit is returned. Returns `None` if the process
+    /// doesn't run in any unit, e.g. for kernel threads.
+    pub fn from_cgroup_path<S: AsRef<str>>(cgroup_path: S) -> Option<Self> {
+        let segments = cgroup_path.as_ref().split('/').collect::<Vec<_>>();
+
+        let unit_index = segments
+            .iter()
+            .rposition(|segment| segment.ends_with(".service") || segment.ends_with(".scope"))?;
+
+        let slice = segments[..unit_index]
+            .iter()
+            .rev()
+            .find(|segment| segment.ends_with(".slice"))
+            .map(|slice| slice.to_string());
+
+        Some(Self {
+            name: segments[unit_index].to_string(),
+            slice,
+            cgroup: segments[..=unit_index].join("/"),
+        })
+    }
+}
+
+/// Resource usage of a whole cgroup as accounted by the kernel, which (unlike summing up the values of its processes)
+/// includes processes that have exited already.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CgroupStats {
+    /// Total CPU time in microseconds
+    pub cpu_usage: u64,
+    /// Memory usage in bytes, `None` if the memory controller isn't enabled for this cgroup
+    pub memory_current: Option<u64>,
+    /// Hard memory limit in bytes, `None` if there is no limit or the memory controller isn't enabled for this cgroup
+    pub memory_max: Option<u64>,
+    /// `None` if the memory controller isn't enabled for this cgroup
+    pub memory_events: Option<MemoryEvents>,
+    /// Bytes read from all block devices, `None` if the io controller isn't enabled for this cgroup
+    pub read_bytes: Option<u64>,
+    /// Bytes written to all block devices, `None` if the io controller isn't enabled for this cgroup
+    pub write_bytes: Option<u64>,
+    /// Number of tasks (processes and threads) in the cgroup, `None` if the pids controller isn't enabled for this
+    /// cgroup
+    pub pids_current: Option<u64>,
+    /// `None` if PSI isn't supported by the kernel
+    pub cpu_pressure: Option<Pressure>,
+    /// `None` if PSI isn't supported by the kernel
+    pub memory_pressure: Option<Pressure>,
+    /// `None` if PSI isn't supported by the kernel
+    pub io_pressure: Option<Pressure>,
+    pub timestamp: u64,
+}
+
+/// How often the memory of a cgroup has hit one of its boundaries, gathered from `memory.events`.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryEvents {
+    /// Times the cgroup was reclaimed from despite being below its `memory.low` boundary
+    pub low: u64,
+    /// Times the cgroup was throttled because it exceeded its `memory.high` boundary
+    pub high: u64,
+    /// Times the cgroup was about to exceed its `memory.max` boundary
+    pub max: u64,
+    /// Times the OOM killer was invoked because the cgroup reached its limit
+    pub oom: u64,
+    /// Processes of the cgroup that have been killed by the OOM killer
+    pub oom_kill: u64,
+}
+
+impl CgroupStats {
+    /// Reads the statistics of the cgroup with the given cgroups v2 path.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the `cpu.stat` file of the cgroup can't be read or parsed, usually because the cgroup has
+    /// been removed in the meantime.
+    pub fn from_cgroupfs<S: AsRef<str>>(cgroupfs: &CgroupfsRoot, cgroup: S) -> Result<Self> {
+        let cgroup = cgroup.as_ref();
+        let cgroup_path = cgroupfs.cgroup_path(cgroup);
+
+        let cpu_stat = std::fs::read_to_string(cgroup_path.join("cpu.stat"))
+            .with_context(|| format!("unable to read cpu.stat of cgroup {cgroup}"))?;
+        let cpu_usage = Self::flat_keyed_value(&cpu_stat, "usage_usec")
+            .context("couldn't parse cpu.stat content")?;
+
+        let memory_current = Self::read_single_value(cgroup_path.join("memory.current"));
+
+        // this is "max" if there is no limit
+        let memory_max = Self::read_single_value(cgroup_path.join("memory.max"));
+
+        let memory_events = std::fs::read_to_string(cgroup_path.join("memory.events"))
+            .ok()
+            .map(|memory_events| MemoryEvents {
+                low: Self::flat_keyed_value(&memory_events, "low").unwrap_or_default(),
+                high: Self::flat_keyed_value(&memory_events, "high").unwrap_or_default(),
+                max: Self::flat_keyed_value(&memory_events, "max").unwrap_or_default(),
+                oom: Self::flat_keyed_value(&memory_events, "oom").unwrap_or_default(),
+                oom_kill: Self::flat_keyed_value(&memory_events, "oom_kill").unwrap_or_default(),
+            });
+
+        let (read_bytes, write_bytes) = std::fs::read_to_string(cgroup_path.join("io.stat"))
+            .map(|io_stat| {
+                let (read_bytes, write_bytes) = Self::parse_io_stat(io_stat);
+                (Some(read_bytes), Some(write_bytes))
+            })
+            .unwrap_or_default();
+
+        let pids_current = Self::read_single_value(cgroup_path.join("pids.current"));
+
+        let pressure = |resource: PressureResource| {
+            Pressure::from_file(cgroup_path.join(resource.cgroup_file_name())).ok()
+        };
+
+        Ok(Self {
+            cpu_usage,
+            memory_current,
+            memory_max,
+            memory_events,
+            read_bytes,
+            write_bytes,
+            pids_current,
+            cpu_pressure: pressure(PressureResource::Cpu),
+            memory_pressure: pressure(PressureResource::Memory),
+            io_pressure: pressure(PressureResource::Io),
+            timestamp: unix_as_millis(),
+        })
+    }
+
+    /// Returns the value of a file that only contains a single number like `memory.current`, `None` if the file
+    /// doesn't exist or contains something else (like `max`).
+    fn read_single_value<P: AsRef<Path>>(path: P) -> Option<u64> {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+    }
+
+    /// Returns the value of `key` in a flat keyed file like `cpu.stat`, which consists of `<key> <value>` lines.
+    fn flat_keyed_value<S: AsRef<str>>(content: S, key: &str) -> Option<u64> {
+        content
+            .as_ref()
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, value)| value.trim().parse().ok())
+    }
+
+    /// Sums up the read and written bytes of all devices in an `io.stat` file, which consists of lines like
+    /// `8:0 rbytes=1459200 wbytes=314773504 rios=192 wios=353 dbytes=0 dios=0`.
+    fn parse_io_stat<S: AsRef<str>>(io_stat: S) -> (u64, u64) {
+        io_stat
+            .as_ref()
+            .lines()
+            .flat_map(|line| line.split_whitespace().skip(1))
+            .filter_map(|field| field.split_once('='))
+            .fold((0, 0), |(read_bytes, write_bytes), (key, value)| {
+                let value = value.parse::<u64>().unwrap_or_default();
+                match key {
+                    "rbytes" => (read_bytes.saturating_add(value), write_bytes),
+                    "wbytes" => (read_bytes, write_bytes.saturating_add(value)),
+                    _ => (read_bytes, write_bytes),
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{CgroupStats, CgroupfsRoot, MemoryEvents, SystemdUnit};
+
+    fn fixture_cgroupfs() -> CgroupfsRoot {
+        CgroupfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/cgroupfs"))
+    }
+
+    #[test]
+    fn systemd_unit_from_cgroup_path() {
+        let unit = |name: &str, slice: Option<&str>, cgroup: &str| SystemdUnit {
+            name: name.into(),
+            slice: slice.map(str::to_string),
+            cgroup: cgroup.into(),
+        };
+
+        assert_eq!(
+            Some(unit(
+                "sshd.service",
+                Some("system.slice"),
+                "/system.slice/sshd.service"
+            )),
+            SystemdUnit::from_cgroup_path("/system.slice/sshd.service")
+        );
+        assert_eq!(
+            Some(unit(
+                "app-gnome-org.gnome.Nautilus-4242.scope",
+                Some("app.slice"),
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-org.gnome.Nautilus-4242.scope"
+            )),
+            SystemdUnit::from_cgroup_path(
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-org.gnome.Nautilus-4242.scope"
+            )
+        );
+        // sub-cgroups below a unit belong to that unit
+        assert_eq!(
+            Some(unit(
+                "libvirtd.service",
+                Some("system.slice"),
+                "/system.slice/libvirtd.service"
+            )),
+            SystemdUnit::from_cgroup_path("/system.slice/libvirtd.service/payload")
+        );
+        assert_eq!(
+            Some(unit("init.scope", None, "/init.scope")),
+            SystemdUnit::from_cgroup_path("/init.scope")
+        );
+        assert_eq!(None, SystemdUnit::from_cgroup_path("/"));
+    }
+
+    #[test]
+    fn cgroup_stats_from_fixture() {
+        let stats = CgroupStats::from_cgroupfs(
+            &fixture_cgroupfs(),
+            "/system.slice/docker-fedcba9876543210.scope",
+        )
+        .unwrap();
+
+        assert_eq!(52_000_000, stats.cpu_usage);
+        assert_eq!(Some(134_217_728), stats.memory_current);
+        assert_eq!(Some(1_048_576 + 2048), stats.read_bytes);
+        assert_eq!(Some(4096 + 8192), stats.write_bytes);
+        assert_eq!(Some(536_870_912), stats.memory_max);
+        assert_eq!(
+            Some(MemoryEvents {
+                low: 0,
+                high: 12,
+                max: 3,
+                oom: 1,
+                oom_kill: 1,
+            }),
+            stats.memory_events
+        );
+        assert_eq!(Some(17), stats.pids_current);
+        assert_eq!(
+            Some(2.5),
+            stats.memory_pressure.map(|pressure| pressure.some.avg10)
+        );
+        assert_eq!(
+            Some(1.25),
+            stats
+                .memory_pressure
+                .and_then(|pressure| pressure.full)
+                .map(|full| full.avg10)
+        );
+        assert_eq!(
+            Some(8_500_000),
+            stats.io_pressure.map(|pressure| pressure.some.total)
+        );
+        assert!(stats.cpu_pressure.is_some());
+    }
+
+    #[test]
+    fn cgroup_stats_without_memory_limit() {
+        let stats = CgroupStats::from_cgroupfs(
+            &fixture_cgroupfs(),
+            "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-org.gnome.Nautilus-4242.scope",
+        )
+        .unwrap();
+
+        assert_eq!(Some(67_108_864), stats.memory_current);
+        assert_eq!(None, stats.memory_max);
+        assert_eq!(None, stats.read_bytes);
+        assert_eq!(Some(4), stats.pids_current);
+    }
+
+    #[test]
+    fn cgroup_stats_without_controllers() {
+        let cgroupfs = fixture_cgroupfs();
+
+        let stats =
+            CgroupStats::from_cgroupfs(&cgroupfs, "/machine.slice/machine-arch.scope").unwrap();
+
+        assert_eq!(1500, stats.cpu_usage);
+        assert_eq!(None, stats.memory_current);
+        assert_eq!(None, stats.read_bytes);
+        assert_eq!(None, stats.memory_events);
+        assert_eq!(None, stats.pids_current);
+        assert_eq!(None, stats.memory_pressure);
+
+        assert!(CgroupStats::from_cgroupfs(&cgroupfs, "/system.slice/gone.scope").is_err());
+    }
+}
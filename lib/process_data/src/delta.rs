@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{unix_as_millis, ProcessData};
+
+/// Changes between two snapshots of all processes.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessDataDelta {
+    /// Sequence number of the snapshot this delta has to be applied to
+    pub base_sequence: u64,
+    /// Sequence number of the snapshot that results from applying this delta
+    pub sequence: u64,
+    /// Processes whose data has not changed keep their old data but should use this as their new timestamp
+    pub timestamp: u64,
+    /// Processes that are new or whose data has changed
+    pub changed: Vec<ProcessData>,
+    /// PIDs of processes that have exited
+    pub exited: Vec<libc::pid_t>,
+}
+
+/// Answer of `resources-processes` to a request using the incremental protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessDataUpdate {
+    /// A complete snapshot, sent if the client has no snapshot yet or if its snapshot doesn't match ours
+    Full {
+        sequence: u64,
+        processes: Vec<ProcessData>,
+    },
+    Delta(ProcessDataDelta),
+}
+
+impl ProcessDataUpdate {
+    pub fn sequence(&self) -> u64 {
+        match self {
+            ProcessDataUpdate::Full { sequence, .. } => *sequence,
+            ProcessDataUpdate::Delta(delta) => delta.sequence,
+        }
+    }
+}
+
+/// Keeps track of the last snapshot that has been sent to the client so that only the differences to it need to be
+/// sent next time.
+#[derive(Debug, Default, Clone)]
+pub struct DeltaTracker {
+    sequence: u64,
+    last_sent: HashMap<libc::pid_t, ProcessData>,
+}
+
+impl DeltaTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the sequence number of the last snapshot, 0 means that there hasn't been any snapshot yet.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Records `processes` as the new snapshot and returns what the client needs to get to it.
+    ///
+    /// If `base_sequence` is not the sequence number of our last snapshot, the client and us disagree about what the
+    /// client currently knows, so a full snapshot is returned instead of a delta.
+    pub fn update(&mut self, base_sequence: u64, processes: Vec<ProcessData>) -> ProcessDataUpdate {
+        let previous_sequence = self.sequence;
+        self.sequence = self.sequence.wrapping_add(1).max(1);
+
+        if previous_sequence == 0 || base_sequence != previous_sequence {
+            self.last_sent = processes
+                .iter()
+                .map(|process| (process.pid, process.clone()))
+                .collect();
+
+            return ProcessDataUpdate::Full {
+                sequence: self.sequence,
+                processes,
+            };
+        }
+
+        let alive: HashSet<_> = processes.iter().map(|process| process.pid).collect();
+
+        let exited = self
+            .last_sent
+            .keys()
+            .filter(|pid| !alive.contains(pid))
+            .copied()
+            .collect();
+
+        let mut changed = Vec::new();
+        for process in processes {
+            let unchanged = self
+                .last_sent
+                .get(&process.pid)
+                .is_some_and(|last| last.eq_ignoring_timestamp(&process));
+
+            if !unchanged {
+                changed.push(process.clone());
+            }
+
+            self.last_sent.insert(process.pid, process);
+        }
+
+        self.last_sent.retain(|pid, _| alive.contains(pid));
+
+        ProcessDataUpdate::Delta(ProcessDataDelta {
+            base_sequence,
+            sequence: self.sequence,
+            timestamp: unix_as_millis(),
+            changed,
+            exited,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::ProcessData;
+
+    use super::{DeltaTracker, ProcessDataUpdate};
+
+    fn process(pid: libc::pid_t, user_cpu_time: u64, timestamp: u64) -> ProcessData {
+        ProcessData {
+            pid,
+            user_cpu_time,
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn first_update_is_full() {
+        let mut tracker = DeltaTracker::new();
+
+        let update = tracker.update(0, vec![process(1, 0, 0)]);
+
+        assert_eq!(
+            ProcessDataUpdate::Full {
+                sequence: 1,
+                processes: vec![process(1, 0, 0)]
+            },
+            update
+        );
+    }
+
+    #[test]
+    fn delta_contains_only_changes() {
+        let mut tracker = DeltaTracker::new();
+        tracker.update(
+            0,
+            vec![process(1, 0, 0), process(2, 0, 0), process(3, 0, 0)],
+        );
+
+        let update = tracker.update(
+            1,
+            vec![process(1, 0, 10), process(2, 5, 10), process(4, 0, 10)],
+        );
+
+        let ProcessDataUpdate::Delta(mut delta) = update else {
+            panic!("expected a delta, got {update:?}");
+        };
+        delta.changed.sort_by_key(|process| process.pid);
+
+        assert_eq!(1, delta.base_sequence);
+        assert_eq!(2, delta.sequence);
+        assert_eq!(vec![process(2, 5, 10), process(4, 0, 10)], delta.changed);
+        assert_eq!(vec![3], delta.exited);
+    }
+
+    #[test]
+    fn mismatching_base_resyncs() {
+        let mut tracker = DeltaTracker::new();
+        tracker.update(0, vec![process(1, 0, 0)]);
+        tracker.update(1, vec![process(1, 1, 0)]);
+
+        let update = tracker.update(1, vec![process(1, 2, 0)]);
+
+        assert_eq!(
+            ProcessDataUpdate::Full {
+                sequence: 3,
+                processes: vec![process(1, 2, 0)]
+            },
+            update
+        );
+    }
+}
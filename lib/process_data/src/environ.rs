@@ -0,0 +1,97 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::procfs::ProcfsRoot;
+
+/// An environment variable of a process, gathered from `/proc/<pid>/environ`.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentVariable {
+    pub name: String,
+    pub value: String,
+}
+
+impl EnvironmentVariable {
+    /// Parses the contents of an environ file, which consists of NUL-separated `NAME=value` pairs. The order of the
+    /// variables is kept.
+    pub fn parse<B: AsRef<[u8]>>(environ: B) -> Vec<Self> {
+        environ
+            .as_ref()
+            .split(|byte| *byte == b'\0')
+            .filter(|variable| !variable.is_empty())
+            .map(|variable| {
+                let variable = String::from_utf8_lossy(variable);
+                // processes are free to put anything into their environment, even entries without a '='
+                let (name, value) = variable.split_once('=').unwrap_or((&variable, ""));
+                Self {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the raw contents of the environ file of the process with the given PID.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the environ file of the process can't be read, usually because the process belongs to
+    /// another user or has exited in the meantime.
+    pub fn raw_from_procfs(procfs: &ProcfsRoot, pid: libc::pid_t) -> std::io::Result<Vec<u8>> {
+        std::fs::read(procfs.process_path(pid).join("environ"))
+    }
+
+    /// Returns the environment variables of the process with the given PID.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the environ file of the process can't be read, usually because the process belongs to
+    /// another user or has exited in the meantime.
+    pub fn all_from_procfs(procfs: &ProcfsRoot, pid: libc::pid_t) -> Result<Vec<Self>> {
+        Self::raw_from_procfs(procfs, pid)
+            .map(Self::parse)
+            .with_context(|| format!("unable to read environment of process {pid}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::procfs::ProcfsRoot;
+
+    use super::EnvironmentVariable;
+
+    fn variable(name: &str, value: &str) -> EnvironmentVariable {
+        EnvironmentVariable {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    #[test]
+    fn environ_from_fixture() {
+        let procfs =
+            ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"));
+
+        assert_eq!(
+            vec![
+                variable("HOME", "/home/user"),
+                variable("DISPLAY", ":0"),
+                variable("LD_PRELOAD", "/usr/lib/libfoo.so"),
+                variable("OPTIONS", "a=b=c"),
+                variable("EMPTY", ""),
+            ],
+            EnvironmentVariable::all_from_procfs(&procfs, 1234).unwrap()
+        );
+    }
+
+    #[test]
+    fn malformed_environ() {
+        assert_eq!(
+            vec![variable("NO_EQUALS_SIGN", ""), variable("A", "\u{FFFD}")],
+            EnvironmentVariable::parse(b"NO_EQUALS_SIGN\0\0A=\xff\0")
+        );
+    }
+}
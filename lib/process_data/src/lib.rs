@@ -1,44 +1,72 @@
+pub mod cgroup;
+pub mod delta;
+pub mod environ;
+pub mod memory_maps;
+pub mod open_files;
 pub mod pci_slot;
+pub mod pressure;
+pub mod proc_connector;
+pub mod procfs;
+pub mod protocol;
+pub mod scanner;
+pub mod signal;
+pub mod users;
 
 use anyhow::{bail, Context, Result};
-use glob::glob;
 use lazy_regex::{lazy_regex, Regex};
 use nutype::nutype;
+#[cfg(feature = "nvidia")]
 use nvml_wrapper::enums::device::UsedGpuMemory;
+#[cfg(feature = "nvidia")]
 use nvml_wrapper::error::NvmlError;
+#[cfg(feature = "nvidia")]
 use nvml_wrapper::struct_wrappers::device::{ProcessInfo, ProcessUtilizationSample};
+#[cfg(feature = "nvidia")]
 use nvml_wrapper::{Device, Nvml};
 use once_cell::sync::Lazy;
 use pci_slot::PciSlot;
+use procfs::ProcfsRoot;
+use scanner::{read_into, ProcessFields, ProcessScanner, ScanBuffers, StaticProcessData};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::fs::File;
-use std::io::{Read, Write};
 use std::os::linux::fs::MetadataExt;
 use std::path::Path;
 use std::str::FromStr;
+#[cfg(feature = "nvidia")]
 use std::sync::RwLock;
-use std::{path::PathBuf, time::SystemTime};
+use std::time::SystemTime;
+use users::Ids;
 
 const STAT_OFFSET: usize = 2; // we split the stat contents where the executable name ends, which is the second element
+const STAT_STATE: usize = 2 - STAT_OFFSET;
 const STAT_PARENT_PID: usize = 3 - STAT_OFFSET;
+const STAT_PROCESS_GROUP: usize = 4 - STAT_OFFSET;
+const STAT_SESSION: usize = 5 - STAT_OFFSET;
+const STAT_TTY: usize = 6 - STAT_OFFSET;
+const STAT_FLAGS: usize = 8 - STAT_OFFSET;
 const STAT_USER_CPU_TIME: usize = 13 - STAT_OFFSET;
 const STAT_SYSTEM_CPU_TIME: usize = 14 - STAT_OFFSET;
 const STAT_NICE: usize = 18 - STAT_OFFSET;
+const STAT_NUM_THREADS: usize = 19 - STAT_OFFSET;
 const STAT_STARTTIME: usize = 21 - STAT_OFFSET;
+const STAT_PROCESSOR: usize = 38 - STAT_OFFSET;
+const STAT_RT_PRIORITY: usize = 39 - STAT_OFFSET;
+const STAT_POLICY: usize = 40 - STAT_OFFSET;
 
-static USERS_CACHE: Lazy<HashMap<libc::uid_t, String>> = Lazy::new(|| unsafe {
-    uzers::all_users()
-        .map(|user| (user.uid(), user.name().to_string_lossy().to_string()))
-        .collect()
-});
+// see include/linux/sched.h
+const PF_KTHREAD: u32 = 0x00200000;
+
+// see include/uapi/linux/ioprio.h
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_SHIFT: u32 = 13;
+// bits 3 to 12 of the priority data hold an optional hint (e.g. for command duration limits), only the lowest 3
+// bits are the level
+const IOPRIO_LEVEL_MASK: u32 = 0x7;
 
 static PAGESIZE: Lazy<usize> = Lazy::new(sysconf::pagesize);
 
 static NUM_CPUS: Lazy<usize> = Lazy::new(num_cpus::get);
 
-static RE_UID: Lazy<Regex> = lazy_regex!(r"Uid:\s*(\d+)");
-
 static RE_AFFINITY: Lazy<Regex> = lazy_regex!(r"Cpus_allowed:\s*([0-9A-Fa-f]+)");
 
 static RE_SWAP_USAGGE: Lazy<Regex> = lazy_regex!(r"VmSwap:\s*([0-9]+)\s*kB");
@@ -47,37 +75,10 @@ static RE_IO_READ: Lazy<Regex> = lazy_regex!(r"read_bytes:\s*(\d+)");
 
 static RE_IO_WRITE: Lazy<Regex> = lazy_regex!(r"write_bytes:\s*(\d+)");
 
-static RE_DRM_PDEV: Lazy<Regex> =
-    lazy_regex!(r"drm-pdev:\s*([0-9A-Fa-f]{4}:[0-9A-Fa-f]{2}:[0-9A-Fa-f]{2}\.[0-9A-Fa-f])");
-
-static RE_DRM_CLIENT_ID: Lazy<Regex> = lazy_regex!(r"drm-client-id:\s*(\d+)");
-
-// AMD only
-static RE_DRM_ENGINE_GFX: Lazy<Regex> = lazy_regex!(r"drm-engine-gfx:\s*(\d+)\s*ns");
-
-// AMD only
-static RE_DRM_ENGINE_COMPUTE: Lazy<Regex> = lazy_regex!(r"drm-engine-compute:\s*(\d+)\s*ns");
-
-// AMD only
-static RE_DRM_ENGINE_ENC: Lazy<Regex> = lazy_regex!(r"drm-engine-enc:\s*(\d+)\s*ns");
-
-// AMD only
-static RE_DRM_ENGINE_DEC: Lazy<Regex> = lazy_regex!(r"drm-engine-dec:\s*(\d+)\s*ns");
-
-// AMD only
-static RE_DRM_MEMORY_VRAM: Lazy<Regex> = lazy_regex!(r"drm-memory-vram:\s*(\d+)\s*KiB");
-
-// AMD only
-static RE_DRM_MEMORY_GTT: Lazy<Regex> = lazy_regex!(r"drm-memory-gtt:\s*(\d+)\s*KiB");
-
-// Intel only
-static RE_DRM_ENGINE_RENDER: Lazy<Regex> = lazy_regex!(r"drm-engine-render:\s*(\d+)\s*ns");
-
-// Intel only
-static RE_DRM_ENGINE_VIDEO: Lazy<Regex> = lazy_regex!(r"drm-engine-video:\s*(\d+)\s*ns");
-
+#[cfg(feature = "nvidia")]
 static NVML: Lazy<Result<Nvml, NvmlError>> = Lazy::new(Nvml::init);
 
+#[cfg(feature = "nvidia")]
 static NVML_DEVICES: Lazy<Vec<(PciSlot, Device)>> = Lazy::new(|| {
     if let Ok(nvml) = NVML.as_ref() {
         let device_count = nvml.device_count().unwrap_or(0);
@@ -96,9 +97,11 @@ static NVML_DEVICES: Lazy<Vec<(PciSlot, Device)>> = Lazy::new(|| {
     }
 });
 
+#[cfg(feature = "nvidia")]
 static NVIDIA_PROCESSES_STATS: Lazy<RwLock<HashMap<PciSlot, Vec<ProcessUtilizationSample>>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
+#[cfg(feature = "nvidia")]
 static NVIDIA_PROCESS_INFOS: Lazy<RwLock<HashMap<PciSlot, Vec<ProcessInfo>>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
@@ -110,30 +113,781 @@ static NVIDIA_PROCESS_INFOS: Lazy<RwLock<HashMap<PciSlot, Vec<ProcessInfo>>>> =
 )]
 pub struct Niceness(i8);
 
+/// The state of a process or thread, see man proc(5)
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Copy)]
+pub enum ProcessState {
+    Running,
+    Sleeping,
+    /// Uninterruptible sleep, usually waiting on I/O
+    DiskSleep,
+    Zombie,
+    Stopped,
+    TracingStop,
+    Dead,
+    Idle,
+    Parked,
+    Waking,
+    #[default]
+    Unknown,
+}
+
+impl From<char> for ProcessState {
+    fn from(value: char) -> Self {
+        match value {
+            'R' => Self::Running,
+            'S' => Self::Sleeping,
+            'D' => Self::DiskSleep,
+            'Z' => Self::Zombie,
+            'T' => Self::Stopped,
+            't' => Self::TracingStop,
+            'X' | 'x' => Self::Dead,
+            'I' => Self::Idle,
+            'P' => Self::Parked,
+            'K' | 'W' => Self::Waking,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// The scheduling policy of a process or thread, see man sched(7)
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Copy)]
+pub enum SchedulingPolicy {
+    /// `SCHED_OTHER`, the default time-sharing policy
+    #[default]
+    Other,
+    /// `SCHED_FIFO`, real-time without time slicing
+    Fifo,
+    /// `SCHED_RR`, real-time with time slicing
+    RoundRobin,
+    /// `SCHED_BATCH`, for CPU-intensive non-interactive tasks
+    Batch,
+    /// `SCHED_IDLE`, for tasks that should only run when nothing else wants to
+    Idle,
+    /// `SCHED_DEADLINE`, which can't be set using `sched_setscheduler()`
+    Deadline,
+    Unknown,
+}
+
+impl From<u32> for SchedulingPolicy {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Other,
+            1 => Self::Fifo,
+            2 => Self::RoundRobin,
+            3 => Self::Batch,
+            5 => Self::Idle,
+            6 => Self::Deadline,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl SchedulingPolicy {
+    /// Returns the value of the policy as used by `sched_setscheduler()`, `None` if it can't be set that way.
+    pub fn as_raw(&self) -> Option<i32> {
+        match self {
+            Self::Other => Some(libc::SCHED_OTHER),
+            Self::Fifo => Some(libc::SCHED_FIFO),
+            Self::RoundRobin => Some(libc::SCHED_RR),
+            Self::Batch => Some(libc::SCHED_BATCH),
+            Self::Idle => Some(libc::SCHED_IDLE),
+            Self::Deadline | Self::Unknown => None,
+        }
+    }
+
+    /// Returns whether this is a real-time policy, i.e. one that uses a real-time priority instead of the niceness.
+    pub fn is_realtime(&self) -> bool {
+        matches!(self, Self::Fifo | Self::RoundRobin)
+    }
+}
+
+/// The I/O scheduling class of a process or thread, see man ioprio_set(2)
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Copy)]
+pub enum IoPriorityClass {
+    /// `IOPRIO_CLASS_NONE`, the I/O priority is derived from the niceness
+    #[default]
+    None,
+    /// `IOPRIO_CLASS_RT`, always gets access to the disk first
+    RealTime,
+    /// `IOPRIO_CLASS_BE`, the default for processes that explicitly set an I/O priority
+    BestEffort,
+    /// `IOPRIO_CLASS_IDLE`, only gets access to the disk when no one else needs it
+    Idle,
+}
+
+/// The I/O priority of a process or thread as used by `ioprio_get()` and `ioprio_set()`
 #[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Copy)]
+pub struct IoPriority {
+    pub class: IoPriorityClass,
+    /// Between 0 (highest) and 7 (lowest), only meaningful for the real-time and best-effort classes
+    pub level: u8,
+}
+
+impl IoPriority {
+    /// Decodes an I/O priority as returned by `ioprio_get()`, `None` if its class is unknown.
+    pub fn from_raw(raw: i32) -> Option<Self> {
+        let raw = raw as u32;
+
+        let class = match raw >> IOPRIO_CLASS_SHIFT {
+            0 => IoPriorityClass::None,
+            1 => IoPriorityClass::RealTime,
+            2 => IoPriorityClass::BestEffort,
+            3 => IoPriorityClass::Idle,
+            _ => return None,
+        };
+
+        Some(Self {
+            class,
+            level: (raw & IOPRIO_LEVEL_MASK) as u8,
+        })
+    }
+
+    /// Encodes this I/O priority for use with `ioprio_set()`.
+    pub fn as_raw(&self) -> i32 {
+        let class = match self.class {
+            IoPriorityClass::None => 0,
+            IoPriorityClass::RealTime => 1,
+            IoPriorityClass::BestEffort => 2,
+            IoPriorityClass::Idle => 3,
+        };
+
+        let level = match self.class {
+            IoPriorityClass::RealTime | IoPriorityClass::BestEffort => u32::from(self.level.min(7)),
+            IoPriorityClass::None | IoPriorityClass::Idle => 0,
+        };
+
+        ((class << IOPRIO_CLASS_SHIFT) | level) as i32
+    }
+
+    /// Returns the I/O priority of the process or thread with the ID `id`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `ioprio_get()` fails, e.g. because the process doesn't exist anymore.
+    pub fn of(id: libc::pid_t) -> Result<Self> {
+        let raw = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, id) };
+
+        if raw == -1 {
+            return Err(std::io::Error::last_os_error()).context("ioprio_get() failed");
+        }
+
+        Self::from_raw(raw as i32).with_context(|| format!("unknown I/O priority {raw}"))
+    }
+}
+
+/// How a process is containerized. Every variant carries the name or ID of the container (or app) if it could be
+/// determined.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Containerization {
     #[default]
     None,
-    Flatpak,
-    Snap,
+    /// Carries the app ID
+    Flatpak(Option<String>),
+    /// Carries the snap name
+    Snap(Option<String>),
+    /// Carries the shortened container ID
+    Docker(Option<String>),
+    /// Carries the container name or the shortened container ID
+    Podman(Option<String>),
+    /// Carries the container name
+    Lxc(Option<String>),
+    /// Carries the machine name
+    Nspawn(Option<String>),
+    /// Carries the container name
+    Toolbox(Option<String>),
+    /// Carries the container name
+    Distrobox(Option<String>),
+    /// Carries the file name of the AppImage
+    AppImage(Option<String>),
 }
 
-/// Represents GPU usage statistics per-process. Depending on the GPU manufacturer (which should be determined in
-/// Resources itself), these numbers need to interpreted differently
-///
-/// AMD (default): gfx, enc and dec are nanoseconds spent for that process
-///
-/// Nvidia: Process info is gathered through NVML, thus gfx, enc and dec are percentages from 0-100 (timestamps
-/// are irrelevant, nvidia bool is set to true)
-///
-/// Intel: enc and dec are not separated, both are accumulated in enc, also mem is always going to be 0
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Copy)]
+impl Containerization {
+    /// Returns the name or ID of the container (or app) if it is known.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::None => None,
+            Self::Flatpak(name)
+            | Self::Snap(name)
+            | Self::Docker(name)
+            | Self::Podman(name)
+            | Self::Lxc(name)
+            | Self::Nspawn(name)
+            | Self::Toolbox(name)
+            | Self::Distrobox(name)
+            | Self::AppImage(name) => name.as_deref(),
+        }
+    }
+
+    fn detect<S: AsRef<str>>(proc_path: &Path, commandline: S, cgroup_path: Option<&str>) -> Self {
+        let commandline = commandline.as_ref();
+        let root = proc_path.join("root");
+
+        if let Some(snap_path) = commandline.strip_prefix("/snap/") {
+            return Self::Snap(
+                snap_path
+                    .split('/')
+                    .next()
+                    .filter(|name| !name.is_empty() && *name != "bin")
+                    .map(str::to_string),
+            );
+        }
+
+        if let Ok(flatpak_info) = std::fs::read_to_string(root.join(".flatpak-info")) {
+            return Self::Flatpak(Self::ini_value(&flatpak_info, "Application", "name"));
+        }
+
+        // AppImages are mounted to $TMPDIR/.mount_XXXXXX by their runtime, only look into the environment if the
+        // commandline suggests that, since reading it for every process would be quite expensive
+        if commandline.contains("/.mount_") {
+            let appimage = std::fs::read(proc_path.join("environ"))
+                .ok()
+                .and_then(|environ| {
+                    environ::EnvironmentVariable::parse(environ)
+                        .into_iter()
+                        .find(|variable| variable.name == "APPIMAGE")
+                })
+                .and_then(|variable| {
+                    Path::new(&variable.value)
+                        .file_name()
+                        .map(|file_name| file_name.to_string_lossy().to_string())
+                });
+            return Self::AppImage(appimage);
+        }
+
+        let from_cgroup = cgroup_path
+            .and_then(Self::from_cgroup_path)
+            .map(|(containerization, _)| containerization);
+
+        if matches!(from_cgroup, None | Some(Self::Podman(_))) {
+            // toolbox and distrobox are based on podman, so they need to be told apart using files inside the container
+            if let Ok(containerenv) = std::fs::read_to_string(root.join("run/.containerenv")) {
+                let name = Self::containerenv_value(&containerenv, "name").or_else(|| {
+                    from_cgroup
+                        .as_ref()
+                        .and_then(|c| c.name().map(str::to_string))
+                });
+
+                return if root.join("run/.toolboxenv").exists() {
+                    Self::Toolbox(name)
+                } else if Self::is_distrobox(proc_path) {
+                    Self::Distrobox(name)
+                } else {
+                    Self::Podman(name)
+                };
+            }
+        }
+
+        if let Some(containerization) = from_cgroup {
+            containerization
+        } else if root.join(".dockerenv").exists() {
+            Self::Docker(None)
+        } else {
+            Self::None
+        }
+    }
+
+    /// Finds the container that a process with the given cgroups v2 path (e.g. `/system.slice/docker-<id>.scope`) runs
+    /// in. Returns the containerization and the path of the container's cgroup.
+    pub fn from_cgroup_path<S: AsRef<str>>(cgroup_path: S) -> Option<(Self, String)> {
+        let segments = cgroup_path.as_ref().split('/').collect::<Vec<_>>();
+
+        segments
+            .iter()
+            .enumerate()
+            .find_map(|(i, segment)| {
+                if let Some(id) = segment
+                    .strip_prefix("libpod-")
+                    .and_then(|s| s.strip_suffix(".scope"))
+                {
+                    // conmon is podman's container monitor, it doesn't run inside of the container
+                    (!id.starts_with("conmon-"))
+                        .then(|| (Self::Podman(Some(Self::short_id(id))), i))
+                } else if let Some(id) = segment
+                    .strip_prefix("docker-")
+                    .and_then(|s| s.strip_suffix(".scope"))
+                {
+                    Some((Self::Docker(Some(Self::short_id(id))), i))
+                } else if *segment == "docker" {
+                    // docker with the cgroupfs driver uses /docker/<id>
+                    segments
+                        .get(i + 1)
+                        .map(|id| (Self::Docker(Some(Self::short_id(id))), i + 1))
+                } else if let Some(name) = segment.strip_prefix("lxc.payload.") {
+                    Some((Self::Lxc(Some(name.to_string())), i))
+                } else if *segment == "machine.slice" {
+                    segments.get(i + 1).and_then(|machine| {
+                        machine
+                            .strip_prefix("systemd-nspawn@")
+                            .and_then(|s| s.strip_suffix(".service"))
+                            .or_else(|| {
+                                machine
+                                    .strip_prefix("machine-")
+                                    .and_then(|s| s.strip_suffix(".scope"))
+                            })
+                            .map(|name| {
+                                let name =
+                                    unescape::unescape(name).unwrap_or_else(|| name.to_string());
+                                (Self::Nspawn(Some(name)), i + 1)
+                            })
+                    })
+                } else {
+                    None
+                }
+            })
+            .map(|(containerization, last_segment)| {
+                (containerization, segments[..=last_segment].join("/"))
+            })
+    }
+
+    fn is_distrobox(proc_path: &Path) -> bool {
+        std::fs::read(proc_path.join("environ"))
+            .map(|environ| {
+                environ::EnvironmentVariable::parse(environ)
+                    .iter()
+                    .any(|variable| variable.name == "DISTROBOX_ENTER_PATH")
+            })
+            .unwrap_or_default()
+    }
+
+    fn short_id<S: AsRef<str>>(id: S) -> String {
+        id.as_ref().chars().take(12).collect()
+    }
+
+    fn containerenv_value<S: AsRef<str>>(containerenv: S, key: &str) -> Option<String> {
+        containerenv
+            .as_ref()
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value.trim_matches('"').to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn ini_value<S: AsRef<str>>(ini: S, section: &str, key: &str) -> Option<String> {
+        let section_header = format!("[{section}]");
+
+        ini.as_ref()
+            .lines()
+            .skip_while(|line| line.trim() != section_header)
+            .skip(1)
+            .take_while(|line| !line.starts_with('['))
+            .filter_map(|line| line.split_once('='))
+            .find(|(k, _)| k.trim() == key)
+            .map(|(_, value)| value.trim().to_string())
+    }
+}
+
+/// What kind of work a GPU engine does, derived from the name the driver gives it in fdinfo.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum GpuEngineClass {
+    /// 3D, compute and anything else that isn't clearly one of the other classes
+    Graphics,
+    Encode,
+    Decode,
+    /// Engines that both encode and decode video, e.g. `video` of i915 or `vcs` of Xe
+    Media,
+    /// Copy engines, video post-processing and the like, which aren't accounted as GPU usage
+    Other,
+}
+
+impl GpuEngineClass {
+    /// Classifies an engine by its name as used in `drm-engine-<name>` or `drm-cycles-<name>`.
+    pub fn from_engine_name<S: AsRef<str>>(name: S) -> Self {
+        let name = name.as_ref().to_ascii_lowercase();
+
+        if name.starts_with("enc") {
+            Self::Encode
+        } else if name.starts_with("dec") || name.starts_with("jpeg") {
+            Self::Decode
+        } else if name == "video" || name == "vcs" || name.starts_with("vcn") {
+            Self::Media
+        } else if matches!(
+            name.as_str(),
+            "copy" | "bcs" | "dma" | "sdma" | "video-enhance" | "vecs" | "tfu" | "cache_clean"
+        ) {
+            Self::Other
+        } else {
+            Self::Graphics
+        }
+    }
+
+    pub fn is_encode(&self) -> bool {
+        matches!(self, Self::Encode | Self::Media)
+    }
+
+    pub fn is_decode(&self) -> bool {
+        matches!(self, Self::Decode | Self::Media)
+    }
+}
+
+/// How busy a single GPU engine has been for a process, as accumulated since the process has opened the GPU.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GpuEngineUsage {
+    /// Nanoseconds the engine has been busy for (`drm-engine-<name>`)
+    Time(u64),
+    /// Cycles the engine has been busy for (`drm-cycles-<name>`) and cycles that have passed in total
+    /// (`drm-total-cycles-<name>`), used by drivers like Xe
+    Cycles { busy: u64, total: u64 },
+    /// Current utilization from 0 to 100 as reported by NVML, which doesn't need a previous sample
+    Percentage(u64),
+}
+
+impl GpuEngineUsage {
+    fn busy(&self) -> u64 {
+        match self {
+            Self::Time(busy) | Self::Cycles { busy, .. } | Self::Percentage(busy) => *busy,
+        }
+    }
+
+    /// Returns the fraction (from 0.0 to 1.0) of time the engine has been busy since `previous`, which has been
+    /// gathered `elapsed_millis` milliseconds earlier.
+    pub fn fraction_since(&self, previous: Option<&Self>, elapsed_millis: u64) -> f32 {
+        let fraction = match (self, previous) {
+            (Self::Percentage(percentage), _) => *percentage as f32 / 100.0,
+            (Self::Time(busy), Some(Self::Time(previous_busy))) if *previous_busy != 0 => {
+                busy.saturating_sub(*previous_busy) as f32 / (elapsed_millis as f32 * 1_000_000.0)
+            }
+            (
+                Self::Cycles { busy, total },
+                Some(Self::Cycles {
+                    busy: previous_busy,
+                    total: previous_total,
+                }),
+            ) if *previous_busy != 0 => {
+                busy.saturating_sub(*previous_busy) as f32
+                    / total.saturating_sub(*previous_total) as f32
+            }
+            _ => 0.0,
+        };
+
+        if fraction.is_finite() {
+            fraction.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Represents GPU usage statistics per-process, following the DRM client usage stats spec
+/// (https://docs.kernel.org/gpu/drm-usage-stats.html) so that every driver implementing it is supported. Nvidia GPUs
+/// are queried through NVML instead, their engines are called `gfx`, `enc` and `dec`.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GpuUsageStats {
-    pub gfx: u64,
-    pub mem: u64,
-    pub enc: u64,
-    pub dec: u64,
-    pub nvidia: bool,
+    /// Key: Name of the engine as reported by the driver, e.g. `gfx`, `render`, `video` or `rcs`
+    pub engines: BTreeMap<String, GpuEngineUsage>,
+    /// Key: Name of the memory region, e.g. `vram`, `gtt` or `system`; Value: Total memory in bytes
+    /// (`drm-total-<region>`)
+    pub memory_total: BTreeMap<String, u64>,
+    /// Key: Name of the memory region; Value: Resident memory in bytes (`drm-resident-<region>` or the legacy
+    /// `drm-memory-<region>`)
+    pub memory_resident: BTreeMap<String, u64>,
+}
+
+impl GpuUsageStats {
+    /// Returns the amount of GPU memory used in bytes, which is the resident memory if the driver reports it and the
+    /// total memory otherwise.
+    pub fn memory(&self) -> u64 {
+        if self.memory_resident.is_empty() {
+            self.memory_total.values().sum()
+        } else {
+            self.memory_resident.values().sum()
+        }
+    }
+
+    /// Returns the fraction of time every engine has been busy since `previous`, which has been gathered
+    /// `elapsed_millis` milliseconds earlier.
+    pub fn engine_fractions(&self, previous: &Self, elapsed_millis: u64) -> BTreeMap<String, f32> {
+        self.engines
+            .iter()
+            .map(|(name, usage)| {
+                (
+                    name.clone(),
+                    usage.fraction_since(previous.engines.get(name), elapsed_millis),
+                )
+            })
+            .collect()
+    }
+
+    /// For GPUs with a media engine that both encodes and decodes, drivers may account its usage under either
+    /// `enc` or `dec`. This makes both of them contain the busier one.
+    pub fn combine_media_engines(&mut self) {
+        let combined = [self.engines.get("enc"), self.engines.get("dec")]
+            .into_iter()
+            .flatten()
+            .max_by_key(|usage| usage.busy())
+            .copied();
+
+        if let Some(combined) = combined {
+            self.engines.insert(String::from("enc"), combined);
+            self.engines.insert(String::from("dec"), combined);
+        }
+    }
+
+    /// Merges `other` into `self` by keeping the busier engines and the larger memory regions.
+    fn merge_max(&mut self, other: Self) {
+        for (name, usage) in other.engines {
+            self.engines
+                .entry(name)
+                .and_modify(|existing| {
+                    if usage.busy() > existing.busy() {
+                        *existing = usage;
+                    }
+                })
+                .or_insert(usage);
+        }
+
+        for (region, bytes) in other.memory_total {
+            let existing = self.memory_total.entry(region).or_default();
+            *existing = (*existing).max(bytes);
+        }
+
+        for (region, bytes) in other.memory_resident {
+            let existing = self.memory_resident.entry(region).or_default();
+            *existing = (*existing).max(bytes);
+        }
+    }
+
+    /// Parses a memory value of fdinfo like `1024 KiB` into bytes.
+    fn parse_memory(value: &str) -> Option<u64> {
+        let mut split = value.split_whitespace();
+        let number = split.next()?.parse::<u64>().ok()?;
+
+        let multiplier = match split.next() {
+            None => 1,
+            Some("KiB") => 1024,
+            Some("MiB") => 1024 * 1024,
+            Some("GiB") => 1024 * 1024 * 1024,
+            Some(_) => return None,
+        };
+
+        Some(number.saturating_mul(multiplier))
+    }
+
+    /// Parses the DRM part of an fdinfo file, returns the PCI slot of the GPU, the usage stats and the DRM client ID.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the fdinfo doesn't belong to a DRM client of a PCI device.
+    pub fn parse_fdinfo<S: AsRef<str>>(fdinfo: S) -> Result<(PciSlot, Self, i64)> {
+        let mut pci_slot = None;
+        let mut client_id = None;
+
+        let mut stats = Self::default();
+        let mut busy_cycles = BTreeMap::new();
+        let mut total_cycles = BTreeMap::new();
+
+        for (key, value) in fdinfo
+            .as_ref()
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim(), value.trim()))
+        {
+            if key == "drm-pdev" {
+                pci_slot = PciSlot::from_str(value).ok();
+            } else if key == "drm-client-id" {
+                client_id = value.parse::<i64>().ok();
+            } else if key.starts_with("drm-engine-capacity-") {
+                // number of engines of that kind, not a usage
+                continue;
+            } else if let Some(engine) = key.strip_prefix("drm-engine-") {
+                if let Some(ns) = value
+                    .strip_suffix("ns")
+                    .and_then(|ns| ns.trim().parse().ok())
+                {
+                    stats
+                        .engines
+                        .insert(engine.to_string(), GpuEngineUsage::Time(ns));
+                }
+            } else if let Some(engine) = key.strip_prefix("drm-cycles-") {
+                if let Ok(cycles) = value.parse::<u64>() {
+                    busy_cycles.insert(engine.to_string(), cycles);
+                }
+            } else if let Some(engine) = key.strip_prefix("drm-total-cycles-") {
+                if let Ok(cycles) = value.parse::<u64>() {
+                    total_cycles.insert(engine.to_string(), cycles);
+                }
+            } else if let Some(region) = key.strip_prefix("drm-total-") {
+                if let Some(bytes) = Self::parse_memory(value) {
+                    stats.memory_total.insert(region.to_string(), bytes);
+                }
+            } else if let Some(region) = key.strip_prefix("drm-resident-") {
+                if let Some(bytes) = Self::parse_memory(value) {
+                    stats.memory_resident.insert(region.to_string(), bytes);
+                }
+            } else if let Some(region) = key.strip_prefix("drm-memory-") {
+                // legacy name of drm-resident-<region>, which takes precedence if both are present
+                if let Some(bytes) = Self::parse_memory(value) {
+                    stats
+                        .memory_resident
+                        .entry(region.to_string())
+                        .or_insert(bytes);
+                }
+            }
+        }
+
+        // drivers like panfrost report both time and cycles for the same engine, prefer the time in that case
+        for (engine, busy) in busy_cycles {
+            if let Some(total) = total_cycles.get(&engine) {
+                stats
+                    .engines
+                    .entry(engine)
+                    .or_insert(GpuEngineUsage::Cycles {
+                        busy,
+                        total: *total,
+                    });
+            }
+        }
+
+        match (pci_slot, client_id) {
+            (Some(pci_slot), Some(client_id)) => Ok((pci_slot, stats, client_id)),
+            _ => bail!("unable to find gpu information in this fdinfo"),
+        }
+    }
+}
+
+/// Memory usage of a process as accounted by `/proc/<pid>/smaps_rollup`, all values are in bytes.
+///
+/// Unlike the resident set size, the proportional set size (PSS) divides shared pages evenly between all processes
+/// mapping them, so summing up the PSS of several processes doesn't count shared memory more than once. The unique
+/// set size (USS) only contains pages that are private to the process.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Copy)]
+pub struct SmapsRollup {
+    pub pss: usize,
+    pub uss: usize,
+    /// Proportional share of anonymous memory
+    pub anonymous: usize,
+    /// Proportional share of file-backed memory
+    pub file: usize,
+    /// Proportional share of shared memory, e.g. tmpfs or SysV shared memory
+    pub shmem: usize,
+}
+
+impl SmapsRollup {
+    /// Parses the contents of a `smaps_rollup` file.
+    ///
+    /// Older kernels don't split up the PSS into anonymous, file-backed and shmem memory. In that case, `anonymous`
+    /// falls back to the (non-proportional) amount of anonymous memory and `file` and `shmem` are 0.
+    pub fn parse<S: AsRef<str>>(smaps_rollup: S) -> Result<Self> {
+        // the first line describes the address range and is skipped because its value doesn't end in kB
+        let fields: HashMap<&str, usize> = smaps_rollup
+            .as_ref()
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                let kib = value
+                    .trim()
+                    .strip_suffix("kB")?
+                    .trim()
+                    .parse::<usize>()
+                    .ok()?;
+                Some((key.trim(), kib.saturating_mul(1024)))
+            })
+            .collect();
+
+        let field = |name: &str| fields.get(name).copied();
+
+        let pss = field("Pss").context("smaps_rollup doesn't contain Pss")?;
+        let uss = field("Private_Clean")
+            .unwrap_or_default()
+            .saturating_add(field("Private_Dirty").unwrap_or_default())
+            .saturating_add(field("Private_Hugetlb").unwrap_or_default());
+        let anonymous = field("Pss_Anon")
+            .or_else(|| field("Anonymous"))
+            .unwrap_or_default();
+        let file = field("Pss_File").unwrap_or_default();
+        let shmem = field("Pss_Shmem").unwrap_or_default();
+
+        Ok(Self {
+            pss,
+            uss,
+            anonymous,
+            file,
+            shmem,
+        })
+    }
+}
+
+/// Data of a single thread of a process, gathered from `/proc/<pid>/task/<tid>`.
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadData {
+    pub tid: libc::pid_t,
+    pub comm: String,
+    pub user_cpu_time: u64,
+    pub system_cpu_time: u64,
+    pub state: ProcessState,
+    pub last_cpu: usize,
+    pub niceness: Niceness,
+}
+
+impl ThreadData {
+    /// Parses the task directory at `task_path`, e.g. `/proc/<pid>/task/<tid>`.
+    pub fn try_from_path<P: AsRef<Path>>(task_path: P) -> Result<Self> {
+        let mut stat = String::new();
+        let mut comm = String::new();
+
+        Self::try_from_path_with_buffers(task_path, &mut stat, &mut comm)
+    }
+
+    /// Like `try_from_path()`, but reads the task's files into `stat` and `comm` instead of allocating new strings.
+    fn try_from_path_with_buffers<P: AsRef<Path>>(
+        task_path: P,
+        stat: &mut String,
+        comm: &mut String,
+    ) -> Result<Self> {
+        let task_path = task_path.as_ref();
+
+        read_into(task_path.join("stat"), stat)?;
+        read_into(task_path.join("comm"), comm)?;
+
+        let tid = task_path
+            .file_name()
+            .context("task_path terminates in ..")?
+            .to_str()
+            .context("can't turn OsStr to str")?
+            .parse()?;
+
+        let stat = stat
+            .rsplit_once(')')
+            .context("stat doesn't have ')'")?
+            .1
+            .split(' ')
+            .skip(1)
+            .collect::<Vec<_>>();
+
+        let comm = comm.trim_end_matches('\n').to_string();
+
+        let state = stat
+            .get(STAT_STATE)
+            .and_then(|x| x.chars().next())
+            .context("wrong stat file format")?
+            .into();
+        let user_cpu_time = stat
+            .get(STAT_USER_CPU_TIME)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let system_cpu_time = stat
+            .get(STAT_SYSTEM_CPU_TIME)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let niceness = stat
+            .get(STAT_NICE)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let last_cpu = stat
+            .get(STAT_PROCESSOR)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+
+        Ok(Self {
+            tid,
+            comm,
+            user_cpu_time,
+            system_cpu_time,
+            state,
+            last_cpu,
+            niceness,
+        })
+    }
 }
 
 /// Data that could be transferred using `resources-processes`, separated from
@@ -143,26 +897,140 @@ pub struct GpuUsageStats {
 pub struct ProcessData {
     pub pid: libc::pid_t,
     pub parent_pid: libc::pid_t,
+    pub state: ProcessState,
+    pub process_group: libc::pid_t,
+    pub session: libc::pid_t,
+    /// Name of the controlling terminal, e.g. `pts/0`
+    pub tty: Option<String>,
+    pub kernel_thread: bool,
+    pub num_threads: u64,
+    /// Real, effective and saved UID
+    pub uids: Ids,
+    /// Real, effective and saved GID
+    pub gids: Ids,
+    /// Name of the real user, or its UID if it has no name
     pub user: String,
+    /// Name of the effective user, or its UID if it has no name
+    pub effective_user: String,
+    /// Name of the real group, or its GID if it has no name
+    pub group: String,
+    /// Name of the effective group, or its GID if it has no name
+    pub effective_group: String,
     pub comm: String,
     pub commandline: String,
     pub user_cpu_time: u64,
     pub system_cpu_time: u64,
     pub niceness: Niceness,
+    pub scheduling_policy: SchedulingPolicy,
+    /// Between 1 and 99 for real-time policies, 0 otherwise
+    pub realtime_priority: u32,
+    /// `None` if it couldn't be determined, e.g. when reading from a procfs snapshot
+    pub io_priority: Option<IoPriority>,
     pub affinity: Vec<bool>,
     pub memory_usage: usize,
     pub swap_usage: usize,
+    /// `None` if `smaps_rollup` couldn't be read, e.g. for processes of other users
+    pub smaps_rollup: Option<SmapsRollup>,
     pub starttime: u64, // in clock ticks, see man proc(5)!
     pub cgroup: Option<String>,
+    /// Full cgroups v2 path, e.g. `/user.slice/user-1000.slice/session-2.scope`
+    pub cgroup_path: Option<String>,
     pub containerization: Containerization,
     pub read_bytes: Option<u64>,
     pub write_bytes: Option<u64>,
     pub timestamp: u64,
     /// Key: PCI Slot ID of the GPU
     pub gpu_usage_stats: BTreeMap<PciSlot, GpuUsageStats>,
+    pub threads: Vec<ThreadData>,
 }
 
 impl ProcessData {
+    /// Compares two `ProcessData` while disregarding the time they have been gathered at.
+    pub fn eq_ignoring_timestamp(&self, other: &Self) -> bool {
+        let Self {
+            pid,
+            parent_pid,
+            state,
+            process_group,
+            session,
+            tty,
+            kernel_thread,
+            num_threads,
+            uids,
+            gids,
+            user,
+            effective_user,
+            group,
+            effective_group,
+            comm,
+            commandline,
+            user_cpu_time,
+            system_cpu_time,
+            niceness,
+            scheduling_policy,
+            realtime_priority,
+            io_priority,
+            affinity,
+            memory_usage,
+            swap_usage,
+            smaps_rollup,
+            starttime,
+            cgroup,
+            cgroup_path,
+            containerization,
+            read_bytes,
+            write_bytes,
+            timestamp: _,
+            gpu_usage_stats,
+            threads,
+        } = self;
+
+        *pid == other.pid
+            && *parent_pid == other.parent_pid
+            && *state == other.state
+            && *process_group == other.process_group
+            && *session == other.session
+            && *tty == other.tty
+            && *kernel_thread == other.kernel_thread
+            && *num_threads == other.num_threads
+            && *uids == other.uids
+            && *gids == other.gids
+            && *user == other.user
+            && *effective_user == other.effective_user
+            && *group == other.group
+            && *effective_group == other.effective_group
+            && *comm == other.comm
+            && *commandline == other.commandline
+            && *user_cpu_time == other.user_cpu_time
+            && *system_cpu_time == other.system_cpu_time
+            && *niceness == other.niceness
+            && *scheduling_policy == other.scheduling_policy
+            && *realtime_priority == other.realtime_priority
+            && *io_priority == other.io_priority
+            && *affinity == other.affinity
+            && *memory_usage == other.memory_usage
+            && *swap_usage == other.swap_usage
+            && *smaps_rollup == other.smaps_rollup
+            && *starttime == other.starttime
+            && *cgroup == other.cgroup
+            && *cgroup_path == other.cgroup_path
+            && *containerization == other.containerization
+            && *read_bytes == other.read_bytes
+            && *write_bytes == other.write_bytes
+            && *gpu_usage_stats == other.gpu_usage_stats
+            && *threads == other.threads
+    }
+
+    /// Returns the cgroups v2 path of a cgroup file, e.g. `/system.slice/docker-<id>.scope` for the line
+    /// `0::/system.slice/docker-<id>.scope`
+    fn cgroup_v2_path<S: AsRef<str>>(cgroup: S) -> Option<String> {
+        cgroup
+            .as_ref()
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .map(str::to_string)
+    }
+
     fn sanitize_cgroup<S: AsRef<str>>(cgroup: S) -> Option<String> {
         let cgroups_v2_line = cgroup.as_ref().split('\n').find(|s| s.starts_with("0::"))?;
         if cgroups_v2_line.ends_with(".scope") {
@@ -196,19 +1064,47 @@ impl ProcessData {
         }
     }
 
-    fn get_uid(proc_path: &Path) -> Result<u32> {
-        let status = std::fs::read_to_string(proc_path.join("status"))?;
-        if let Some(captures) = RE_UID.captures(&status) {
-            let first_num_str = captures.get(1).context("no uid found")?;
-            first_num_str
-                .as_str()
-                .parse::<u32>()
-                .context("couldn't parse uid in /status")
-        } else {
-            Ok(0)
+    /// Turns the `tty_nr` field of `/proc/<pid>/stat` into the name of the terminal, see
+    /// Documentation/admin-guide/devices.txt in the kernel sources
+    fn tty_name(tty_nr: u32) -> Option<String> {
+        if tty_nr == 0 {
+            return None;
         }
+
+        let major = (tty_nr >> 8) & 0xfff;
+        let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+
+        Some(match major {
+            4 if minor < 64 => format!("tty{minor}"),
+            4 => format!("ttyS{}", minor - 64),
+            136..=143 => format!("pts/{}", (major - 136) * 256 + minor),
+            _ => format!("{major}:{minor}"),
+        })
+    }
+
+    fn threads(proc_path: &Path, buffers: &mut ScanBuffers) -> Vec<ThreadData> {
+        let Ok(entries) = std::fs::read_dir(proc_path.join("task")) else {
+            return Vec::new();
+        };
+
+        let mut threads: Vec<_> = entries
+            .flatten()
+            .filter_map(|entry| {
+                ThreadData::try_from_path_with_buffers(
+                    entry.path(),
+                    &mut buffers.task_stat,
+                    &mut buffers.task_comm,
+                )
+                .ok()
+            })
+            .collect();
+
+        threads.sort_unstable_by_key(|thread| thread.tid);
+
+        threads
     }
 
+    #[cfg(feature = "nvidia")]
     pub fn update_nvidia_stats() {
         {
             let mut stats = NVIDIA_PROCESSES_STATS.write().unwrap();
@@ -223,27 +1119,57 @@ impl ProcessData {
     }
 
     pub fn all_process_data() -> Result<Vec<Self>> {
-        Self::update_nvidia_stats();
+        Self::all_process_data_from(&ProcfsRoot::default())
+    }
 
-        let mut process_data = vec![];
-        for entry in glob("/proc/[0-9]*/").context("unable to glob")?.flatten() {
-            let data = ProcessData::try_from_path(&entry);
+    /// Collects the data of all processes that can be found within `procfs`.
+    ///
+    /// This uses a throwaway `ProcessScanner`. When collecting process data periodically, keep a `ProcessScanner`
+    /// around instead, so that it can reuse what it has read in previous scans.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the process directories of `procfs` can't be enumerated
+    pub fn all_process_data_from(procfs: &ProcfsRoot) -> Result<Vec<Self>> {
+        ProcessScanner::new(procfs.clone()).scan()
+    }
 
-            if let Ok(data) = data {
-                process_data.push(data);
-            }
-        }
+    pub fn try_from_path<P: AsRef<Path>>(proc_path: P) -> Result<Self> {
+        Self::try_from_procfs(&ProcfsRoot::default(), proc_path)
+    }
 
-        Ok(process_data)
+    /// Parses the process directory at `proc_path`, which is expected to be located within `procfs`.
+    pub fn try_from_procfs<P: AsRef<Path>>(procfs: &ProcfsRoot, proc_path: P) -> Result<Self> {
+        Self::scan(
+            procfs,
+            proc_path.as_ref(),
+            &mut ScanBuffers::default(),
+            None,
+            ProcessFields::ALL,
+        )
     }
 
-    pub fn try_from_path(proc_path: &PathBuf) -> Result<Self> {
-        let stat = std::fs::read_to_string(proc_path.join("stat"))?;
-        let statm = std::fs::read_to_string(proc_path.join("statm"))?;
-        let status = std::fs::read_to_string(proc_path.join("status"))?;
-        let comm = std::fs::read_to_string(proc_path.join("comm"))?;
-        let commandline = std::fs::read_to_string(proc_path.join("cmdline"))?;
-        let io = std::fs::read_to_string(proc_path.join("io")).ok();
+    /// Parses the process directory at `proc_path` using the read buffers of `buffers`. If `cached` belongs to the
+    /// same process (i.e. it has the same start time and name), its fields are used instead of reading them again.
+    /// Optional fields that aren't part of `fields` are left empty.
+    fn scan(
+        procfs: &ProcfsRoot,
+        proc_path: &Path,
+        buffers: &mut ScanBuffers,
+        cached: Option<&StaticProcessData>,
+        fields: ProcessFields,
+    ) -> Result<Self> {
+        read_into(proc_path.join("stat"), &mut buffers.stat)?;
+        read_into(proc_path.join("statm"), &mut buffers.statm)?;
+        read_into(proc_path.join("status"), &mut buffers.status)?;
+        read_into(proc_path.join("comm"), &mut buffers.comm)?;
+        let io = (fields.contains(ProcessFields::IO)
+            && read_into(proc_path.join("io"), &mut buffers.io).is_ok())
+        .then_some(buffers.io.as_str());
+
+        let stat = &buffers.stat;
+        let statm = &buffers.statm;
+        let status = &buffers.status;
 
         let pid = proc_path
             .file_name()
@@ -252,10 +1178,13 @@ impl ProcessData {
             .context("can't turn OsStr to str")?
             .parse()?;
 
-        let user = USERS_CACHE
-            .get(&Self::get_uid(proc_path)?)
-            .cloned()
-            .unwrap_or(String::from("root"));
+        let uids = Ids::from_status(status, "Uid")?;
+        let gids = Ids::from_status(status, "Gid")?;
+
+        let user = users::user_name(uids.real);
+        let effective_user = users::user_name(uids.effective);
+        let group = users::group_name(gids.real);
+        let effective_group = users::group_name(gids.effective);
 
         let stat = stat
             .split(')') // since we don't care about the pid or the executable name, split after the executable name to make our life easier
@@ -267,13 +1196,39 @@ impl ProcessData {
 
         let statm = statm.split(' ').collect::<Vec<_>>();
 
-        let comm = comm.replace('\n', "");
+        let comm = buffers.comm.trim_end_matches('\n').to_string();
 
         // -2 to accommodate for only collecting after the second item (which is the executable name as mentioned above)
         let parent_pid = stat
             .get(STAT_PARENT_PID)
             .context("wrong stat file format")
             .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let state = stat
+            .get(STAT_STATE)
+            .and_then(|x| x.chars().next())
+            .context("wrong stat file format")?
+            .into();
+        let process_group = stat
+            .get(STAT_PROCESS_GROUP)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let session = stat
+            .get(STAT_SESSION)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let tty = stat
+            .get(STAT_TTY)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse::<i32>().context("couldn't parse stat file content"))
+            .map(|tty_nr| Self::tty_name(tty_nr as u32))?;
+        let flags = stat
+            .get(STAT_FLAGS)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse::<u32>().context("couldn't parse stat file content"))?;
+        let num_threads = stat
+            .get(STAT_NUM_THREADS)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
         let user_cpu_time = stat
             .get(STAT_USER_CPU_TIME)
             .context("wrong stat file format")
@@ -286,6 +1241,15 @@ impl ProcessData {
             .get(STAT_NICE)
             .context("wrong stat file format")
             .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let realtime_priority = stat
+            .get(STAT_RT_PRIORITY)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let scheduling_policy = stat
+            .get(STAT_POLICY)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse::<u32>().context("couldn't parse stat file content"))?
+            .into();
         let starttime = stat
             .get(STAT_STARTTIME)
             .context("wrong stat file format")
@@ -293,7 +1257,7 @@ impl ProcessData {
 
         let mut affinity = Vec::with_capacity(*NUM_CPUS);
         RE_AFFINITY
-            .captures(&status)
+            .captures(status)
             .and_then(|captures| captures.get(1))
             .map(|capture| capture.as_str())
             .unwrap_or_default()
@@ -311,7 +1275,7 @@ impl ProcessData {
             });
 
         let swap_usage = RE_SWAP_USAGGE
-            .captures(&status)
+            .captures(status)
             .and_then(|captures| captures.get(1))
             .map(|capture| capture.as_str())
             .unwrap_or_default()
@@ -337,17 +1301,36 @@ impl ProcessData {
             )
             .saturating_mul(*PAGESIZE);
 
-        let cgroup = std::fs::read_to_string(proc_path.join("cgroup"))
-            .ok()
-            .and_then(|raw| Self::sanitize_cgroup(raw));
+        // the kernel has to walk through all memory mappings of the process for this, so it's rather expensive
+        let smaps_rollup = fields
+            .contains(ProcessFields::SMAPS_ROLLUP)
+            .then(|| std::fs::read_to_string(proc_path.join("smaps_rollup")).ok())
+            .flatten()
+            .and_then(|raw| SmapsRollup::parse(raw).ok());
 
-        let containerization = if commandline.starts_with("/snap/") {
-            Containerization::Snap
-        } else if proc_path.join("root").join(".flatpak-info").exists() {
-            Containerization::Flatpak
-        } else {
-            Containerization::None
-        };
+        let (commandline, cgroup, cgroup_path, containerization) =
+            match cached.filter(|cached| cached.starttime == starttime && cached.comm == comm) {
+                Some(cached) => (
+                    cached.commandline.clone(),
+                    cached.cgroup.clone(),
+                    cached.cgroup_path.clone(),
+                    cached.containerization.clone(),
+                ),
+                None => {
+                    let commandline = std::fs::read_to_string(proc_path.join("cmdline"))?;
+
+                    let raw_cgroup = std::fs::read_to_string(proc_path.join("cgroup")).ok();
+
+                    let cgroup = raw_cgroup.as_ref().and_then(Self::sanitize_cgroup);
+
+                    let cgroup_path = raw_cgroup.as_ref().and_then(Self::cgroup_v2_path);
+
+                    let containerization =
+                        Containerization::detect(proc_path, &commandline, cgroup_path.as_deref());
+
+                    (commandline, cgroup, cgroup_path, containerization)
+                }
+            };
 
         let read_bytes = io.as_ref().and_then(|io| {
             RE_IO_READ
@@ -363,68 +1346,84 @@ impl ProcessData {
                 .and_then(|capture| capture.as_str().parse::<u64>().ok())
         });
 
-        let gpu_usage_stats = Self::gpu_usage_stats(proc_path, pid);
+        // ioprio_get() asks the kernel about a live process, which makes no sense for a procfs snapshot
+        let io_priority = procfs.is_live().then(|| IoPriority::of(pid).ok()).flatten();
+
+        let mut gpu_usage_stats = BTreeMap::new();
+        if fields.contains(ProcessFields::GPU) {
+            gpu_usage_stats =
+                Self::drm_gpu_usage_stats(proc_path, pid, procfs.is_live(), &mut buffers.fdinfo)
+                    .unwrap_or_default();
+            #[cfg(feature = "nvidia")]
+            gpu_usage_stats.extend(Self::nvidia_gpu_stats_all(pid));
+        }
+
+        let threads = if fields.contains(ProcessFields::THREADS) {
+            Self::threads(proc_path, buffers)
+        } else {
+            Vec::new()
+        };
 
         let timestamp = unix_as_millis();
 
         Ok(Self {
             pid,
             parent_pid,
+            state,
+            process_group,
+            session,
+            tty,
+            kernel_thread: flags & PF_KTHREAD != 0,
+            num_threads,
+            uids,
+            gids,
             user,
+            effective_user,
+            group,
+            effective_group,
             comm,
             commandline,
             user_cpu_time,
             system_cpu_time,
             niceness: nice,
+            scheduling_policy,
+            realtime_priority,
+            io_priority,
             affinity,
             memory_usage,
             swap_usage,
+            smaps_rollup,
             starttime,
             cgroup,
+            cgroup_path,
             containerization,
             read_bytes,
             write_bytes,
             timestamp,
             gpu_usage_stats,
+            threads,
         })
     }
 
-    fn gpu_usage_stats(proc_path: &Path, pid: i32) -> BTreeMap<PciSlot, GpuUsageStats> {
-        let nvidia_stats = Self::nvidia_gpu_stats_all(pid);
-        let mut other_stats = Self::other_gpu_usage_stats(proc_path, pid).unwrap_or_default();
-        other_stats.extend(nvidia_stats);
-        other_stats
-    }
-
-    fn other_gpu_usage_stats(
+    fn drm_gpu_usage_stats(
         proc_path: &Path,
         pid: i32,
+        live_procfs: bool,
+        fdinfo_buffer: &mut String,
     ) -> Result<BTreeMap<PciSlot, GpuUsageStats>> {
+        let fd_dir = proc_path.join("fd");
         let fdinfo_dir = proc_path.join("fdinfo");
 
         let mut seen_fds = HashSet::new();
 
         let mut return_map = BTreeMap::new();
-        for entry in std::fs::read_dir(fdinfo_dir)? {
+        for entry in std::fs::read_dir(&fd_dir)? {
             let entry = entry?;
-            let fdinfo_path = entry.path();
-
-            let _file = std::fs::File::open(&fdinfo_path);
-            if _file.is_err() {
-                continue;
-            }
-            let mut file = _file.unwrap();
-
-            let _metadata = file.metadata();
-            if _metadata.is_err() {
-                continue;
-            }
-            let metadata = _metadata.unwrap();
+            let fd_name = entry.file_name();
 
             // if our fd is 0, 1 or 2 it's probably just a std stream so skip it
-            let fd_num = fdinfo_path
-                .file_name()
-                .and_then(|osstr| osstr.to_str())
+            let fd_num = fd_name
+                .to_str()
                 .unwrap_or("0")
                 .parse::<usize>()
                 .unwrap_or(0);
@@ -432,145 +1431,48 @@ impl ProcessData {
                 continue;
             }
 
-            if !metadata.is_file() {
-                continue;
-            }
-
             // Adapted from nvtop's `is_drm_fd()`
             // https://github.com/Syllo/nvtop/blob/master/src/extract_processinfo_fdinfo.c
-            let fd_path = fdinfo_path.to_str().map(|s| s.replace("fdinfo", "fd"));
-            if let Some(fd_path) = fd_path {
-                if let Ok(fd_metadata) = std::fs::metadata(fd_path) {
-                    let major = unsafe { libc::major(fd_metadata.st_rdev()) };
-                    if (fd_metadata.st_mode() & libc::S_IFMT) != libc::S_IFCHR || major != 226 {
-                        continue;
-                    }
+            // checking the fd itself first saves us from reading the fdinfo of every fd that isn't a DRM device
+            if let Ok(fd_metadata) = std::fs::metadata(entry.path()) {
+                let major = unsafe { libc::major(fd_metadata.st_rdev()) };
+                if (fd_metadata.st_mode() & libc::S_IFMT) != libc::S_IFCHR || major != 226 {
+                    continue;
                 }
             }
 
             // Adapted from nvtop's `processinfo_sweep_fdinfos()`
             // https://github.com/Syllo/nvtop/blob/master/src/extract_processinfo_fdinfo.c
             // if we've already seen the file this fd refers to, skip
-            let not_unique = seen_fds.iter().any(|seen_fd| unsafe {
-                syscalls::syscall!(syscalls::Sysno::kcmp, pid, pid, 0, fd_num, *seen_fd)
-                    .unwrap_or(0)
-                    == 0
-            });
+            // kcmp only makes sense for PIDs of our own kernel, for other procfs roots we rely on merging the stats
+            let not_unique = live_procfs
+                && seen_fds.iter().any(|seen_fd| unsafe {
+                    syscalls::syscall!(syscalls::Sysno::kcmp, pid, pid, 0, fd_num, *seen_fd)
+                        .unwrap_or(0)
+                        == 0
+                });
             if not_unique {
                 continue;
             }
 
             seen_fds.insert(fd_num);
 
-            if let Ok(stats) = Self::read_fdinfo(&mut file, metadata.len() as usize) {
+            if read_into(fdinfo_dir.join(&fd_name), fdinfo_buffer).is_err() {
+                continue;
+            }
+
+            if let Ok((pci_slot, stats, _)) = GpuUsageStats::parse_fdinfo(&fdinfo_buffer) {
                 return_map
-                    .entry(stats.0)
-                    .and_modify(|existing_value: &mut GpuUsageStats| {
-                        if stats.1.gfx > existing_value.gfx {
-                            existing_value.gfx = stats.1.gfx;
-                        }
-                        if stats.1.dec > existing_value.dec {
-                            existing_value.dec = stats.1.dec;
-                        }
-                        if stats.1.enc > existing_value.enc {
-                            existing_value.enc = stats.1.enc;
-                        }
-                        if stats.1.mem > existing_value.mem {
-                            existing_value.mem = stats.1.mem;
-                        }
-                    })
-                    .or_insert(stats.1);
+                    .entry(pci_slot)
+                    .or_insert_with(GpuUsageStats::default)
+                    .merge_max(stats);
             }
         }
 
         Ok(return_map)
     }
 
-    fn read_fdinfo(
-        fdinfo_file: &mut File,
-        file_size: usize,
-    ) -> Result<(PciSlot, GpuUsageStats, i64)> {
-        let mut content = String::with_capacity(file_size);
-        fdinfo_file.read_to_string(&mut content)?;
-        fdinfo_file.flush()?;
-
-        let pci_slot = RE_DRM_PDEV
-            .captures(&content)
-            .and_then(|captures| captures.get(1))
-            .and_then(|capture| PciSlot::from_str(capture.as_str()).ok());
-
-        let client_id = RE_DRM_CLIENT_ID
-            .captures(&content)
-            .and_then(|captures| captures.get(1))
-            .and_then(|capture| capture.as_str().parse::<i64>().ok());
-
-        if let (Some(pci_slot), Some(client_id)) = (pci_slot, client_id) {
-            let gfx = RE_DRM_ENGINE_GFX // amd
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .or_else(|| {
-                    // intel
-                    RE_DRM_ENGINE_RENDER
-                        .captures(&content)
-                        .and_then(|captures| captures.get(1))
-                        .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                })
-                .unwrap_or_default();
-
-            let compute = RE_DRM_ENGINE_COMPUTE
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default();
-
-            let enc = RE_DRM_ENGINE_ENC // amd
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .or_else(|| {
-                    // intel
-                    RE_DRM_ENGINE_VIDEO
-                        .captures(&content)
-                        .and_then(|captures| captures.get(1))
-                        .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                })
-                .unwrap_or_default();
-
-            let dec = RE_DRM_ENGINE_DEC
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default();
-
-            let vram = RE_DRM_MEMORY_VRAM
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default()
-                .saturating_mul(1024);
-
-            let gtt = RE_DRM_MEMORY_GTT
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default()
-                .saturating_mul(1024);
-
-            let stats = GpuUsageStats {
-                gfx: gfx.saturating_add(compute),
-                mem: vram.saturating_add(gtt),
-                enc,
-                dec,
-                nvidia: false,
-            };
-
-            return Ok((pci_slot, stats, client_id));
-        }
-
-        bail!("unable to find gpu information in this fdinfo");
-    }
-
+    #[cfg(feature = "nvidia")]
     fn nvidia_gpu_stats_all(pid: i32) -> BTreeMap<PciSlot, GpuUsageStats> {
         let mut return_map = BTreeMap::new();
 
@@ -583,6 +1485,7 @@ impl ProcessData {
         return_map
     }
 
+    #[cfg(feature = "nvidia")]
     fn nvidia_gpu_stats(pid: i32, pci_slot: PciSlot) -> Result<GpuUsageStats> {
         let this_process_stats = NVIDIA_PROCESSES_STATS
             .read()
@@ -607,16 +1510,21 @@ impl ProcessData {
             })
             .sum();
 
+        let (gfx, enc, dec) = this_process_stats.unwrap_or_default();
+
         let gpu_stats = GpuUsageStats {
-            gfx: this_process_stats.unwrap_or_default().0 as u64,
-            mem: this_process_mem_stats,
-            enc: this_process_stats.unwrap_or_default().1 as u64,
-            dec: this_process_stats.unwrap_or_default().2 as u64,
-            nvidia: true,
+            engines: BTreeMap::from([
+                (String::from("gfx"), GpuEngineUsage::Percentage(gfx as u64)),
+                (String::from("enc"), GpuEngineUsage::Percentage(enc as u64)),
+                (String::from("dec"), GpuEngineUsage::Percentage(dec as u64)),
+            ]),
+            memory_total: BTreeMap::new(),
+            memory_resident: BTreeMap::from([(String::from("vram"), this_process_mem_stats)]),
         };
         Ok(gpu_stats)
     }
 
+    #[cfg(feature = "nvidia")]
     fn nvidia_process_infos() -> HashMap<PciSlot, Vec<ProcessInfo>> {
         let mut return_map = HashMap::new();
 
@@ -630,6 +1538,7 @@ impl ProcessData {
         return_map
     }
 
+    #[cfg(feature = "nvidia")]
     fn nvidia_process_stats() -> HashMap<PciSlot, Vec<ProcessUtilizationSample>> {
         let mut return_map = HashMap::new();
 
@@ -655,3 +1564,509 @@ pub fn unix_as_millis() -> u64 {
         .unwrap()
         .as_millis() as u64
 }
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        procfs::ProcfsRoot, users::Ids, Containerization, GpuEngineClass, GpuEngineUsage,
+        GpuUsageStats, IoPriority, IoPriorityClass, PciSlot, ProcessData, ProcessState,
+        SchedulingPolicy, SmapsRollup, ThreadData,
+    };
+
+    fn fixture_procfs() -> ProcfsRoot {
+        ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"))
+    }
+
+    fn fixture_process(pid: libc::pid_t) -> ProcessData {
+        let procfs = fixture_procfs();
+        ProcessData::try_from_procfs(&procfs, procfs.process_path(pid)).unwrap()
+    }
+
+    #[test]
+    fn tty_names() {
+        assert_eq!(None, ProcessData::tty_name(0));
+        assert_eq!(Some("tty1".to_string()), ProcessData::tty_name(0x0401));
+        assert_eq!(Some("ttyS0".to_string()), ProcessData::tty_name(0x0440));
+        assert_eq!(Some("pts/3".to_string()), ProcessData::tty_name(0x8803));
+        assert_eq!(Some("pts/300".to_string()), ProcessData::tty_name(0x892c));
+    }
+
+    #[test]
+    fn containerization_from_cgroup_path() {
+        let container = |containerization: Containerization, cgroup: &str| {
+            Some((containerization, cgroup.to_string()))
+        };
+
+        assert_eq!(
+            container(
+                Containerization::Podman(Some("0123456789ab".into())),
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-0123456789abcdef.scope"
+            ),
+            Containerization::from_cgroup_path(
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-0123456789abcdef.scope/container"
+            )
+        );
+        assert_eq!(
+            None,
+            Containerization::from_cgroup_path(
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-conmon-0123456789abcdef.scope"
+            )
+        );
+        assert_eq!(
+            container(
+                Containerization::Docker(Some("fedcba987654".into())),
+                "/system.slice/docker-fedcba9876543210.scope"
+            ),
+            Containerization::from_cgroup_path("/system.slice/docker-fedcba9876543210.scope")
+        );
+        assert_eq!(
+            container(
+                Containerization::Docker(Some("fedcba987654".into())),
+                "/docker/fedcba9876543210"
+            ),
+            Containerization::from_cgroup_path("/docker/fedcba9876543210")
+        );
+        assert_eq!(
+            container(
+                Containerization::Lxc(Some("web".into())),
+                "/lxc.payload.web"
+            ),
+            Containerization::from_cgroup_path("/lxc.payload.web/system.slice/nginx.service")
+        );
+        assert_eq!(
+            container(
+                Containerization::Nspawn(Some("debian-sid".into())),
+                "/machine.slice/systemd-nspawn@debian\\x2dsid.service"
+            ),
+            Containerization::from_cgroup_path(
+                "/machine.slice/systemd-nspawn@debian\\x2dsid.service/payload"
+            )
+        );
+        assert_eq!(
+            container(
+                Containerization::Nspawn(Some("arch".into())),
+                "/machine.slice/machine-arch.scope"
+            ),
+            Containerization::from_cgroup_path("/machine.slice/machine-arch.scope/payload")
+        );
+        assert_eq!(
+            None,
+            Containerization::from_cgroup_path(
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-org.gnome.Nautilus-5678.scope"
+            )
+        );
+    }
+
+    #[test]
+    fn containerization_metadata_files() {
+        let containerenv = "engine=\"podman-5.2.2\"\nname=\"fedora-toolbox-41\"\nid=\"0123456789abcdef\"\nrootless=1\n";
+        assert_eq!(
+            Some("fedora-toolbox-41".to_string()),
+            Containerization::containerenv_value(containerenv, "name")
+        );
+        assert_eq!(
+            None,
+            Containerization::containerenv_value(containerenv, "image")
+        );
+
+        let flatpak_info = "[Application]\nname=org.gnome.Nautilus\nruntime=runtime/org.gnome.Platform/x86_64/47\n\n[Instance]\nname=other\n";
+        assert_eq!(
+            Some("org.gnome.Nautilus".to_string()),
+            Containerization::ini_value(flatpak_info, "Application", "name")
+        );
+        assert_eq!(
+            None,
+            Containerization::ini_value(flatpak_info, "Application", "branch")
+        );
+    }
+
+    #[test]
+    fn fixture_procfs_is_not_live() {
+        assert!(!fixture_procfs().is_live());
+        assert!(ProcfsRoot::default().is_live());
+    }
+
+    #[test]
+    fn all_fixture_processes() {
+        let mut pids: Vec<_> = ProcessData::all_process_data_from(&fixture_procfs())
+            .unwrap()
+            .into_iter()
+            .map(|process| process.pid)
+            .collect();
+        pids.sort_unstable();
+
+        assert_eq!(vec![2, 1234], pids);
+    }
+
+    #[test]
+    fn user_process_from_fixture() {
+        let process = fixture_process(1234);
+
+        assert_eq!(1234, process.pid);
+        assert_eq!(1, process.parent_pid);
+        assert_eq!(ProcessState::Sleeping, process.state);
+        assert_eq!(1234, process.process_group);
+        assert_eq!(1234, process.session);
+        assert_eq!(Some("pts/0".to_string()), process.tty);
+        assert!(!process.kernel_thread);
+        assert_eq!(2, process.num_threads);
+        assert_eq!(
+            Ids {
+                real: 1000,
+                effective: 1000,
+                saved: 1000,
+            },
+            process.uids
+        );
+        assert_eq!(process.uids, process.gids);
+        assert_eq!("worker (1)", process.comm);
+        assert_eq!("/usr/bin/worker\0--threads\x004\0", process.commandline);
+        assert_eq!(150, process.user_cpu_time);
+        assert_eq!(75, process.system_cpu_time);
+        assert_eq!(5, *process.niceness);
+        assert_eq!(SchedulingPolicy::Batch, process.scheduling_policy);
+        assert_eq!(0, process.realtime_priority);
+        assert_eq!(None, process.io_priority);
+        assert_eq!(123456, process.starttime);
+        assert_eq!((2560 - 512) * *super::PAGESIZE, process.memory_usage);
+        assert_eq!(1024 * 1000, process.swap_usage);
+        assert!(process.affinity.iter().all(|allowed| *allowed));
+        assert_eq!(Some(4096), process.read_bytes);
+        assert_eq!(Some(8192), process.write_bytes);
+        assert_eq!(Some("org.gnome.Nautilus".to_string()), process.cgroup);
+        assert_eq!(
+            Some(
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-org.gnome.Nautilus-5678.scope"
+                    .to_string()
+            ),
+            process.cgroup_path
+        );
+        assert_eq!(Containerization::None, process.containerization);
+    }
+
+    #[test]
+    fn smaps_rollup_from_fixture() {
+        let process = fixture_process(1234);
+
+        assert_eq!(
+            Some(SmapsRollup {
+                pss: 5120 * 1024,
+                uss: (1024 + 3584) * 1024,
+                anonymous: 3072 * 1024,
+                file: 1536 * 1024,
+                shmem: 512 * 1024,
+            }),
+            process.smaps_rollup
+        );
+    }
+
+    #[test]
+    fn smaps_rollup_without_pss_breakdown() {
+        let smaps_rollup = SmapsRollup::parse(
+            "00400000-7ffd3e5fe000 ---p 00000000 00:00 0 [rollup]\n\
+             Rss: 300 kB\n\
+             Pss: 200 kB\n\
+             Private_Clean: 10 kB\n\
+             Private_Dirty: 90 kB\n\
+             Anonymous: 150 kB\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            SmapsRollup {
+                pss: 200 * 1024,
+                uss: 100 * 1024,
+                anonymous: 150 * 1024,
+                file: 0,
+                shmem: 0,
+            },
+            smaps_rollup
+        );
+
+        assert!(SmapsRollup::parse("").is_err());
+    }
+
+    #[test]
+    fn gpu_usage_from_fixture_fdinfo() {
+        let process = fixture_process(1234);
+
+        let pci_slot = PciSlot {
+            domain: 0,
+            bus: 3,
+            number: 0,
+            function: 0,
+        };
+
+        assert_eq!(1, process.gpu_usage_stats.len());
+        assert_eq!(
+            Some(&GpuUsageStats {
+                engines: BTreeMap::from([
+                    (String::from("gfx"), GpuEngineUsage::Time(1_000_000)),
+                    (String::from("compute"), GpuEngineUsage::Time(500_000)),
+                    (String::from("enc"), GpuEngineUsage::Time(2000)),
+                    (String::from("dec"), GpuEngineUsage::Time(3000)),
+                ]),
+                memory_total: BTreeMap::new(),
+                memory_resident: BTreeMap::from([
+                    (String::from("vram"), 1024 * 1024),
+                    (String::from("gtt"), 512 * 1024),
+                ]),
+            }),
+            process.gpu_usage_stats.get(&pci_slot)
+        );
+        assert_eq!(
+            (1024 + 512) * 1024,
+            process.gpu_usage_stats[&pci_slot].memory()
+        );
+    }
+
+    #[test]
+    fn gpu_usage_merge_keeps_maximum() {
+        // without kcmp, fds that refer to the same DRM file are only recognizable by reporting the same stats, so
+        // merging must not add them up
+        let mut stats = GpuUsageStats {
+            engines: BTreeMap::from([
+                (String::from("gfx"), GpuEngineUsage::Time(1000)),
+                (String::from("enc"), GpuEngineUsage::Time(200)),
+            ]),
+            memory_total: BTreeMap::from([(String::from("vram"), 4096)]),
+            memory_resident: BTreeMap::from([(String::from("vram"), 2048)]),
+        };
+        stats.merge_max(stats.clone());
+        stats.merge_max(GpuUsageStats {
+            engines: BTreeMap::from([
+                (String::from("gfx"), GpuEngineUsage::Time(500)),
+                (String::from("enc"), GpuEngineUsage::Time(300)),
+                (String::from("dec"), GpuEngineUsage::Time(100)),
+            ]),
+            memory_total: BTreeMap::from([(String::from("gtt"), 1024)]),
+            memory_resident: BTreeMap::from([(String::from("vram"), 3072)]),
+        });
+
+        assert_eq!(
+            GpuUsageStats {
+                engines: BTreeMap::from([
+                    (String::from("gfx"), GpuEngineUsage::Time(1000)),
+                    (String::from("enc"), GpuEngineUsage::Time(300)),
+                    (String::from("dec"), GpuEngineUsage::Time(100)),
+                ]),
+                memory_total: BTreeMap::from([
+                    (String::from("vram"), 4096),
+                    (String::from("gtt"), 1024),
+                ]),
+                memory_resident: BTreeMap::from([(String::from("vram"), 3072)]),
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn gpu_usage_from_xe_fdinfo() {
+        let (pci_slot, stats, client_id) = GpuUsageStats::parse_fdinfo(
+            "pos:\t0\nflags:\t02100002\ndrm-driver:\txe\ndrm-client-id:\t7\ndrm-pdev:\t0000:00:02.0\n\
+             drm-total-system:\t12 MiB\ndrm-shared-system:\t4 MiB\ndrm-resident-system:\t8 MiB\n\
+             drm-total-vram0:\t1024\ndrm-cycles-rcs:\t28257900\ndrm-total-cycles-rcs:\t7655183225\n\
+             drm-cycles-vcs:\t100\ndrm-total-cycles-vcs:\t7655183225\ndrm-engine-capacity-vcs:\t2\n\
+             drm-cycles-bcs:\t0\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            PciSlot {
+                domain: 0,
+                bus: 0,
+                number: 2,
+                function: 0,
+            },
+            pci_slot
+        );
+        assert_eq!(7, client_id);
+        assert_eq!(
+            BTreeMap::from([
+                (
+                    String::from("rcs"),
+                    GpuEngineUsage::Cycles {
+                        busy: 28_257_900,
+                        total: 7_655_183_225,
+                    }
+                ),
+                (
+                    String::from("vcs"),
+                    GpuEngineUsage::Cycles {
+                        busy: 100,
+                        total: 7_655_183_225,
+                    }
+                ),
+            ]),
+            stats.engines
+        );
+        assert_eq!(
+            BTreeMap::from([
+                (String::from("system"), 12 * 1024 * 1024),
+                (String::from("vram0"), 1024),
+            ]),
+            stats.memory_total
+        );
+        assert_eq!(8 * 1024 * 1024, stats.memory());
+    }
+
+    #[test]
+    fn gpu_engine_fractions() {
+        let previous = GpuEngineUsage::Time(1_000_000_000);
+        let current = GpuEngineUsage::Time(1_500_000_000);
+        assert_eq!(0.5, current.fraction_since(Some(&previous), 1000));
+        assert_eq!(0.0, current.fraction_since(None, 1000));
+
+        let previous = GpuEngineUsage::Cycles {
+            busy: 100,
+            total: 1000,
+        };
+        let current = GpuEngineUsage::Cycles {
+            busy: 350,
+            total: 2000,
+        };
+        assert_eq!(0.25, current.fraction_since(Some(&previous), 1000));
+
+        assert_eq!(0.42, GpuEngineUsage::Percentage(42).fraction_since(None, 0));
+
+        assert_eq!(
+            GpuEngineClass::Graphics,
+            GpuEngineClass::from_engine_name("render")
+        );
+        assert_eq!(
+            GpuEngineClass::Graphics,
+            GpuEngineClass::from_engine_name("fragment")
+        );
+        assert_eq!(
+            GpuEngineClass::Media,
+            GpuEngineClass::from_engine_name("vcs")
+        );
+        assert_eq!(
+            GpuEngineClass::Encode,
+            GpuEngineClass::from_engine_name("enc_1")
+        );
+        assert_eq!(
+            GpuEngineClass::Other,
+            GpuEngineClass::from_engine_name("video-enhance")
+        );
+        assert_eq!(
+            GpuEngineClass::Other,
+            GpuEngineClass::from_engine_name("copy")
+        );
+    }
+
+    #[test]
+    fn threads_from_fixture() {
+        let process = fixture_process(1234);
+
+        assert_eq!(
+            vec![
+                ThreadData {
+                    tid: 1234,
+                    comm: "worker (1)".into(),
+                    user_cpu_time: 150,
+                    system_cpu_time: 75,
+                    state: ProcessState::Sleeping,
+                    last_cpu: 3,
+                    niceness: 5.try_into().unwrap(),
+                },
+                ThreadData {
+                    tid: 1240,
+                    comm: "tokio-rt-1".into(),
+                    user_cpu_time: 400,
+                    system_cpu_time: 20,
+                    state: ProcessState::Running,
+                    last_cpu: 1,
+                    niceness: 5.try_into().unwrap(),
+                },
+            ],
+            process.threads
+        );
+    }
+
+    #[test]
+    fn scheduling_policy_from_raw() {
+        assert_eq!(SchedulingPolicy::Other, SchedulingPolicy::from(0));
+        assert_eq!(SchedulingPolicy::Fifo, SchedulingPolicy::from(1));
+        assert_eq!(SchedulingPolicy::RoundRobin, SchedulingPolicy::from(2));
+        assert_eq!(SchedulingPolicy::Batch, SchedulingPolicy::from(3));
+        assert_eq!(SchedulingPolicy::Idle, SchedulingPolicy::from(5));
+        assert_eq!(SchedulingPolicy::Deadline, SchedulingPolicy::from(6));
+        assert_eq!(SchedulingPolicy::Unknown, SchedulingPolicy::from(4));
+
+        assert_eq!(Some(libc::SCHED_FIFO), SchedulingPolicy::Fifo.as_raw());
+        assert_eq!(None, SchedulingPolicy::Deadline.as_raw());
+        assert!(SchedulingPolicy::RoundRobin.is_realtime());
+        assert!(!SchedulingPolicy::Batch.is_realtime());
+    }
+
+    #[test]
+    fn io_priority_from_raw() {
+        let best_effort = IoPriority {
+            class: IoPriorityClass::BestEffort,
+            level: 4,
+        };
+        assert_eq!(Some(best_effort), IoPriority::from_raw(2 << 13 | 4));
+        assert_eq!(2 << 13 | 4, best_effort.as_raw());
+
+        let idle = IoPriority {
+            class: IoPriorityClass::Idle,
+            level: 0,
+        };
+        assert_eq!(Some(idle), IoPriority::from_raw(3 << 13));
+        assert_eq!(
+            3 << 13,
+            IoPriority {
+                class: IoPriorityClass::Idle,
+                level: 5,
+            }
+            .as_raw()
+        );
+
+        assert_eq!(Some(IoPriority::default()), IoPriority::from_raw(0));
+        assert_eq!(None, IoPriority::from_raw(4 << 13));
+    }
+
+    #[test]
+    fn io_priority_from_raw_with_hint() {
+        // a hint in bits 3 to 12 must neither change the level nor the class
+        assert_eq!(
+            Some(IoPriority {
+                class: IoPriorityClass::RealTime,
+                level: 2,
+            }),
+            IoPriority::from_raw(1 << 13 | 5 << 3 | 2)
+        );
+        assert_eq!(
+            Some(IoPriority {
+                class: IoPriorityClass::BestEffort,
+                level: 0,
+            }),
+            IoPriority::from_raw(2 << 13 | 0x3ff << 3)
+        );
+    }
+
+    #[test]
+    fn kernel_thread_from_fixture() {
+        let process = fixture_process(2);
+
+        assert_eq!(2, process.pid);
+        assert_eq!(0, process.parent_pid);
+        assert_eq!(None, process.tty);
+        assert!(process.kernel_thread);
+        assert_eq!("kthreadd", process.comm);
+        assert_eq!("", process.commandline);
+        assert_eq!(SchedulingPolicy::Other, process.scheduling_policy);
+        assert_eq!(0, process.memory_usage);
+        assert_eq!(None, process.smaps_rollup);
+        assert_eq!(None, process.read_bytes);
+        assert_eq!(None, process.write_bytes);
+        assert_eq!(None, process.cgroup);
+        assert!(process.gpu_usage_stats.is_empty());
+        assert_eq!(1, process.threads.len());
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::procfs::ProcfsRoot;
+
+/// A single memory mapping of a process, gathered from `/proc/<pid>/maps` and `/proc/<pid>/smaps`.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryMapping {
+    pub start: u64,
+    pub end: u64,
+    /// Permissions as displayed by the kernel, e.g. `r-xp`
+    pub permissions: String,
+    pub offset: u64,
+    pub inode: u64,
+    /// Backing file or pseudo-path like `[heap]`, `None` for anonymous mappings
+    pub pathname: Option<String>,
+    /// Resident set size in bytes, `None` if smaps couldn't be read
+    pub rss: Option<usize>,
+    /// Proportional set size in bytes, `None` if smaps couldn't be read
+    pub pss: Option<usize>,
+}
+
+/// Memory of all mappings that share the same backing file or pseudo-path, all values are in bytes.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryMappingTotals {
+    /// `None` for anonymous mappings
+    pub pathname: Option<String>,
+    pub mappings: usize,
+    pub size: u64,
+    pub rss: usize,
+    pub pss: usize,
+}
+
+impl MemoryMapping {
+    pub fn size(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Returns all memory mappings of the process with the given PID, sorted by their address.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the maps file of the process can't be read, usually because the process belongs to another
+    /// user or has exited in the meantime.
+    pub fn all_from_procfs(procfs: &ProcfsRoot, pid: libc::pid_t) -> Result<Vec<Self>> {
+        let proc_path = procfs.process_path(pid);
+
+        let maps = std::fs::read_to_string(proc_path.join("maps"))
+            .with_context(|| format!("unable to read memory maps of process {pid}"))?;
+
+        // smaps is way more expensive to generate for the kernel, but it's the only way to get per-mapping memory usage
+        let smaps_usage = std::fs::read_to_string(proc_path.join("smaps"))
+            .map(|smaps| Self::parse_smaps_usage(&smaps))
+            .unwrap_or_default();
+
+        let mut mappings = maps
+            .lines()
+            .filter_map(|line| Self::parse_maps_line(line).ok())
+            .map(|mut mapping| {
+                if let Some((rss, pss)) = smaps_usage.get(&(mapping.start, mapping.end)) {
+                    mapping.rss = Some(*rss);
+                    mapping.pss = Some(*pss);
+                }
+                mapping
+            })
+            .collect::<Vec<_>>();
+
+        mappings.sort_by_key(|mapping| mapping.start);
+
+        Ok(mappings)
+    }
+
+    /// Sums up the mappings that share the same backing file or pseudo-path, sorted by their PSS in descending order.
+    pub fn totals_by_pathname(mappings: &[Self]) -> Vec<MemoryMappingTotals> {
+        let mut totals: BTreeMap<Option<&str>, MemoryMappingTotals> = BTreeMap::new();
+
+        for mapping in mappings {
+            let entry = totals
+                .entry(mapping.pathname.as_deref())
+                .or_insert_with(|| MemoryMappingTotals {
+                    pathname: mapping.pathname.clone(),
+                    ..Default::default()
+                });
+
+            entry.mappings += 1;
+            entry.size = entry.size.saturating_add(mapping.size());
+            entry.rss = entry.rss.saturating_add(mapping.rss.unwrap_or_default());
+            entry.pss = entry.pss.saturating_add(mapping.pss.unwrap_or_default());
+        }
+
+        let mut totals = totals.into_values().collect::<Vec<_>>();
+        totals.sort_by(|a, b| b.pss.cmp(&a.pss).then_with(|| b.size.cmp(&a.size)));
+        totals
+    }
+
+    /// Parses a line of a maps file, e.g.
+    /// `7f2c4a5e5000-7f2c4a60b000 r--p 00000000 103:02 1839202                   /usr/lib/libc.so.6`
+    fn parse_maps_line<S: AsRef<str>>(line: S) -> Result<Self> {
+        // the pathname may contain spaces itself, so only split off the first five fields
+        let mut fields = line.as_ref().splitn(6, ' ');
+
+        let (start, end) = fields
+            .next()
+            .and_then(|range| range.split_once('-'))
+            .context("wrong maps file format")?;
+        let start = u64::from_str_radix(start, 16).context("couldn't parse maps file content")?;
+        let end = u64::from_str_radix(end, 16).context("couldn't parse maps file content")?;
+
+        let permissions = fields.next().context("wrong maps file format")?.to_string();
+
+        let offset = fields
+            .next()
+            .context("wrong maps file format")
+            .and_then(|x| u64::from_str_radix(x, 16).context("couldn't parse maps file content"))?;
+
+        let _device = fields.next().context("wrong maps file format")?;
+
+        let inode = fields
+            .next()
+            .context("wrong maps file format")
+            .and_then(|x| x.parse().context("couldn't parse maps file content"))?;
+
+        let pathname = fields
+            .next()
+            .map(str::trim_start)
+            .filter(|pathname| !pathname.is_empty())
+            .map(str::to_string);
+
+        Ok(Self {
+            start,
+            end,
+            permissions,
+            offset,
+            inode,
+            pathname,
+            rss: None,
+            pss: None,
+        })
+    }
+
+    /// Returns the RSS and PSS (in bytes) of every mapping in a smaps file, keyed by the address range of the mapping.
+    fn parse_smaps_usage<S: AsRef<str>>(smaps: S) -> HashMap<(u64, u64), (usize, usize)> {
+        let mut usage = HashMap::new();
+        let mut current = None;
+
+        for line in smaps.as_ref().lines() {
+            if let Ok(mapping) = Self::parse_maps_line(line) {
+                current = Some((mapping.start, mapping.end));
+                usage.insert((mapping.start, mapping.end), (0, 0));
+                continue;
+            }
+
+            let (Some(range), Some((key, value))) = (current, line.split_once(':')) else {
+                continue;
+            };
+
+            let Some(bytes) = value
+                .trim()
+                .strip_suffix("kB")
+                .and_then(|kib| kib.trim().parse::<usize>().ok())
+                .map(|kib| kib.saturating_mul(1024))
+            else {
+                continue;
+            };
+
+            if let Some((rss, pss)) = usage.get_mut(&range) {
+                match key {
+                    "Rss" => *rss = bytes,
+                    "Pss" => *pss = bytes,
+                    _ => {}
+                }
+            }
+        }
+
+        usage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::procfs::ProcfsRoot;
+
+    use super::{MemoryMapping, MemoryMappingTotals};
+
+    #[test]
+    fn memory_maps_from_fixture() {
+        let procfs =
+            ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"));
+
+        let mappings = MemoryMapping::all_from_procfs(&procfs, 1234).unwrap();
+
+        assert_eq!(6, mappings.len());
+        assert_eq!(
+            MemoryMapping {
+                start: 0x7f2c4a5e5000,
+                end: 0x7f2c4a60b000,
+                permissions: "r--p".into(),
+                offset: 0,
+                inode: 1839202,
+                pathname: Some("/usr/lib/libc.so.6".into()),
+                rss: Some(152 * 1024),
+                pss: Some(12 * 1024),
+            },
+            mappings[2]
+        );
+        assert_eq!(None, mappings[1].pathname);
+        assert_eq!(
+            Some("/home/user/My Files/data.bin".into()),
+            mappings[4].pathname
+        );
+        // not present in the fixture's smaps
+        assert_eq!(None, mappings[5].rss);
+    }
+
+    #[test]
+    fn totals_from_fixture() {
+        let procfs =
+            ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"));
+
+        let mappings = MemoryMapping::all_from_procfs(&procfs, 1234).unwrap();
+        let totals = MemoryMapping::totals_by_pathname(&mappings);
+
+        assert_eq!(
+            MemoryMappingTotals {
+                pathname: Some("/usr/lib/libc.so.6".into()),
+                mappings: 2,
+                size: 0x26000 + 0x155000,
+                rss: (152 + 1300) * 1024,
+                pss: (12 + 80) * 1024,
+            },
+            totals[1]
+        );
+        assert_eq!(None, totals[0].pathname);
+    }
+}
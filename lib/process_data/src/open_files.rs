@@ -0,0 +1,176 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::procfs::ProcfsRoot;
+
+/// What kind of object a file descriptor refers to, derived from the target of its `/proc/<pid>/fd` link.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileDescriptorKind {
+    #[default]
+    File,
+    Socket,
+    Pipe,
+    /// A file without a corresponding inode on any filesystem, e.g. an eventfd or a timerfd
+    AnonInode,
+    /// A GPU opened through the Direct Rendering Manager
+    DrmDevice,
+}
+
+impl FileDescriptorKind {
+    fn from_target<S: AsRef<str>>(target: S) -> Self {
+        let target = target.as_ref();
+        if target.starts_with("socket:") {
+            Self::Socket
+        } else if target.starts_with("pipe:") {
+            Self::Pipe
+        } else if target.starts_with("anon_inode:") {
+            Self::AnonInode
+        } else if target.starts_with("/dev/dri/") {
+            Self::DrmDevice
+        } else {
+            Self::File
+        }
+    }
+}
+
+/// A file descriptor held open by a process, gathered from `/proc/<pid>/fd` and `/proc/<pid>/fdinfo`.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenFile {
+    pub fd: i32,
+    /// Target of the fd link, e.g. a path or `socket:[12345]`
+    pub target: String,
+    pub kind: FileDescriptorKind,
+    /// File offset, `None` if fdinfo couldn't be read
+    pub position: Option<u64>,
+    /// Flags the file has been opened with (see open(2)), `None` if fdinfo couldn't be read
+    pub flags: Option<u32>,
+}
+
+impl OpenFile {
+    /// Returns `true` if the file has been opened for reading, `None` if the flags are unknown.
+    pub fn readable(&self) -> Option<bool> {
+        self.flags
+            .map(|flags| flags as i32 & libc::O_ACCMODE != libc::O_WRONLY)
+    }
+
+    /// Returns `true` if the file has been opened for writing, `None` if the flags are unknown.
+    pub fn writable(&self) -> Option<bool> {
+        self.flags
+            .map(|flags| flags as i32 & libc::O_ACCMODE != libc::O_RDONLY)
+    }
+
+    /// Returns all file descriptors the process with the given PID holds open, sorted by their number.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the fd directory of the process can't be read, usually because the process belongs to
+    /// another user or has exited in the meantime.
+    pub fn all_from_procfs(procfs: &ProcfsRoot, pid: libc::pid_t) -> Result<Vec<Self>> {
+        let proc_path = procfs.process_path(pid);
+
+        let mut open_files = std::fs::read_dir(proc_path.join("fd"))
+            .with_context(|| format!("unable to read open files of process {pid}"))?
+            .flatten()
+            .filter_map(|entry| Self::try_from_fd_path(&proc_path, entry.path()).ok())
+            .collect::<Vec<_>>();
+
+        open_files.sort_by_key(|open_file| open_file.fd);
+
+        Ok(open_files)
+    }
+
+    fn try_from_fd_path<P: AsRef<Path>>(proc_path: &Path, fd_path: P) -> Result<Self> {
+        let fd_path = fd_path.as_ref();
+
+        let fd: i32 = fd_path
+            .file_name()
+            .context("fd_path terminates in ..")?
+            .to_str()
+            .context("can't turn OsStr to str")?
+            .parse()?;
+
+        // the fd might have been closed in the meantime
+        let target = std::fs::read_link(fd_path)?.to_string_lossy().to_string();
+
+        let kind = FileDescriptorKind::from_target(&target);
+
+        let fdinfo = std::fs::read_to_string(proc_path.join("fdinfo").join(fd.to_string())).ok();
+
+        let fdinfo_field = |name: &str| {
+            fdinfo.as_ref().and_then(|fdinfo| {
+                fdinfo
+                    .lines()
+                    .filter_map(|line| line.split_once(':'))
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.trim().to_string())
+            })
+        };
+
+        let position = fdinfo_field("pos").and_then(|pos| pos.parse().ok());
+
+        // flags are printed in octal
+        let flags = fdinfo_field("flags").and_then(|flags| u32::from_str_radix(&flags, 8).ok());
+
+        Ok(Self {
+            fd,
+            target,
+            kind,
+            position,
+            flags,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::procfs::ProcfsRoot;
+
+    use super::{FileDescriptorKind, OpenFile};
+
+    #[test]
+    fn open_files_from_fixture() {
+        let procfs =
+            ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"));
+
+        let open_files = OpenFile::all_from_procfs(&procfs, 1234).unwrap();
+
+        assert_eq!(
+            vec![
+                (0, "/dev/pts/0", FileDescriptorKind::File),
+                (3, "socket:[48213]", FileDescriptorKind::Socket),
+                (4, "pipe:[48214]", FileDescriptorKind::Pipe),
+                (5, "/dev/dri/renderD128", FileDescriptorKind::DrmDevice),
+                (6, "anon_inode:[eventfd]", FileDescriptorKind::AnonInode),
+                (7, "/home/user/notes.txt", FileDescriptorKind::File),
+            ],
+            open_files
+                .iter()
+                .map(|open_file| (open_file.fd, open_file.target.as_str(), open_file.kind))
+                .collect::<Vec<_>>()
+        );
+
+        let notes = &open_files[5];
+        assert_eq!(Some(1337), notes.position);
+        assert_eq!(Some(0o2100002), notes.flags);
+        assert_eq!(Some(true), notes.readable());
+        assert_eq!(Some(true), notes.writable());
+
+        // fd 6 has no fdinfo in the fixture
+        assert_eq!(None, open_files[4].flags);
+        assert_eq!(None, open_files[4].readable());
+    }
+
+    #[test]
+    fn open_files_of_missing_process() {
+        let procfs =
+            ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"));
+
+        assert!(OpenFile::all_from_procfs(&procfs, 4321).is_err());
+    }
+}
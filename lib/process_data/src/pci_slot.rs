@@ -1,10 +1,8 @@
 use std::{error::Error, fmt::Display, str::FromStr};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(
-    Debug, Clone, Copy, Serialize, Deserialize, Default, Hash, PartialEq, Eq, PartialOrd, Ord,
-)]
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct PciSlot {
     pub domain: u16,
     pub bus: u8,
@@ -12,6 +10,17 @@ pub struct PciSlot {
     pub function: u8,
 }
 
+/// The representation of `PciSlot` in non-human-readable formats such as MessagePack, which is what deriving
+/// `Serialize` and `Deserialize` for `PciSlot` would result in.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "PciSlot")]
+struct PciSlotRepr {
+    domain: u16,
+    bus: u8,
+    number: u8,
+    function: u8,
+}
+
 impl PciSlot {
     pub fn new(domain: u16, bus: u8, number: u8, function: u8) -> Self {
         Self {
@@ -94,9 +103,44 @@ impl Display for PciSlot {
     }
 }
 
+// human-readable formats (e.g. JSON) get the usual string representation, which also allows `PciSlot` to be used as
+// key in maps
+impl Serialize for PciSlot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            PciSlotRepr {
+                domain: self.domain,
+                bus: self.bus,
+                number: self.number,
+                function: self.function,
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PciSlot {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            PciSlot::from_str(&s).map_err(de::Error::custom)
+        } else {
+            let repr = PciSlotRepr::deserialize(deserializer)?;
+            Ok(PciSlot::new(
+                repr.domain,
+                repr.bus,
+                repr.number,
+                repr.function,
+            ))
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use std::str::FromStr;
+    use std::{collections::BTreeMap, str::FromStr};
 
     use pretty_assertions::assert_eq;
 
@@ -115,4 +159,19 @@ mod test {
         let pci_id_str = "0000:01:fe.3";
         assert_eq!(pci_id_str, pci_id.to_string());
     }
+
+    #[test]
+    fn pci_id_json_map_key() {
+        let map = BTreeMap::from([(PciSlot::new(0x0, 0x1, 0xfe, 0x3), 42)]);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(r#"{"0000:01:fe.3":42}"#, json);
+        assert_eq!(map, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn pci_id_msgpack_roundtrip() {
+        let pci_id = PciSlot::new(0x0, 0x1, 0xfe, 0x3);
+        let encoded = rmp_serde::to_vec(&pci_id).unwrap();
+        assert_eq!(pci_id, rmp_serde::from_slice(&encoded).unwrap());
+    }
 }
@@ -0,0 +1,182 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+const PROC_PRESSURE: &str = "/proc/pressure";
+
+/// A resource whose pressure stall information is exposed by the kernel.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PressureResource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl PressureResource {
+    /// Returns the name of the resource as it is used in `/proc/pressure/<name>` and `<name>.pressure` of cgroups.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PressureResource::Cpu => "cpu",
+            PressureResource::Memory => "memory",
+            PressureResource::Io => "io",
+        }
+    }
+
+    /// Returns the name of the pressure file of this resource within a cgroup, e.g. `memory.pressure`.
+    pub fn cgroup_file_name(&self) -> String {
+        format!("{}.pressure", self.name())
+    }
+}
+
+/// Share of time during which tasks were stalled waiting for a resource.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PressureStall {
+    /// Percentage of time stalled, averaged over the last 10 seconds
+    pub avg10: f32,
+    /// Percentage of time stalled, averaged over the last 60 seconds
+    pub avg60: f32,
+    /// Percentage of time stalled, averaged over the last 300 seconds
+    pub avg300: f32,
+    /// Total time stalled in microseconds
+    pub total: u64,
+}
+
+/// Pressure stall information (PSI) of a resource, either system-wide or of a single cgroup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pressure {
+    /// Time during which at least some tasks were stalled
+    pub some: PressureStall,
+    /// Time during which all non-idle tasks were stalled at once, `None` for system-wide CPU pressure on kernels
+    /// older than 5.13
+    pub full: Option<PressureStall>,
+}
+
+impl Pressure {
+    /// Parses the content of a PSI file, which consists of lines like
+    /// `some avg10=1.53 avg60=0.87 avg300=0.22 total=59722082`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is no valid `some` line.
+    pub fn parse<S: AsRef<str>>(content: S) -> Result<Self> {
+        let mut some = None;
+        let mut full = None;
+
+        for line in content.as_ref().lines() {
+            let Some((kind, fields)) = line.split_once(' ') else {
+                continue;
+            };
+
+            let stall = Self::parse_stall(fields);
+
+            match kind {
+                "some" => some = stall,
+                "full" => full = stall,
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            some: some.context("no valid \"some\" line in pressure file")?,
+            full,
+        })
+    }
+
+    fn parse_stall(fields: &str) -> Option<PressureStall> {
+        let mut avg10 = None;
+        let mut avg60 = None;
+        let mut avg300 = None;
+        let mut total = None;
+
+        for (key, value) in fields
+            .split_whitespace()
+            .filter_map(|field| field.split_once('='))
+        {
+            match key {
+                "avg10" => avg10 = value.parse().ok(),
+                "avg60" => avg60 = value.parse().ok(),
+                "avg300" => avg300 = value.parse().ok(),
+                "total" => total = value.parse().ok(),
+                _ => {}
+            }
+        }
+
+        Some(PressureStall {
+            avg10: avg10?,
+            avg60: avg60?,
+            avg300: avg300?,
+            total: total?,
+        })
+    }
+
+    /// Reads and parses the PSI file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file can't be read (e.g. because the kernel has been built without PSI support) or
+    /// parsed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        std::fs::read_to_string(path)
+            .with_context(|| format!("unable to read {}", path.display()))
+            .and_then(Self::parse)
+    }
+
+    /// Returns the system-wide pressure of `resource`, gathered from `/proc/pressure`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the pressure file can't be read or parsed, usually because PSI isn't supported or has
+    /// been disabled using `psi=0`.
+    pub fn system(resource: PressureResource) -> Result<Self> {
+        Self::from_file(Path::new(PROC_PRESSURE).join(resource.name()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{Pressure, PressureStall};
+
+    #[test]
+    fn parse_pressure() {
+        let pressure = Pressure::parse(
+            "some avg10=1.53 avg60=0.87 avg300=0.22 total=59722082\nfull avg10=0.00 avg60=0.10 avg300=0.05 total=1234\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Pressure {
+                some: PressureStall {
+                    avg10: 1.53,
+                    avg60: 0.87,
+                    avg300: 0.22,
+                    total: 59_722_082,
+                },
+                full: Some(PressureStall {
+                    avg10: 0.0,
+                    avg60: 0.1,
+                    avg300: 0.05,
+                    total: 1234,
+                }),
+            },
+            pressure
+        );
+    }
+
+    #[test]
+    fn parse_pressure_without_full() {
+        let pressure =
+            Pressure::parse("some avg10=12.50 avg60=3.00 avg300=1.00 total=42\n").unwrap();
+
+        assert_eq!(12.5, pressure.some.avg10);
+        assert_eq!(None, pressure.full);
+
+        assert!(Pressure::parse("").is_err());
+        assert!(Pressure::parse("full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n").is_err());
+        assert!(Pressure::parse("some avg10=0.00 total=0\n").is_err());
+    }
+}
@@ -0,0 +1,647 @@
+//! Event-driven tracking of process starts and exits using the proc connector of the kernel.
+//!
+//! The periodic snapshots only see processes that are alive while they're being taken, so processes that live
+//! shorter than the refresh interval are missed entirely and exits are only noticed once a PID has vanished. The proc
+//! connector instead tells us about every fork, exec and exit as it happens, including the exit status.
+//!
+//! Subscribing to the proc connector requires `CAP_NET_ADMIN`, so this is only available when running with elevated
+//! privileges (e.g. when `resources-processes` has been started using pkexec).
+//!
+//! See `include/uapi/linux/connector.h` and `include/uapi/linux/cn_proc.h` for the message layouts.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{procfs::ProcfsRoot, unix_as_millis};
+
+const NETLINK_CONNECTOR: libc::c_int = 11;
+const NLMSG_DONE: u16 = 3;
+const NLMSG_HEADER_LEN: usize = 16;
+
+const CN_IDX_PROC: u32 = 1;
+const CN_VAL_PROC: u32 = 1;
+const CN_MSG_HEADER_LEN: usize = 20;
+
+const PROC_CN_MCAST_LISTEN: u32 = 1;
+const PROC_CN_MCAST_IGNORE: u32 = 2;
+
+const PROC_EVENT_FORK: u32 = 0x0000_0001;
+const PROC_EVENT_EXEC: u32 = 0x0000_0002;
+const PROC_EVENT_EXIT: u32 = 0x8000_0000;
+
+/// Offset of the event-specific data within a `struct proc_event`, after `what`, `cpu` and `timestamp_ns`.
+const PROC_EVENT_DATA_OFFSET: usize = 16;
+
+/// Large enough for a handful of messages, each of them being well below 100 bytes.
+const RECEIVE_BUFFER_LEN: usize = 4096;
+
+/// How long the listener thread blocks on the socket before checking whether it should stop.
+const RECEIVE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Events beyond this many are dropped (oldest first) if nobody takes them out of the listener.
+const MAX_BUFFERED_EVENTS: usize = 16384;
+
+/// How a process has exited, decoded from the wait status reported by the kernel.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExitStatus {
+    /// The process has exited on its own with this exit code.
+    Exited(i32),
+    /// The process has been terminated by a signal.
+    Signaled { signal: i32, core_dumped: bool },
+}
+
+impl ExitStatus {
+    /// Decodes a wait status as described in wait(2).
+    pub fn from_raw(raw: u32) -> Self {
+        let signal = (raw & 0x7f) as i32;
+        if signal == 0 {
+            Self::Exited(((raw >> 8) & 0xff) as i32)
+        } else {
+            Self::Signaled {
+                signal,
+                core_dumped: raw & 0x80 != 0,
+            }
+        }
+    }
+
+    /// Returns `true` if the process has exited on its own with exit code 0.
+    pub fn is_success(&self) -> bool {
+        *self == Self::Exited(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessEventKind {
+    /// The process has been created by `parent_pid`.
+    Fork { parent_pid: libc::pid_t },
+    /// The process has executed a new program.
+    Exec,
+    /// The process has exited.
+    Exit { status: ExitStatus },
+}
+
+/// Something that happened to a process, as reported by the proc connector. Only events concerning whole processes
+/// (i.e. thread group leaders) are reported, not those of their other threads.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessEvent {
+    pub pid: libc::pid_t,
+    /// Name of the process, `None` if the process has exited before it could be determined
+    pub comm: Option<String>,
+    /// When the event happened, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub kind: ProcessEventKind,
+}
+
+/// The events that have been received since the last time they've been taken from a `ProcessEventListener`.
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessEvents {
+    pub events: Vec<ProcessEvent>,
+    /// Lower bound of the number of events that have been lost, either because the kernel dropped them (it doesn't
+    /// tell how many) or because they haven't been taken out of the listener in time
+    pub lost: u64,
+}
+
+/// A proc connector event as sent by the kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RawEvent {
+    /// Nanoseconds since boot (`CLOCK_MONOTONIC`)
+    timestamp_ns: u64,
+    pid: libc::pid_t,
+    tgid: libc::pid_t,
+    kind: ProcessEventKind,
+}
+
+impl RawEvent {
+    /// Parses a `struct proc_event`, returns `None` for event types we're not interested in.
+    fn parse(event: &[u8]) -> Option<Self> {
+        let u32_at = |offset: usize| -> Option<u32> {
+            Some(u32::from_ne_bytes(
+                event.get(offset..offset + 4)?.try_into().ok()?,
+            ))
+        };
+        let pid_at = |index: usize| -> Option<libc::pid_t> {
+            u32_at(PROC_EVENT_DATA_OFFSET + index * 4).map(|pid| pid as libc::pid_t)
+        };
+
+        let what = u32_at(0)?;
+        let timestamp_ns = u64::from_ne_bytes(event.get(8..16)?.try_into().ok()?);
+
+        match what {
+            // parent_pid, parent_tgid, child_pid, child_tgid
+            PROC_EVENT_FORK => Some(Self {
+                timestamp_ns,
+                pid: pid_at(2)?,
+                tgid: pid_at(3)?,
+                kind: ProcessEventKind::Fork {
+                    parent_pid: pid_at(1)?,
+                },
+            }),
+            // process_pid, process_tgid
+            PROC_EVENT_EXEC => Some(Self {
+                timestamp_ns,
+                pid: pid_at(0)?,
+                tgid: pid_at(1)?,
+                kind: ProcessEventKind::Exec,
+            }),
+            // process_pid, process_tgid, exit_code, exit_signal, parent_pid, parent_tgid
+            PROC_EVENT_EXIT => Some(Self {
+                timestamp_ns,
+                pid: pid_at(0)?,
+                tgid: pid_at(1)?,
+                kind: ProcessEventKind::Exit {
+                    status: ExitStatus::from_raw(u32_at(PROC_EVENT_DATA_OFFSET + 8)?),
+                },
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this event concerns a whole process instead of one of its threads.
+    fn is_process_event(&self) -> bool {
+        self.pid == self.tgid
+    }
+}
+
+/// Parses all proc connector events contained in a netlink datagram.
+fn parse_datagram(datagram: &[u8]) -> Vec<RawEvent> {
+    let mut events = Vec::new();
+
+    let mut offset = 0;
+    while let Some(header) = datagram.get(offset..offset + NLMSG_HEADER_LEN) {
+        let len = u32::from_ne_bytes(header[0..4].try_into().unwrap_or_default()) as usize;
+        if len < NLMSG_HEADER_LEN {
+            break;
+        }
+
+        if let Some(event) = datagram
+            .get(offset + NLMSG_HEADER_LEN + CN_MSG_HEADER_LEN..offset + len)
+            .and_then(RawEvent::parse)
+        {
+            events.push(event);
+        }
+
+        // netlink messages are aligned to 4 bytes
+        offset += (len + 3) & !3;
+    }
+
+    events
+}
+
+/// Builds a netlink message that sends `operation` to the proc connector.
+fn control_message(operation: u32) -> Vec<u8> {
+    let len = NLMSG_HEADER_LEN + CN_MSG_HEADER_LEN + 4;
+
+    let mut message = Vec::with_capacity(len);
+
+    // struct nlmsghdr
+    message.extend_from_slice(&(len as u32).to_ne_bytes());
+    message.extend_from_slice(&NLMSG_DONE.to_ne_bytes());
+    message.extend_from_slice(&0_u16.to_ne_bytes()); // flags
+    message.extend_from_slice(&0_u32.to_ne_bytes()); // seq
+    message.extend_from_slice(&std::process::id().to_ne_bytes());
+
+    // struct cn_msg
+    message.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+    message.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+    message.extend_from_slice(&0_u32.to_ne_bytes()); // seq
+    message.extend_from_slice(&0_u32.to_ne_bytes()); // ack
+    message.extend_from_slice(&4_u16.to_ne_bytes()); // len
+    message.extend_from_slice(&0_u16.to_ne_bytes()); // flags
+
+    // enum proc_cn_mcast_op
+    message.extend_from_slice(&operation.to_ne_bytes());
+
+    message
+}
+
+fn monotonic_nanos() -> u64 {
+    let mut now = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut now) };
+    (now.tv_sec as u64)
+        .saturating_mul(1_000_000_000)
+        .saturating_add(now.tv_nsec as u64)
+}
+
+/// A netlink socket subscribed to the proc connector.
+#[derive(Debug)]
+pub struct ProcConnector {
+    socket: OwnedFd,
+}
+
+impl ProcConnector {
+    /// Opens a netlink socket and subscribes to process events.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the socket can't be opened or if the subscription is refused, which usually means that
+    /// we lack `CAP_NET_ADMIN`.
+    pub fn connect() -> Result<Self> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                NETLINK_CONNECTOR,
+            )
+        };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error()).context("unable to open netlink socket");
+        }
+
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let mut address: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        address.nl_groups = CN_IDX_PROC;
+
+        let bound = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                std::ptr::addr_of!(address).cast(),
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if bound < 0 {
+            return Err(std::io::Error::last_os_error()).context("unable to bind netlink socket");
+        }
+
+        let timeout = libc::timeval {
+            tv_sec: RECEIVE_TIMEOUT.as_secs() as libc::time_t,
+            tv_usec: RECEIVE_TIMEOUT.subsec_micros() as libc::suseconds_t,
+        };
+        unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                std::ptr::addr_of!(timeout).cast(),
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+
+        let connector = Self { socket };
+        connector
+            .send(PROC_CN_MCAST_LISTEN)
+            .context("unable to subscribe to process events")?;
+
+        Ok(connector)
+    }
+
+    fn send(&self, operation: u32) -> std::io::Result<()> {
+        let message = control_message(operation);
+
+        let sent = unsafe {
+            libc::send(
+                self.socket.as_raw_fd(),
+                message.as_ptr().cast(),
+                message.len(),
+                0,
+            )
+        };
+
+        if sent < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Waits (for a short while) for the next datagram and returns the events in it.
+    fn receive(&self, buffer: &mut [u8]) -> std::io::Result<Vec<RawEvent>> {
+        let received = unsafe {
+            libc::recv(
+                self.socket.as_raw_fd(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+                0,
+            )
+        };
+
+        if received < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(parse_datagram(&buffer[..received as usize]))
+        }
+    }
+}
+
+impl Drop for ProcConnector {
+    fn drop(&mut self) {
+        let _ = self.send(PROC_CN_MCAST_IGNORE);
+    }
+}
+
+#[derive(Debug, Default)]
+struct EventBuffer {
+    events: VecDeque<ProcessEvent>,
+    lost: u64,
+}
+
+/// Listens to process events in a background thread and collects them until they're taken out.
+#[derive(Debug)]
+pub struct ProcessEventListener {
+    buffer: Arc<Mutex<EventBuffer>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ProcessEventListener {
+    /// Subscribes to process events and starts collecting them. The names of processes are read from `procfs` as
+    /// soon as they've been forked or executed something, so that they're known even if they exit right away.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if subscribing to process events has failed, see `ProcConnector::connect()`.
+    pub fn start(procfs: ProcfsRoot) -> Result<Self> {
+        if !procfs.is_live() {
+            bail!("process events are only available for the procfs of the running kernel");
+        }
+
+        let connector = ProcConnector::connect()?;
+
+        let buffer = Arc::new(Mutex::new(EventBuffer::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = std::thread::spawn({
+            let buffer = Arc::clone(&buffer);
+            let stop = Arc::clone(&stop);
+            move || Self::listen(&connector, &procfs, &buffer, &stop)
+        });
+
+        Ok(Self {
+            buffer,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns all events received since the last call.
+    pub fn take(&self) -> ProcessEvents {
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return ProcessEvents::default();
+        };
+
+        ProcessEvents {
+            events: buffer.events.drain(..).collect(),
+            lost: std::mem::take(&mut buffer.lost),
+        }
+    }
+
+    /// Forgets the names of processes that don't exist anymore.
+    fn prune_comms(comms: &mut HashMap<libc::pid_t, String>, procfs: &ProcfsRoot) {
+        comms.retain(|pid, _| procfs.process_path(*pid).exists());
+    }
+
+    fn listen(
+        connector: &ProcConnector,
+        procfs: &ProcfsRoot,
+        buffer: &Mutex<EventBuffer>,
+        stop: &AtomicBool,
+    ) {
+        let mut receive_buffer = vec![0; RECEIVE_BUFFER_LEN];
+
+        // Key: PID, remembered so that we can name processes after they've exited
+        let mut comms: HashMap<libc::pid_t, String> = HashMap::new();
+
+        while !stop.load(Ordering::Relaxed) {
+            let raw_events = match connector.receive(&mut receive_buffer) {
+                Ok(raw_events) => raw_events,
+                Err(error) => {
+                    match error.raw_os_error() {
+                        Some(libc::EAGAIN | libc::EINTR) => {}
+                        // the kernel has dropped events because we've been too slow
+                        Some(libc::ENOBUFS) => {
+                            if let Ok(mut buffer) = buffer.lock() {
+                                buffer.lost += 1;
+                            }
+                            // exits might have been among the dropped events, so their names would never be removed
+                            Self::prune_comms(&mut comms, procfs);
+                        }
+                        _ => return,
+                    }
+                    continue;
+                }
+            };
+
+            // the kernel stamps its events with CLOCK_MONOTONIC, convert them to wall clock time
+            let now_millis = unix_as_millis();
+            let now_nanos = monotonic_nanos();
+
+            let events: Vec<_> = raw_events
+                .into_iter()
+                .filter(RawEvent::is_process_event)
+                .map(|raw_event| {
+                    let comm = match raw_event.kind {
+                        ProcessEventKind::Fork { .. } | ProcessEventKind::Exec => {
+                            let comm = std::fs::read_to_string(
+                                procfs.process_path(raw_event.pid).join("comm"),
+                            )
+                            .ok()
+                            .map(|comm| comm.trim_end_matches('\n').to_string())
+                            .or_else(|| comms.get(&raw_event.pid).cloned());
+
+                            if let Some(comm) = &comm {
+                                comms.insert(raw_event.pid, comm.clone());
+                            }
+
+                            comm
+                        }
+                        ProcessEventKind::Exit { .. } => comms.remove(&raw_event.pid),
+                    };
+
+                    let age_millis = now_nanos.saturating_sub(raw_event.timestamp_ns) / 1_000_000;
+
+                    ProcessEvent {
+                        pid: raw_event.pid,
+                        comm,
+                        timestamp: now_millis.saturating_sub(age_millis),
+                        kind: raw_event.kind,
+                    }
+                })
+                .collect();
+
+            if events.is_empty() {
+                continue;
+            }
+
+            if let Ok(mut buffer) = buffer.lock() {
+                buffer.events.extend(events);
+
+                let excess = buffer.events.len().saturating_sub(MAX_BUFFERED_EVENTS);
+                buffer.events.drain(..excess);
+                buffer.lost += excess as u64;
+            }
+        }
+    }
+}
+
+impl Drop for ProcessEventListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::procfs::ProcfsRoot;
+
+    use super::{
+        control_message, parse_datagram, ExitStatus, ProcessEventKind, ProcessEventListener,
+        RawEvent, CN_MSG_HEADER_LEN, NLMSG_HEADER_LEN, PROC_CN_MCAST_LISTEN, PROC_EVENT_EXEC,
+        PROC_EVENT_EXIT, PROC_EVENT_FORK,
+    };
+
+    /// Builds a netlink message containing a `struct proc_event` with the given type and data.
+    fn event_message(what: u32, timestamp_ns: u64, data: &[u32]) -> Vec<u8> {
+        let mut event = Vec::new();
+        event.extend_from_slice(&what.to_ne_bytes());
+        event.extend_from_slice(&0_u32.to_ne_bytes()); // cpu
+        event.extend_from_slice(&timestamp_ns.to_ne_bytes());
+        for value in data {
+            event.extend_from_slice(&value.to_ne_bytes());
+        }
+
+        let len = NLMSG_HEADER_LEN + CN_MSG_HEADER_LEN + event.len();
+
+        let mut message = vec![0; NLMSG_HEADER_LEN + CN_MSG_HEADER_LEN];
+        message[0..4].copy_from_slice(&(len as u32).to_ne_bytes());
+        message.extend_from_slice(&event);
+        message
+    }
+
+    #[test]
+    fn exit_status_from_raw() {
+        assert_eq!(ExitStatus::Exited(0), ExitStatus::from_raw(0));
+        assert!(ExitStatus::from_raw(0).is_success());
+        assert_eq!(ExitStatus::Exited(3), ExitStatus::from_raw(3 << 8));
+        assert_eq!(
+            ExitStatus::Signaled {
+                signal: libc::SIGKILL,
+                core_dumped: false
+            },
+            ExitStatus::from_raw(libc::SIGKILL as u32)
+        );
+        assert_eq!(
+            ExitStatus::Signaled {
+                signal: libc::SIGSEGV,
+                core_dumped: true
+            },
+            ExitStatus::from_raw(libc::SIGSEGV as u32 | 0x80)
+        );
+    }
+
+    #[test]
+    fn parse_fork_exec_exit() {
+        let mut datagram = event_message(PROC_EVENT_FORK, 100, &[1, 1, 4321, 4321]);
+        datagram.extend(event_message(PROC_EVENT_EXEC, 200, &[4321, 4321]));
+        datagram.extend(event_message(
+            PROC_EVENT_EXIT,
+            300,
+            &[4321, 4321, 1 << 8, 17, 1, 1],
+        ));
+
+        assert_eq!(
+            vec![
+                RawEvent {
+                    timestamp_ns: 100,
+                    pid: 4321,
+                    tgid: 4321,
+                    kind: ProcessEventKind::Fork { parent_pid: 1 },
+                },
+                RawEvent {
+                    timestamp_ns: 200,
+                    pid: 4321,
+                    tgid: 4321,
+                    kind: ProcessEventKind::Exec,
+                },
+                RawEvent {
+                    timestamp_ns: 300,
+                    pid: 4321,
+                    tgid: 4321,
+                    kind: ProcessEventKind::Exit {
+                        status: ExitStatus::Exited(1),
+                    },
+                },
+            ],
+            parse_datagram(&datagram)
+        );
+    }
+
+    #[test]
+    fn thread_events_are_recognized() {
+        // a new thread 4322 within process 4321
+        let datagram = event_message(PROC_EVENT_FORK, 100, &[4321, 4321, 4322, 4321]);
+
+        let events = parse_datagram(&datagram);
+
+        assert_eq!(1, events.len());
+        assert!(!events[0].is_process_event());
+    }
+
+    #[test]
+    fn unknown_and_truncated_events_are_skipped() {
+        // PROC_EVENT_UID
+        let mut datagram = event_message(0x4, 100, &[4321, 4321, 1000, 1000]);
+        // an exec event without its data
+        datagram.extend(event_message(PROC_EVENT_EXEC, 200, &[]));
+
+        assert_eq!(Vec::<RawEvent>::new(), parse_datagram(&datagram));
+    }
+
+    #[test]
+    fn control_message_layout() {
+        let message = control_message(PROC_CN_MCAST_LISTEN);
+
+        assert_eq!(NLMSG_HEADER_LEN + CN_MSG_HEADER_LEN + 4, message.len());
+        assert_eq!(
+            message.len() as u32,
+            u32::from_ne_bytes(message[0..4].try_into().unwrap())
+        );
+        assert_eq!(
+            PROC_CN_MCAST_LISTEN,
+            u32::from_ne_bytes(message[message.len() - 4..].try_into().unwrap())
+        );
+    }
+
+    #[test]
+    fn prune_comms_of_exited_processes() {
+        let procfs =
+            ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"));
+
+        let mut comms = HashMap::from([
+            (2, String::from("kthreadd")),
+            (1234, String::from("worker")),
+            (4321, String::from("gone")),
+        ]);
+
+        ProcessEventListener::prune_comms(&mut comms, &procfs);
+
+        assert_eq!(
+            HashMap::from([
+                (2, String::from("kthreadd")),
+                (1234, String::from("worker"))
+            ]),
+            comms
+        );
+    }
+}
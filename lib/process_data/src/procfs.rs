@@ -0,0 +1,75 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use glob::glob;
+
+const DEFAULT_PROCFS_ROOT: &str = "/proc";
+
+/// The root of a procfs tree that process data can be collected from.
+///
+/// By default this is the `/proc` of the running kernel, but it can also point to a host's procfs that is mounted
+/// somewhere else (e.g. from inside a container) or to a captured fixture tree that mimics the layout of procfs.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ProcfsRoot {
+    path: PathBuf,
+}
+
+impl Default for ProcfsRoot {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROCFS_ROOT)
+    }
+}
+
+impl ProcfsRoot {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if this is the procfs of the kernel we're running on, meaning that the PIDs found in it can be
+    /// used for syscalls like `ioprio_get`.
+    pub fn is_live(&self) -> bool {
+        self.path == Path::new(DEFAULT_PROCFS_ROOT)
+    }
+
+    /// Returns the path to the directory of the process with the given PID within this procfs root.
+    pub fn process_path(&self, pid: libc::pid_t) -> PathBuf {
+        self.path.join(pid.to_string())
+    }
+
+    /// Returns the paths to the directories of all processes found within this procfs root.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the procfs root can't be represented as a valid glob pattern.
+    pub fn process_paths(&self) -> Result<Vec<PathBuf>> {
+        let pattern = self.path.join("[0-9]*/");
+        let pattern = pattern
+            .to_str()
+            .context("procfs root is not valid unicode")?;
+
+        Ok(glob(pattern).context("unable to glob")?.flatten().collect())
+    }
+
+    /// Returns the PIDs of all processes found within this procfs root in ascending order. This is cheaper than
+    /// `process_paths()` since it doesn't need to match a glob pattern against every entry.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the procfs root can't be read.
+    pub fn pids(&self) -> Result<Vec<libc::pid_t>> {
+        let mut pids: Vec<libc::pid_t> = std::fs::read_dir(&self.path)
+            .with_context(|| format!("unable to read {}", self.path.display()))?
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+            .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+            .collect();
+
+        pids.sort_unstable();
+
+        Ok(pids)
+    }
+}
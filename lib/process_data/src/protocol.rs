@@ -0,0 +1,149 @@
+//! The protocol spoken between Resources and `resources-processes` over stdin and stdout.
+//!
+//! Every request starts with a single request byte. Every answer consists of its length as a little-endian `u64`,
+//! followed by the MessagePack-encoded answer itself.
+//!
+//! Besides the periodic snapshots of all processes, there are on-demand queries about a single process. Their request
+//! byte is followed by the PID (as a little-endian `i32`) and their answer is a `Result<_, String>`, so that the reason
+//! why a query failed can be shown to the user.
+//!
+//! Requests that need more than a PID (like `REQUEST_CGROUP_STATS`) are followed by the length of their arguments (as
+//! a little-endian `u32`) and the MessagePack-encoded arguments themselves.
+
+use serde::{Deserialize, Serialize};
+
+/// Magic bytes that identify an answer to a handshake request.
+pub const MAGIC: [u8; 4] = *b"RSPD";
+
+/// Version of the protocol. This needs to be increased whenever the layout of any message changes, including
+/// `ProcessData` and everything contained in it, since older or newer counterparts won't be able to decode them.
+pub const PROTOCOL_VERSION: u32 = 11;
+
+/// Request byte for the legacy request, the answer is a `Vec<ProcessData>` of all processes. This is the only request
+/// understood by versions of `resources-processes` that predate the handshake, which answer every request byte this
+/// way.
+pub const REQUEST_FULL: u8 = b'\n';
+
+/// Request byte for the incremental protocol. It is followed by the sequence number (as a little-endian `u64`) of the
+/// last update the client has applied, the answer is a `ProcessDataUpdate`.
+pub const REQUEST_DELTA: u8 = b'd';
+
+/// Request byte for the handshake, the answer is a `Handshake`.
+pub const REQUEST_HANDSHAKE: u8 = b'h';
+
+/// Request byte for the open files of a process, the answer is a `Result<Vec<OpenFile>, String>`.
+pub const REQUEST_OPEN_FILES: u8 = b'f';
+
+/// Request byte for the memory mappings of a process, the answer is a `Result<Vec<MemoryMapping>, String>`.
+pub const REQUEST_MEMORY_MAPS: u8 = b'm';
+
+/// Request byte for the statistics of a set of cgroups. The arguments are a `Vec<String>` of cgroups v2 paths, the
+/// answer is a `Result<HashMap<String, CgroupStats>, String>` that only contains the cgroups whose statistics could be
+/// read.
+pub const REQUEST_CGROUP_STATS: u8 = b'c';
+
+/// Request byte for selecting the optional parts of `ProcessData` that are collected for all following process data
+/// requests. The arguments are a `ProcessFields`, the answer is a `Result<(), String>`. Everything is collected until
+/// this has been requested for the first time.
+pub const REQUEST_SELECT_FIELDS: u8 = b's';
+
+/// Request byte for the process events (starts, execs and exits) that happened since the last time this has been
+/// requested, the answer is a `Result<ProcessEvents, String>`. The first request subscribes to process events, so it
+/// will usually return none. This fails if `resources-processes` lacks the privileges to subscribe.
+pub const REQUEST_PROCESS_EVENTS: u8 = b'e';
+
+/// `resources-processes` understands `REQUEST_DELTA`.
+pub const FEATURE_DELTA: u64 = 1 << 0;
+
+/// `resources-processes` understands `REQUEST_OPEN_FILES`.
+pub const FEATURE_OPEN_FILES: u64 = 1 << 1;
+
+/// `resources-processes` understands `REQUEST_MEMORY_MAPS`.
+pub const FEATURE_MEMORY_MAPS: u64 = 1 << 2;
+
+/// `resources-processes` understands `REQUEST_CGROUP_STATS`.
+pub const FEATURE_CGROUP_STATS: u64 = 1 << 3;
+
+/// `resources-processes` understands `REQUEST_SELECT_FIELDS`.
+pub const FEATURE_SELECT_FIELDS: u64 = 1 << 4;
+
+/// `resources-processes` understands `REQUEST_PROCESS_EVENTS`.
+pub const FEATURE_PROCESS_EVENTS: u64 = 1 << 5;
+
+/// Features supported by this version of the protocol.
+pub const FEATURES: u64 = FEATURE_DELTA
+    | FEATURE_OPEN_FILES
+    | FEATURE_MEMORY_MAPS
+    | FEATURE_CGROUP_STATS
+    | FEATURE_SELECT_FIELDS
+    | FEATURE_PROCESS_EVENTS;
+
+/// Answer to `REQUEST_HANDSHAKE`, describing what the answering `resources-processes` is able to do.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Handshake {
+    pub magic: [u8; 4],
+    pub version: u32,
+    pub features: u64,
+}
+
+impl Default for Handshake {
+    fn default() -> Self {
+        Self {
+            magic: MAGIC,
+            version: PROTOCOL_VERSION,
+            features: FEATURES,
+        }
+    }
+}
+
+impl Handshake {
+    /// Returns `true` if the counterpart that sent this handshake speaks the same protocol version as we do.
+    pub fn is_compatible(&self) -> bool {
+        self.magic == MAGIC && self.version == PROTOCOL_VERSION
+    }
+
+    /// Returns `true` if both us and the counterpart that sent this handshake support `feature`.
+    pub fn supports(&self, feature: u64) -> bool {
+        self.is_compatible() && (self.features & FEATURES & feature) == feature
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::ProcessData;
+
+    use super::{Handshake, FEATURE_DELTA, PROTOCOL_VERSION};
+
+    #[test]
+    fn handshake_roundtrip() {
+        let handshake = Handshake::default();
+
+        let encoded = rmp_serde::to_vec(&handshake).unwrap();
+
+        assert_eq!(
+            handshake,
+            rmp_serde::from_slice::<Handshake>(&encoded).unwrap()
+        );
+        assert!(handshake.supports(FEATURE_DELTA));
+    }
+
+    #[test]
+    fn different_version_is_incompatible() {
+        let handshake = Handshake {
+            version: PROTOCOL_VERSION + 1,
+            ..Default::default()
+        };
+
+        assert!(!handshake.is_compatible());
+        assert!(!handshake.supports(FEATURE_DELTA));
+    }
+
+    #[test]
+    fn legacy_answer_is_not_a_handshake() {
+        let legacy_answer = rmp_serde::to_vec(&vec![ProcessData::default()]).unwrap();
+
+        assert!(rmp_serde::from_slice::<Handshake>(&legacy_answer).is_err());
+    }
+}
@@ -0,0 +1,376 @@
+//! Periodic scanning of all processes of a procfs tree.
+//!
+//! Reading a process means reading a handful of files in its procfs directory, which adds up with thousands of
+//! processes. A `ProcessScanner` therefore reuses its read buffers across processes and scans, remembers the fields of
+//! a process that don't change while it's running and spreads the processes across several threads.
+
+use std::{
+    collections::HashMap,
+    io::Read,
+    num::NonZeroUsize,
+    ops::{BitOr, BitOrAssign},
+    path::Path,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{procfs::ProcfsRoot, Containerization, ProcessData};
+
+/// The cached static fields of a process are read again after this many scans anyway, because a process can be
+/// moved to another cgroup (e.g. by systemd right after it has been launched) or change its commandline.
+const STATIC_DATA_MAX_AGE: u64 = 10;
+
+/// Spawning a thread isn't free either, so every thread should have at least this many processes to scan.
+const MIN_PROCESSES_PER_WORKER: usize = 64;
+
+/// Reading procfs is mostly limited by the kernel, more threads than this don't make scans any faster.
+const MAX_WORKERS: usize = 8;
+
+/// A set of the parts of `ProcessData` that are expensive to collect and can therefore be left out if nobody needs
+/// them. Parts that aren't collected stay empty (`None`, an empty map or an empty list).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessFields(u64);
+
+impl ProcessFields {
+    /// No optional parts at all
+    pub const NONE: Self = Self(0);
+
+    /// `read_bytes` and `write_bytes` from `/proc/<pid>/io`
+    pub const IO: Self = Self(1 << 0);
+
+    /// `gpu_usage_stats` from `/proc/<pid>/fdinfo` and NVML
+    pub const GPU: Self = Self(1 << 1);
+
+    /// `threads` from `/proc/<pid>/task`
+    pub const THREADS: Self = Self(1 << 2);
+
+    /// `smaps_rollup` from `/proc/<pid>/smaps_rollup`
+    pub const SMAPS_ROLLUP: Self = Self(1 << 3);
+
+    /// All optional parts
+    pub const ALL: Self = Self(Self::IO.0 | Self::GPU.0 | Self::THREADS.0 | Self::SMAPS_ROLLUP.0);
+
+    /// Returns `true` if all parts of `other` are also part of `self`.
+    pub fn contains(self, other: Self) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl Default for ProcessFields {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl BitOr for ProcessFields {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ProcessFields {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Reads the file at `path` into `buffer`, replacing its previous content but keeping its capacity.
+pub(crate) fn read_into<P: AsRef<Path>>(path: P, buffer: &mut String) -> std::io::Result<()> {
+    buffer.clear();
+    std::fs::File::open(path)?.read_to_string(buffer)?;
+    Ok(())
+}
+
+/// Read buffers that are reused for every process a worker scans.
+#[derive(Debug, Default)]
+pub(crate) struct ScanBuffers {
+    pub stat: String,
+    pub statm: String,
+    pub status: String,
+    pub comm: String,
+    pub io: String,
+    pub fdinfo: String,
+    pub task_stat: String,
+    pub task_comm: String,
+}
+
+/// Fields of a process that rarely change while it's running, along with what's needed to tell whether they still
+/// belong to the same process.
+#[derive(Debug, Clone)]
+pub(crate) struct StaticProcessData {
+    pub starttime: u64,
+    pub comm: String,
+    pub commandline: String,
+    pub cgroup: Option<String>,
+    pub cgroup_path: Option<String>,
+    pub containerization: Containerization,
+    /// Number of the scan these fields have been read in
+    pub scan: u64,
+}
+
+impl StaticProcessData {
+    fn new(process_data: &ProcessData, scan: u64) -> Self {
+        Self {
+            starttime: process_data.starttime,
+            comm: process_data.comm.clone(),
+            commandline: process_data.commandline.clone(),
+            cgroup: process_data.cgroup.clone(),
+            cgroup_path: process_data.cgroup_path.clone(),
+            containerization: process_data.containerization.clone(),
+            scan,
+        }
+    }
+
+    /// Returns whether these fields are the ones of `process_data`, i.e. whether they've been used for it.
+    fn belongs_to(&self, process_data: &ProcessData) -> bool {
+        self.starttime == process_data.starttime && self.comm == process_data.comm
+    }
+}
+
+/// Collects the data of all processes within a procfs tree, meant to be kept around for periodic scans.
+#[derive(Debug)]
+pub struct ProcessScanner {
+    procfs: ProcfsRoot,
+    /// One set of buffers per worker thread
+    buffers: Vec<ScanBuffers>,
+    /// Key: PID
+    static_data: HashMap<libc::pid_t, StaticProcessData>,
+    scans: u64,
+    fields: ProcessFields,
+}
+
+impl ProcessScanner {
+    /// Creates a new scanner for `procfs` that uses as many threads as there are CPUs (but at most 8).
+    pub fn new(procfs: ProcfsRoot) -> Self {
+        let workers = std::thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(MAX_WORKERS);
+
+        Self::with_workers(procfs, workers)
+    }
+
+    /// Creates a new scanner for `procfs` that uses at most `workers` threads.
+    pub fn with_workers(procfs: ProcfsRoot, workers: usize) -> Self {
+        Self {
+            procfs,
+            buffers: (0..workers.max(1))
+                .map(|_| ScanBuffers::default())
+                .collect(),
+            static_data: HashMap::new(),
+            scans: 0,
+            fields: ProcessFields::ALL,
+        }
+    }
+
+    pub fn procfs(&self) -> &ProcfsRoot {
+        &self.procfs
+    }
+
+    /// Returns the optional parts of `ProcessData` this scanner collects.
+    pub fn fields(&self) -> ProcessFields {
+        self.fields
+    }
+
+    /// Sets the optional parts of `ProcessData` this scanner collects, all of them are collected by default.
+    pub fn set_fields(&mut self, fields: ProcessFields) {
+        self.fields = fields;
+    }
+
+    /// Collects the data of all processes that can currently be found within the procfs tree. Processes that
+    /// vanish during the scan are left out.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the process directories can't be enumerated
+    pub fn scan(&mut self) -> Result<Vec<ProcessData>> {
+        #[cfg(feature = "nvidia")]
+        if self.fields.contains(ProcessFields::GPU) {
+            ProcessData::update_nvidia_stats();
+        }
+
+        self.scans += 1;
+
+        let pids = self.procfs.pids()?;
+
+        let workers = pids
+            .len()
+            .div_ceil(MIN_PROCESSES_PER_WORKER)
+            .clamp(1, self.buffers.len());
+        let chunk_size = pids.len().div_ceil(workers).max(1);
+
+        let Self {
+            procfs,
+            buffers,
+            static_data,
+            scans,
+            fields,
+        } = self;
+
+        let procfs = &*procfs;
+        let scan = *scans;
+        let fields = *fields;
+
+        let scanned: Vec<Vec<(ProcessData, bool)>> = if workers == 1 {
+            vec![Self::scan_chunk(
+                procfs,
+                &pids,
+                &mut buffers[0],
+                static_data,
+                scan,
+                fields,
+            )]
+        } else {
+            let static_data = &*static_data;
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = pids
+                    .chunks(chunk_size)
+                    .zip(buffers.iter_mut())
+                    .map(|(chunk, buffers)| {
+                        scope.spawn(move || {
+                            Self::scan_chunk(procfs, chunk, buffers, static_data, scan, fields)
+                        })
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap_or_default())
+                    .collect()
+            })
+        };
+
+        let mut process_data = Vec::with_capacity(pids.len());
+        let mut new_static_data = HashMap::with_capacity(pids.len());
+
+        for (data, cached) in scanned.into_iter().flatten() {
+            let entry = if cached {
+                static_data.remove(&data.pid)
+            } else {
+                None
+            }
+            .unwrap_or_else(|| StaticProcessData::new(&data, scan));
+
+            new_static_data.insert(data.pid, entry);
+            process_data.push(data);
+        }
+
+        // this also forgets about processes that don't exist anymore
+        *static_data = new_static_data;
+
+        Ok(process_data)
+    }
+
+    /// Scans the processes in `pids`, returns their data and whether their cached static fields have been used.
+    fn scan_chunk(
+        procfs: &ProcfsRoot,
+        pids: &[libc::pid_t],
+        buffers: &mut ScanBuffers,
+        static_data: &HashMap<libc::pid_t, StaticProcessData>,
+        scan: u64,
+        fields: ProcessFields,
+    ) -> Vec<(ProcessData, bool)> {
+        pids.iter()
+            .filter_map(|pid| {
+                let cached = static_data
+                    .get(pid)
+                    .filter(|cached| scan.saturating_sub(cached.scan) < STATIC_DATA_MAX_AGE);
+
+                ProcessData::scan(procfs, &procfs.process_path(*pid), buffers, cached, fields)
+                    .ok()
+                    .map(|data| {
+                        let cached = cached.is_some_and(|cached| cached.belongs_to(&data));
+                        (data, cached)
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{procfs::ProcfsRoot, ProcessData};
+
+    use super::{ProcessFields, ProcessScanner};
+
+    fn fixture_procfs() -> ProcfsRoot {
+        ProcfsRoot::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/procfs"))
+    }
+
+    #[test]
+    fn scans_match_single_processes() {
+        let procfs = fixture_procfs();
+
+        for workers in [1, 4] {
+            let mut scanner = ProcessScanner::with_workers(procfs.clone(), workers);
+
+            // the second scan uses the cached static fields of the first one
+            for _ in 0..2 {
+                let scanned = scanner.scan().unwrap();
+
+                assert_eq!(procfs.pids().unwrap().len(), scanned.len());
+
+                for process in scanned {
+                    let single =
+                        ProcessData::try_from_procfs(&procfs, procfs.process_path(process.pid))
+                            .unwrap();
+                    assert!(process.eq_ignoring_timestamp(&single));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unselected_fields_are_skipped() {
+        let mut scanner = ProcessScanner::with_workers(fixture_procfs(), 1);
+        scanner.set_fields(ProcessFields::IO);
+
+        let process = scanner
+            .scan()
+            .unwrap()
+            .into_iter()
+            .find(|process| process.pid == 1234)
+            .unwrap();
+
+        assert!(process.read_bytes.is_some());
+        assert!(process.gpu_usage_stats.is_empty());
+        assert!(process.threads.is_empty());
+        assert_eq!(None, process.smaps_rollup);
+
+        scanner.set_fields(ProcessFields::default());
+
+        let process = scanner
+            .scan()
+            .unwrap()
+            .into_iter()
+            .find(|process| process.pid == 1234)
+            .unwrap();
+
+        assert!(!process.gpu_usage_stats.is_empty());
+        assert_eq!(2, process.threads.len());
+        assert!(process.smaps_rollup.is_some());
+    }
+
+    #[test]
+    fn field_set_operations() {
+        let mut fields = ProcessFields::NONE;
+        assert!(!fields.contains(ProcessFields::GPU));
+
+        fields |= ProcessFields::GPU;
+        assert!(fields.contains(ProcessFields::GPU));
+        assert!(!fields.contains(ProcessFields::GPU | ProcessFields::IO));
+        assert!(ProcessFields::ALL.contains(fields));
+        assert!(fields.contains(ProcessFields::NONE));
+    }
+
+    #[test]
+    fn fixture_pids() {
+        assert_eq!(vec![2, 1234], fixture_procfs().pids().unwrap());
+    }
+}
@@ -0,0 +1,113 @@
+//! Names of the signals that can be sent to processes.
+
+/// The standard signals of Linux and their names without the `SIG` prefix, ordered by their numbers.
+pub const SIGNALS: &[(libc::c_int, &str)] = &[
+    (libc::SIGHUP, "HUP"),
+    (libc::SIGINT, "INT"),
+    (libc::SIGQUIT, "QUIT"),
+    (libc::SIGILL, "ILL"),
+    (libc::SIGTRAP, "TRAP"),
+    (libc::SIGABRT, "ABRT"),
+    (libc::SIGBUS, "BUS"),
+    (libc::SIGFPE, "FPE"),
+    (libc::SIGKILL, "KILL"),
+    (libc::SIGUSR1, "USR1"),
+    (libc::SIGSEGV, "SEGV"),
+    (libc::SIGUSR2, "USR2"),
+    (libc::SIGPIPE, "PIPE"),
+    (libc::SIGALRM, "ALRM"),
+    (libc::SIGTERM, "TERM"),
+    (libc::SIGSTKFLT, "STKFLT"),
+    (libc::SIGCHLD, "CHLD"),
+    (libc::SIGCONT, "CONT"),
+    (libc::SIGSTOP, "STOP"),
+    (libc::SIGTSTP, "TSTP"),
+    (libc::SIGTTIN, "TTIN"),
+    (libc::SIGTTOU, "TTOU"),
+    (libc::SIGURG, "URG"),
+    (libc::SIGXCPU, "XCPU"),
+    (libc::SIGXFSZ, "XFSZ"),
+    (libc::SIGVTALRM, "VTALRM"),
+    (libc::SIGPROF, "PROF"),
+    (libc::SIGWINCH, "WINCH"),
+    (libc::SIGIO, "IO"),
+    (libc::SIGPWR, "PWR"),
+    (libc::SIGSYS, "SYS"),
+];
+
+/// Returns the name of `signal` including the `SIG` prefix, e.g. `SIGKILL`. Real-time signals are named relative to
+/// `SIGRTMIN`, other unknown signals by their number.
+pub fn signal_name(signal: libc::c_int) -> String {
+    if let Some((_, name)) = SIGNALS.iter().find(|(number, _)| *number == signal) {
+        format!("SIG{name}")
+    } else if (libc::SIGRTMIN()..=libc::SIGRTMAX()).contains(&signal) {
+        match signal - libc::SIGRTMIN() {
+            0 => String::from("SIGRTMIN"),
+            offset => format!("SIGRTMIN+{offset}"),
+        }
+    } else {
+        signal.to_string()
+    }
+}
+
+/// Parses a signal given by its number or its name, with or without the `SIG` prefix and ignoring case, e.g. `HUP`,
+/// `sigusr1` or `RTMIN+3`. Returns `None` if there is no such signal.
+pub fn parse_signal(signal: &str) -> Option<libc::c_int> {
+    let signal = signal.trim();
+
+    if let Ok(number) = signal.parse::<libc::c_int>() {
+        return (1..=libc::SIGRTMAX()).contains(&number).then_some(number);
+    }
+
+    let name = signal.to_ascii_uppercase();
+    let name = name.strip_prefix("SIG").unwrap_or(&name);
+
+    if let Some((number, _)) = SIGNALS.iter().find(|(_, signal_name)| *signal_name == name) {
+        return Some(*number);
+    }
+
+    let realtime = if name == "RTMIN" {
+        Some(libc::SIGRTMIN())
+    } else if name == "RTMAX" {
+        Some(libc::SIGRTMAX())
+    } else if let Some(offset) = name.strip_prefix("RTMIN+") {
+        libc::SIGRTMIN().checked_add(offset.parse().ok()?)
+    } else if let Some(offset) = name.strip_prefix("RTMAX-") {
+        libc::SIGRTMAX().checked_sub(offset.parse().ok()?)
+    } else {
+        None
+    };
+
+    realtime.filter(|number| (libc::SIGRTMIN()..=libc::SIGRTMAX()).contains(number))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_signal, signal_name};
+
+    #[test]
+    fn signal_names() {
+        assert_eq!("SIGHUP", signal_name(libc::SIGHUP));
+        assert_eq!("SIGKILL", signal_name(9));
+        assert_eq!("SIGRTMIN", signal_name(libc::SIGRTMIN()));
+        assert_eq!("SIGRTMIN+2", signal_name(libc::SIGRTMIN() + 2));
+        assert_eq!("0", signal_name(0));
+    }
+
+    #[test]
+    fn parse_signals() {
+        assert_eq!(Some(libc::SIGHUP), parse_signal("HUP"));
+        assert_eq!(Some(libc::SIGUSR1), parse_signal("sigusr1"));
+        assert_eq!(Some(libc::SIGKILL), parse_signal(" SIGKILL\n"));
+        assert_eq!(Some(15), parse_signal("15"));
+        assert_eq!(Some(libc::SIGRTMIN()), parse_signal("RTMIN"));
+        assert_eq!(Some(libc::SIGRTMIN() + 3), parse_signal("SIGRTMIN+3"));
+        assert_eq!(Some(libc::SIGRTMAX() - 1), parse_signal("RTMAX-1"));
+        assert_eq!(None, parse_signal("RTMIN+100"));
+        assert_eq!(None, parse_signal("0"));
+        assert_eq!(None, parse_signal("-9"));
+        assert_eq!(None, parse_signal("FOO"));
+    }
+}
@@ -0,0 +1,233 @@
+//! User and group IDs of processes and the resolution of those IDs to names.
+//!
+//! Names are looked up through NSS every now and then instead of being read once at startup, so that users that come
+//! and go while we're running (e.g. those of systemd services with `DynamicUser=yes`, or LDAP and SSSD users that
+//! haven't been cached yet) are still resolved properly. IDs without a name are shown as numbers.
+
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// Resolved names are looked up again after this long, since dynamic users may reuse IDs under a different name.
+const NAME_TTL: Duration = Duration::from_secs(60);
+
+/// IDs that couldn't be resolved are looked up again after this long, their user might just not exist yet.
+const MISS_TTL: Duration = Duration::from_secs(5);
+
+static USER_NAMES: Lazy<NameCache> = Lazy::new(|| {
+    NameCache::new(
+        |uid| uzers::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().to_string()),
+        NAME_TTL,
+        MISS_TTL,
+    )
+});
+
+static GROUP_NAMES: Lazy<NameCache> = Lazy::new(|| {
+    NameCache::new(
+        |gid| uzers::get_group_by_gid(gid).map(|group| group.name().to_string_lossy().to_string()),
+        NAME_TTL,
+        MISS_TTL,
+    )
+});
+
+/// The real, effective and saved set IDs of a process, either for its user or its group.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ids {
+    pub real: u32,
+    pub effective: u32,
+    pub saved: u32,
+}
+
+impl Ids {
+    /// Parses the `Uid:` or `Gid:` line (depending on `key`) of a `/proc/<pid>/status` file. Returns the default (all
+    /// IDs being 0) if there is no such line.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the line is malformed.
+    pub fn from_status<S: AsRef<str>>(status: S, key: &str) -> Result<Self> {
+        let Some(values) = status
+            .as_ref()
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+        else {
+            return Ok(Self::default());
+        };
+
+        let mut ids = values.split_whitespace().map(|id| {
+            id.parse()
+                .with_context(|| format!("couldn't parse {key} in status"))
+        });
+
+        let mut next = || {
+            ids.next()
+                .with_context(|| format!("{key} line too short"))?
+        };
+
+        Ok(Self {
+            real: next()?,
+            effective: next()?,
+            saved: next()?,
+        })
+    }
+
+    /// Returns `true` if the effective or saved ID differ from the real one, e.g. for setuid programs.
+    pub fn is_mixed(&self) -> bool {
+        self.effective != self.real || self.saved != self.real
+    }
+}
+
+/// Returns the name of the user with the given UID, or the UID itself if it has no name.
+pub fn user_name(uid: libc::uid_t) -> String {
+    USER_NAMES.get(uid)
+}
+
+/// Returns the name of the group with the given GID, or the GID itself if it has no name.
+pub fn group_name(gid: libc::gid_t) -> String {
+    GROUP_NAMES.get(gid)
+}
+
+#[derive(Debug, Clone)]
+struct CachedName {
+    name: Option<String>,
+    resolved_at: Instant,
+}
+
+/// Thread-safe cache of ID-to-name lookups whose entries expire after a while.
+struct NameCache {
+    resolve: fn(u32) -> Option<String>,
+    ttl: Duration,
+    miss_ttl: Duration,
+    names: RwLock<HashMap<u32, CachedName>>,
+}
+
+impl NameCache {
+    fn new(resolve: fn(u32) -> Option<String>, ttl: Duration, miss_ttl: Duration) -> Self {
+        Self {
+            resolve,
+            ttl,
+            miss_ttl,
+            names: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, id: u32) -> String {
+        let cached = self
+            .names
+            .read()
+            .ok()
+            .and_then(|names| names.get(&id).cloned())
+            .filter(|cached| {
+                let ttl = if cached.name.is_some() {
+                    self.ttl
+                } else {
+                    self.miss_ttl
+                };
+                cached.resolved_at.elapsed() < ttl
+            });
+
+        let name = match cached {
+            Some(cached) => cached.name,
+            None => {
+                let name = (self.resolve)(id);
+
+                if let Ok(mut names) = self.names.write() {
+                    names.insert(
+                        id,
+                        CachedName {
+                            name: name.clone(),
+                            resolved_at: Instant::now(),
+                        },
+                    );
+                }
+
+                name
+            }
+        };
+
+        name.unwrap_or_else(|| id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    use pretty_assertions::assert_eq;
+
+    use super::{Ids, NameCache};
+
+    #[test]
+    fn ids_from_status() {
+        let status = "Name:\tsudo\nUid:\t1000\t0\t0\t0\nGid:\t1000\t1000\t1000\t1000\n";
+
+        let uids = Ids::from_status(status, "Uid").unwrap();
+        assert_eq!(
+            Ids {
+                real: 1000,
+                effective: 0,
+                saved: 0,
+            },
+            uids
+        );
+        assert!(uids.is_mixed());
+
+        let gids = Ids::from_status(status, "Gid").unwrap();
+        assert_eq!(
+            Ids {
+                real: 1000,
+                effective: 1000,
+                saved: 1000,
+            },
+            gids
+        );
+        assert!(!gids.is_mixed());
+
+        assert_eq!(
+            Ids::default(),
+            Ids::from_status("Name:\tsudo\n", "Uid").unwrap()
+        );
+        assert!(Ids::from_status("Uid:\t1000\n", "Uid").is_err());
+    }
+
+    #[test]
+    fn unknown_ids_are_numbers() {
+        let cache = NameCache::new(
+            |id| (id == 0).then(|| String::from("root")),
+            Duration::MAX,
+            Duration::MAX,
+        );
+
+        assert_eq!("root", cache.get(0));
+        assert_eq!("61234", cache.get(61234));
+    }
+
+    #[test]
+    fn misses_are_retried() {
+        static LOOKUPS: AtomicU32 = AtomicU32::new(0);
+
+        let cache = NameCache::new(
+            |_| {
+                // the user only exists from the second lookup on
+                (LOOKUPS.fetch_add(1, Ordering::SeqCst) > 0).then(|| String::from("dynamic"))
+            },
+            Duration::MAX,
+            Duration::ZERO,
+        );
+
+        assert_eq!("61234", cache.get(61234));
+        assert_eq!("dynamic", cache.get(61234));
+        // resolved names are kept
+        assert_eq!("dynamic", cache.get(61234));
+        assert_eq!(2, LOOKUPS.load(Ordering::SeqCst));
+    }
+}
@@ -1,5 +1,8 @@
 use std::{env, path::PathBuf};
 
+// see include/uapi/linux/ioprio.h
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+
 use nix::{
     sched::{sched_setaffinity, CpuSet},
     unistd::Pid,
@@ -17,14 +20,24 @@ fn main() {
                     }
                 }
 
-                adjust(pid, nice, &cpu_set);
+                // the scheduling policy and its real-time priority are optional and only changed if both are given,
+                // "-" can be used as a placeholder to leave them unchanged while still giving an I/O priority
+                let scheduling = env::args()
+                    .nth(4)
+                    .and_then(|s| s.trim().parse().ok())
+                    .zip(env::args().nth(5).and_then(|s| s.trim().parse().ok()));
+
+                // raw I/O priority as used by ioprio_set(), optional as well
+                let io_priority = env::args().nth(6).and_then(|s| s.trim().parse().ok());
+
+                adjust(pid, nice, &cpu_set, scheduling, io_priority);
 
                 // find tasks that belong to this process
                 let tasks_path = PathBuf::from("/proc/").join(pid.to_string()).join("task");
                 for entry in std::fs::read_dir(tasks_path).unwrap().flatten() {
                     let thread_id = entry.file_name().to_string_lossy().parse().unwrap();
 
-                    adjust(thread_id, nice, &cpu_set);
+                    adjust(thread_id, nice, &cpu_set, scheduling, io_priority);
                 }
 
                 std::process::exit(0)
@@ -34,7 +47,42 @@ fn main() {
     std::process::exit(255);
 }
 
-fn adjust(id: i32, nice: i32, cpu_set: &CpuSet) {
+fn adjust(
+    id: i32,
+    nice: i32,
+    cpu_set: &CpuSet,
+    scheduling: Option<(i32, i32)>,
+    io_priority: Option<i32>,
+) {
+    if let Some((policy, priority)) = scheduling {
+        let param = libc::sched_param {
+            sched_priority: priority,
+        };
+
+        let result = unsafe { libc::sched_setscheduler(id, policy, &param) };
+
+        if result == -1 {
+            std::process::exit(
+                std::io::Error::last_os_error()
+                    .raw_os_error()
+                    .unwrap_or_default(),
+            )
+        }
+    }
+
+    if let Some(io_priority) = io_priority {
+        let result =
+            unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, id, io_priority) };
+
+        if result == -1 {
+            std::process::exit(
+                std::io::Error::last_os_error()
+                    .raw_os_error()
+                    .unwrap_or_default(),
+            )
+        }
+    }
+
     unsafe {
         libc::setpriority(libc::PRIO_PROCESS, id as u32, nice);
     };
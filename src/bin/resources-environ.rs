@@ -0,0 +1,25 @@
+use std::{env, io::Write};
+
+use process_data::{environ::EnvironmentVariable, procfs::ProcfsRoot};
+
+fn main() {
+    if let Some(pid) = env::args().nth(1).and_then(|s| s.trim().parse().ok()) {
+        // we might be running as root through pkexec, so never read from anywhere but the real procfs
+        match EnvironmentVariable::raw_from_procfs(&ProcfsRoot::default(), pid) {
+            Ok(environ) => {
+                let mut stdout = std::io::stdout().lock();
+                if stdout
+                    .write_all(&environ)
+                    .and_then(|()| stdout.flush())
+                    .is_err()
+                {
+                    std::process::exit(253);
+                }
+                std::process::exit(0);
+            }
+            // Resources retries with pkexec if we exit with EPERM or EACCES
+            Err(err) => std::process::exit(err.raw_os_error().unwrap_or(253)),
+        }
+    }
+    std::process::exit(255);
+}
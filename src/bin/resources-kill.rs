@@ -1,21 +1,19 @@
 use std::env;
 
-use nix::{sys::signal, unistd::Pid};
+use nix::errno::Errno;
+use process_data::signal::parse_signal;
 
 fn main() {
     if let Some(pid) = env::args().nth(1).and_then(|s| s.trim().parse().ok()) {
         if let Some(arg) = env::args().nth(2) {
-            let signal = match arg.as_str() {
-                "STOP" => signal::Signal::SIGSTOP,
-                "CONT" => signal::Signal::SIGCONT,
-                "TERM" => signal::Signal::SIGTERM,
-                "KILL" => signal::Signal::SIGKILL,
-                _ => std::process::exit(254),
+            let Some(signal) = parse_signal(&arg) else {
+                std::process::exit(254);
             };
-            let result = signal::kill(Pid::from_raw(pid), Some(signal));
+            // nix's `Signal` doesn't cover real-time signals, so call kill(2) directly
+            let result = Errno::result(unsafe { libc::kill(pid, signal) });
             if let Err(errno) = result {
                 match errno {
-                    nix::errno::Errno::UnknownErrno => std::process::exit(253),
+                    Errno::UnknownErrno => std::process::exit(253),
                     _ => std::process::exit(errno as i32),
                 };
             }
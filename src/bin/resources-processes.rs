@@ -1,10 +1,35 @@
 use anyhow::Result;
-use process_data::ProcessData;
+use process_data::{
+    cgroup::{CgroupStats, CgroupfsRoot},
+    delta::DeltaTracker,
+    memory_maps::MemoryMapping,
+    open_files::OpenFile,
+    proc_connector::{ProcessEventListener, ProcessEvents},
+    procfs::ProcfsRoot,
+    protocol::{
+        Handshake, REQUEST_CGROUP_STATS, REQUEST_DELTA, REQUEST_HANDSHAKE, REQUEST_MEMORY_MAPS,
+        REQUEST_OPEN_FILES, REQUEST_PROCESS_EVENTS, REQUEST_SELECT_FIELDS,
+    },
+    scanner::{ProcessFields, ProcessScanner},
+    GpuEngineClass, ProcessData,
+};
 use ron::ser::PrettyConfig;
-use std::io::{Read, Write};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    path::PathBuf,
+    sync::LazyLock,
+    time::Duration,
+};
 
 use clap::Parser;
 
+static TICK_RATE: LazyLock<usize> =
+    LazyLock::new(|| sysconf::sysconf(sysconf::SysconfVariable::ScClkTck).unwrap_or(100) as usize);
+
+const MIN_INTERVAL_SECS: f64 = 0.1;
+
 #[derive(Parser, Debug)]
 #[command(version, about)]
 struct Args {
@@ -13,39 +38,364 @@ struct Args {
     once: bool,
 
     /// Use Rusty Object Notation (use this only for debugging this binary on its own, Resources won't be able to decode RON)
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, default_value_t = false, conflicts_with_all = ["json", "ndjson"])]
     ron: bool,
+
+    /// Output JSON, one array of all processes per sample
+    #[arg(short, long, default_value_t = false, conflicts_with = "ndjson")]
+    json: bool,
+
+    /// Output newline-delimited JSON, one object per process and line
+    #[arg(long, default_value_t = false)]
+    ndjson: bool,
+
+    /// Output a sample every INTERVAL seconds instead of waiting for requests on stdin. Combined with --once, a single
+    /// sample is output after INTERVAL seconds so that rates can be computed
+    #[arg(short, long, value_name = "INTERVAL", value_parser = parse_interval)]
+    interval: Option<Duration>,
+
+    /// Only output the process with this PID, can be given multiple times
+    #[arg(short, long = "pid", value_name = "PID")]
+    pids: Vec<libc::pid_t>,
+
+    /// Only output processes whose real user has this name or UID
+    #[arg(short, long)]
+    user: Option<String>,
+
+    /// Only output processes whose name or commandline contains this (case-insensitive)
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// Root of the procfs tree to collect process data from
+    #[arg(long, default_value = "/proc")]
+    procfs: PathBuf,
+
+    /// Root of the cgroups v2 hierarchy to collect cgroup statistics from
+    #[arg(long, default_value = "/sys/fs/cgroup")]
+    cgroupfs: PathBuf,
+}
+
+impl Args {
+    fn format(&self) -> Format {
+        if self.ron {
+            Format::Ron
+        } else if self.json {
+            Format::Json
+        } else if self.ndjson {
+            Format::NdJson
+        } else {
+            Format::MessagePack
+        }
+    }
+
+    fn matches(&self, process: &ProcessData) -> bool {
+        (self.pids.is_empty() || self.pids.contains(&process.pid))
+            && self.user.as_ref().map_or(true, |user| {
+                process.user == *user || process.uids.real.to_string() == *user
+            })
+            && self.name.as_ref().map_or(true, |name| {
+                let name = name.to_lowercase();
+                process.comm.to_lowercase().contains(&name)
+                    || process.commandline.to_lowercase().contains(&name)
+            })
+    }
+
+    fn process_data(&self, scanner: &mut ProcessScanner) -> Result<Vec<ProcessData>> {
+        let mut data = scanner.scan()?;
+        data.retain(|process| self.matches(process));
+        Ok(data)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    MessagePack,
+    Ron,
+    Json,
+    NdJson,
+}
+
+/// A process as it is output in JSON, including rates that are derived from the previous sample of the same process.
+/// Rates are `null` if there hasn't been a previous sample.
+#[derive(Debug, Serialize)]
+struct ProcessSample {
+    #[serde(flatten)]
+    data: ProcessData,
+    /// Percent of a single CPU core
+    cpu_usage: Option<f64>,
+    /// Bytes per second
+    read_speed: Option<f64>,
+    /// Bytes per second
+    write_speed: Option<f64>,
+    /// Percent, highest usage across all GPUs
+    gpu_usage: Option<f64>,
+}
+
+impl ProcessSample {
+    fn new(data: ProcessData, previous: Option<&ProcessData>) -> Self {
+        // make sure the PID hasn't been reused in the meantime
+        let previous = previous.filter(|previous| previous.starttime == data.starttime);
+
+        let elapsed_secs = previous
+            .map(|previous| data.timestamp.saturating_sub(previous.timestamp) as f64 / 1000.0)
+            .filter(|elapsed_secs| *elapsed_secs > 0.0);
+
+        let (Some(previous), Some(elapsed_secs)) = (previous, elapsed_secs) else {
+            return Self {
+                data,
+                cpu_usage: None,
+                read_speed: None,
+                write_speed: None,
+                gpu_usage: None,
+            };
+        };
+
+        let cpu_time = data.user_cpu_time.saturating_add(data.system_cpu_time);
+        let cpu_time_previous = previous
+            .user_cpu_time
+            .saturating_add(previous.system_cpu_time);
+        let cpu_usage = (cpu_time.saturating_sub(cpu_time_previous) as f64 / *TICK_RATE as f64)
+            / elapsed_secs
+            * 100.0;
+
+        let speed = |current: Option<u64>, previous: Option<u64>| {
+            current
+                .zip(previous)
+                .map(|(current, previous)| current.saturating_sub(previous) as f64 / elapsed_secs)
+        };
+        let read_speed = speed(data.read_bytes, previous.read_bytes);
+        let write_speed = speed(data.write_bytes, previous.write_bytes);
+
+        let elapsed_millis = data.timestamp.saturating_sub(previous.timestamp);
+        let gpu_usage = data
+            .gpu_usage_stats
+            .iter()
+            .filter_map(|(pci_slot, stats)| {
+                previous.gpu_usage_stats.get(pci_slot).map(|previous| {
+                    stats
+                        .engine_fractions(previous, elapsed_millis)
+                        .into_iter()
+                        .filter(|(engine, _)| {
+                            GpuEngineClass::from_engine_name(engine) == GpuEngineClass::Graphics
+                        })
+                        .map(|(_, fraction)| f64::from(fraction))
+                        .sum::<f64>()
+                        .min(1.0)
+                        * 100.0
+                })
+            })
+            .reduce(f64::max);
+
+        Self {
+            data,
+            cpu_usage: Some(cpu_usage),
+            read_speed,
+            write_speed,
+            gpu_usage,
+        }
+    }
+}
+
+/// Remembers the last sample of every process to derive rates from.
+#[derive(Debug, Default)]
+struct Sampler {
+    previous: HashMap<libc::pid_t, ProcessData>,
+}
+
+impl Sampler {
+    fn sample(&mut self, data: Vec<ProcessData>) -> Vec<ProcessSample> {
+        let previous = std::mem::take(&mut self.previous);
+
+        data.into_iter()
+            .map(|process| {
+                self.previous.insert(process.pid, process.clone());
+                let previous = previous.get(&process.pid);
+                ProcessSample::new(process, previous)
+            })
+            .collect()
+    }
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    let procfs = ProcfsRoot::new(&args.procfs);
+    let cgroupfs = CgroupfsRoot::new(&args.cgroupfs);
+
+    let mut scanner = ProcessScanner::new(procfs.clone());
+
+    if let Some(interval) = args.interval {
+        return sample_periodically(&args, &mut scanner, interval);
+    }
+
     if args.once {
-        output(args.ron)?;
-        return Ok(());
+        let mut sampler = Sampler::default();
+        return output_sample(
+            &mut sampler,
+            args.process_data(&mut scanner)?,
+            args.format(),
+        );
     }
 
+    let mut delta_tracker = DeltaTracker::new();
+    let mut sampler = Sampler::default();
+    // only subscribed to once process events are requested for the first time
+    let mut event_listener: Option<ProcessEventListener> = None;
+
     loop {
         let mut buffer = [0; 1];
 
         std::io::stdin().read_exact(&mut buffer)?;
 
-        output(args.ron)?;
+        match buffer[0] {
+            REQUEST_HANDSHAKE => output(&Handshake::default(), args.ron)?,
+            REQUEST_DELTA => {
+                let mut base_sequence = [0; 8];
+                std::io::stdin().read_exact(&mut base_sequence)?;
+
+                let data = args.process_data(&mut scanner)?;
+                let update = delta_tracker.update(u64::from_le_bytes(base_sequence), data);
+                output(&update, args.ron)?;
+            }
+            REQUEST_OPEN_FILES => {
+                let pid = read_pid()?;
+
+                let open_files =
+                    OpenFile::all_from_procfs(&procfs, pid).map_err(|err| format!("{err:#}"));
+                output(&open_files, args.ron)?;
+            }
+            REQUEST_MEMORY_MAPS => {
+                let pid = read_pid()?;
+
+                let memory_maps =
+                    MemoryMapping::all_from_procfs(&procfs, pid).map_err(|err| format!("{err:#}"));
+                output(&memory_maps, args.ron)?;
+            }
+            REQUEST_CGROUP_STATS => {
+                let cgroups: Vec<String> = read_arguments()?;
+
+                let cgroup_stats: Result<HashMap<String, CgroupStats>, String> = Ok(cgroups
+                    .into_iter()
+                    .filter_map(|cgroup| {
+                        CgroupStats::from_cgroupfs(&cgroupfs, &cgroup)
+                            .ok()
+                            .map(|stats| (cgroup, stats))
+                    })
+                    .collect());
+                output(&cgroup_stats, args.ron)?;
+            }
+            REQUEST_SELECT_FIELDS => {
+                let fields: ProcessFields = read_arguments()?;
+
+                scanner.set_fields(fields);
+                output(&Ok::<(), String>(()), args.ron)?;
+            }
+            REQUEST_PROCESS_EVENTS => {
+                let events: Result<ProcessEvents, String> = match &event_listener {
+                    Some(listener) => Ok(listener.take()),
+                    None => ProcessEventListener::start(procfs.clone())
+                        .map(|listener| event_listener.insert(listener).take())
+                        .map_err(|err| format!("{err:#}")),
+                };
+                output(&events, args.ron)?;
+            }
+            // everything else is treated as a legacy full request
+            _ => output_sample(
+                &mut sampler,
+                args.process_data(&mut scanner)?,
+                args.format(),
+            )?,
+        }
     }
 }
 
-fn output(ron: bool) -> Result<()> {
-    let data = ProcessData::all_process_data()?;
+/// Parses the sampling interval in seconds, rejecting values that `Duration` can't represent and intervals so short
+/// that sampling would just spin
+fn parse_interval(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = s.parse().map_err(|e| format!("{e}"))?;
+
+    if !seconds.is_finite() || seconds < MIN_INTERVAL_SECS {
+        return Err(format!(
+            "must be a number of seconds that is at least {MIN_INTERVAL_SECS}"
+        ));
+    }
+
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("{e}"))
+}
+
+fn read_pid() -> Result<libc::pid_t> {
+    let mut pid = [0; 4];
+    std::io::stdin().read_exact(&mut pid)?;
+    Ok(libc::pid_t::from_le_bytes(pid))
+}
+
+fn read_arguments<T: DeserializeOwned>() -> Result<T> {
+    let mut len = [0; 4];
+    std::io::stdin().read_exact(&mut len)?;
+
+    let mut arguments = vec![0; u32::from_le_bytes(len) as usize];
+    std::io::stdin().read_exact(&mut arguments)?;
+
+    Ok(rmp_serde::from_slice(&arguments)?)
+}
+
+fn sample_periodically(
+    args: &Args,
+    scanner: &mut ProcessScanner,
+    interval: Duration,
+) -> Result<()> {
+    let mut sampler = Sampler::default();
+
+    // take an initial sample that is not output so that every sample we do output has rates
+    sampler.sample(args.process_data(scanner)?);
+
+    loop {
+        std::thread::sleep(interval);
+
+        output_sample(&mut sampler, args.process_data(scanner)?, args.format())?;
+
+        if args.once {
+            return Ok(());
+        }
+    }
+}
+
+fn output_sample(sampler: &mut Sampler, data: Vec<ProcessData>, format: Format) -> Result<()> {
+    match format {
+        Format::MessagePack => output(&data, false),
+        Format::Ron => output(&data, true),
+        Format::Json | Format::NdJson => {
+            let samples = sampler.sample(data);
+
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+
+            if format == Format::Json {
+                serde_json::to_writer(&mut handle, &samples)?;
+                handle.write_all(b"\n")?;
+            } else {
+                for sample in &samples {
+                    serde_json::to_writer(&mut handle, sample)?;
+                    handle.write_all(b"\n")?;
+                }
+            }
+
+            handle.flush()?;
+            Ok(())
+        }
+    }
+}
 
+fn output<T: Serialize>(data: &T, ron: bool) -> Result<()> {
     let encoded = if ron {
-        ron::ser::to_string_pretty(&data, PrettyConfig::default())?
+        ron::ser::to_string_pretty(data, PrettyConfig::default())?
             .as_bytes()
             .to_vec()
     } else {
-        rmp_serde::to_vec(&data)?
+        rmp_serde::to_vec(data)?
     };
 
-    let len_byte_array = encoded.len().to_le_bytes();
+    let len_byte_array = (encoded.len() as u64).to_le_bytes();
 
     let stdout = std::io::stdout();
     let mut handle = stdout.lock();
@@ -44,6 +44,14 @@ mod imp {
         #[template_child]
         pub decoder_usage: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub pressure: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub cpu_pressure: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_pressure: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub io_pressure: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub id: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub running_since: TemplateChild<adw::ActionRow>,
@@ -190,5 +198,20 @@ impl ResAppDialog {
 
         imp.processes_amount
             .set_subtitle(&app.running_processes().to_string());
+
+        // pressure is only known for apps that have cgroups of their own
+        imp.pressure.set_visible(app.cpu_pressure() >= 0.0);
+
+        for (row, pressure) in [
+            (&imp.cpu_pressure, app.cpu_pressure()),
+            (&imp.memory_pressure, app.memory_pressure()),
+            (&imp.io_pressure, app.io_pressure()),
+        ] {
+            if pressure < 0.0 {
+                row.set_subtitle(&i18n("N/A"));
+            } else {
+                row.set_subtitle(&format!("{pressure:.1} %"));
+            }
+        }
     }
 }
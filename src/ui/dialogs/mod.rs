@@ -2,3 +2,4 @@ pub mod app_dialog;
 pub mod process_dialog;
 pub mod process_options_dialog;
 pub mod settings_dialog;
+pub mod unit_dialog;
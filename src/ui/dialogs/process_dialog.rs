@@ -1,10 +1,22 @@
 use adw::{prelude::*, subclass::prelude::*};
-use gtk::glib::{self, GString};
+use gtk::glib::{self, clone, closure, GString, MainContext, Object};
+use gtk::{gio, Widget};
+use log::warn;
+use process_data::environ::EnvironmentVariable;
+use process_data::memory_maps::{MemoryMapping, MemoryMappingTotals};
+use process_data::open_files::{FileDescriptorKind, OpenFile};
+use process_data::users::Ids;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
 use crate::config::PROFILE;
-use crate::i18n::i18n;
+use crate::i18n::{i18n, i18n_f, ni18n_f};
 use crate::ui::pages::processes::process_entry::ProcessEntry;
+use crate::ui::pages::processes::thread_entry::ThreadEntry;
+use crate::utils::process::Process;
+use crate::utils::settings::SETTINGS;
 use crate::utils::units::{convert_speed, convert_storage, format_time};
+use crate::utils::NUM_CPUS;
 
 mod imp {
 
@@ -24,6 +36,16 @@ mod imp {
         #[template_child]
         pub swap_usage: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub pss_usage: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub uss_usage: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub anonymous_memory: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub file_memory: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub shmem_usage: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub drive_read_speed: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub drive_read_total: TemplateChild<adw::ActionRow>,
@@ -54,9 +76,42 @@ mod imp {
         #[template_child]
         pub user: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub group: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub cgroup: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub threads_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub open_files_refresh_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub open_files_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub open_files_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub open_files_placeholder: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub memory_maps_refresh_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub memory_maps_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub memory_maps_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub memory_maps_placeholder: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub environ_copy_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub environ_load_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub environ_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub environ_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub environ_placeholder: TemplateChild<gtk::Label>,
+
+        pub process_pid: Cell<libc::pid_t>,
+        pub environ: RefCell<Vec<EnvironmentVariable>>,
     }
 
     #[glib::object_subclass]
@@ -110,6 +165,423 @@ impl ResProcessDialog {
 
     pub fn init<S: AsRef<str>>(&self, process: &ProcessEntry, user: S) {
         self.setup_widgets(process, user.as_ref());
+        self.setup_signals();
+        self.refresh_open_files();
+        self.refresh_memory_maps();
+    }
+
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.open_files_refresh_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.refresh_open_files()
+        ));
+
+        imp.open_files_search_entry.connect_search_changed(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.imp().open_files_list.invalidate_filter()
+        ));
+
+        imp.open_files_list.set_filter_func(clone!(
+            #[weak(rename_to = this)]
+            self,
+            #[upgrade_or]
+            true,
+            move |row| {
+                let search_string = this
+                    .imp()
+                    .open_files_search_entry
+                    .text()
+                    .to_string()
+                    .to_lowercase();
+
+                row.downcast_ref::<adw::ActionRow>().is_some_and(|row| {
+                    row.title().to_lowercase().contains(&search_string)
+                        || row.subtitle().is_some_and(|subtitle| {
+                            subtitle.to_lowercase().contains(&search_string)
+                        })
+                })
+            }
+        ));
+
+        imp.memory_maps_refresh_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.refresh_memory_maps()
+        ));
+
+        imp.memory_maps_search_entry.connect_search_changed(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.imp().memory_maps_list.invalidate_filter()
+        ));
+
+        imp.memory_maps_list.set_filter_func(clone!(
+            #[weak(rename_to = this)]
+            self,
+            #[upgrade_or]
+            true,
+            move |row| {
+                let search_string = this
+                    .imp()
+                    .memory_maps_search_entry
+                    .text()
+                    .to_string()
+                    .to_lowercase();
+
+                row.downcast_ref::<adw::ExpanderRow>()
+                    .is_some_and(|row| row.title().to_lowercase().contains(&search_string))
+            }
+        ));
+
+        imp.environ_load_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.refresh_environ()
+        ));
+
+        imp.environ_copy_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |button| {
+                let environ = this
+                    .imp()
+                    .environ
+                    .borrow()
+                    .iter()
+                    .map(|variable| format!("{}={}", variable.name, variable.value))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                button.clipboard().set_text(&environ);
+            }
+        ));
+
+        imp.environ_search_entry.connect_search_changed(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.imp().environ_list.invalidate_filter()
+        ));
+
+        imp.environ_list.set_filter_func(clone!(
+            #[weak(rename_to = this)]
+            self,
+            #[upgrade_or]
+            true,
+            move |row| {
+                let search_string = this
+                    .imp()
+                    .environ_search_entry
+                    .text()
+                    .to_string()
+                    .to_lowercase();
+
+                row.downcast_ref::<adw::ActionRow>().is_some_and(|row| {
+                    row.title().to_lowercase().contains(&search_string)
+                        || row.subtitle().is_some_and(|subtitle| {
+                            subtitle.to_lowercase().contains(&search_string)
+                        })
+                })
+            }
+        ));
+    }
+
+    /// Queries the open files of the process in the background and replaces the currently displayed ones.
+    pub fn refresh_open_files(&self) {
+        let imp = self.imp();
+
+        imp.open_files_placeholder.set_label(&i18n("Loading…"));
+        while let Some(row) = imp.open_files_list.row_at_index(0) {
+            imp.open_files_list.remove(&row);
+        }
+
+        let pid = imp.process_pid.get();
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let result = gio::spawn_blocking(move || Process::open_files(pid)).await;
+
+                let imp = this.imp();
+
+                match result {
+                    Ok(Ok(open_files)) => {
+                        imp.open_files_placeholder
+                            .set_label(&i18n("No open files found"));
+                        for open_file in &open_files {
+                            imp.open_files_list.append(&Self::open_file_row(open_file));
+                        }
+                    }
+                    Ok(Err(error)) => {
+                        warn!("Unable to get open files of process {pid}: {error}");
+                        imp.open_files_placeholder
+                            .set_label(&i18n("Unable to get open files of this process"));
+                    }
+                    Err(_) => {
+                        imp.open_files_placeholder
+                            .set_label(&i18n("Unable to get open files of this process"));
+                    }
+                }
+            }
+        ));
+    }
+
+    /// Queries the memory mappings of the process in the background and replaces the currently displayed ones.
+    pub fn refresh_memory_maps(&self) {
+        let imp = self.imp();
+
+        imp.memory_maps_placeholder.set_label(&i18n("Loading…"));
+        while let Some(row) = imp.memory_maps_list.row_at_index(0) {
+            imp.memory_maps_list.remove(&row);
+        }
+
+        let pid = imp.process_pid.get();
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let result = gio::spawn_blocking(move || Process::memory_maps(pid)).await;
+
+                let imp = this.imp();
+
+                match result {
+                    Ok(Ok(mappings)) => {
+                        imp.memory_maps_placeholder
+                            .set_label(&i18n("No memory maps found"));
+
+                        let mut grouped: HashMap<Option<String>, Vec<MemoryMapping>> =
+                            HashMap::new();
+                        for mapping in &mappings {
+                            grouped
+                                .entry(mapping.pathname.clone())
+                                .or_default()
+                                .push(mapping.clone());
+                        }
+
+                        for totals in MemoryMapping::totals_by_pathname(&mappings) {
+                            let mappings = grouped.remove(&totals.pathname).unwrap_or_default();
+                            imp.memory_maps_list
+                                .append(&Self::memory_map_totals_row(&totals, mappings));
+                        }
+                    }
+                    Ok(Err(error)) => {
+                        warn!("Unable to get memory maps of process {pid}: {error}");
+                        imp.memory_maps_placeholder
+                            .set_label(&i18n("Unable to get memory maps of this process"));
+                    }
+                    Err(_) => {
+                        imp.memory_maps_placeholder
+                            .set_label(&i18n("Unable to get memory maps of this process"));
+                    }
+                }
+            }
+        ));
+    }
+
+    /// Reads the environment variables of the process in the background and replaces the currently displayed ones.
+    /// Since this might ask for authentication, it's only done when the user explicitly requests it.
+    pub fn refresh_environ(&self) {
+        let imp = self.imp();
+
+        imp.environ_placeholder.set_label(&i18n("Loading…"));
+        imp.environ_copy_button.set_sensitive(false);
+        imp.environ.borrow_mut().clear();
+        while let Some(row) = imp.environ_list.row_at_index(0) {
+            imp.environ_list.remove(&row);
+        }
+
+        let pid = imp.process_pid.get();
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let result = gio::spawn_blocking(move || Process::environ(pid)).await;
+
+                let imp = this.imp();
+
+                match result {
+                    Ok(Ok(environ)) => {
+                        imp.environ_placeholder
+                            .set_label(&i18n("No environment variables found"));
+                        for variable in &environ {
+                            imp.environ_list.append(&Self::environ_row(variable));
+                        }
+                        imp.environ_copy_button.set_sensitive(!environ.is_empty());
+                        imp.environ.replace(environ);
+                    }
+                    Ok(Err(error)) => {
+                        warn!("Unable to get environment variables of process {pid}: {error}");
+                        imp.environ_placeholder.set_label(&i18n(
+                            "Unable to get environment variables of this process",
+                        ));
+                    }
+                    Err(_) => {
+                        imp.environ_placeholder.set_label(&i18n(
+                            "Unable to get environment variables of this process",
+                        ));
+                    }
+                }
+            }
+        ));
+    }
+
+    fn environ_row(variable: &EnvironmentVariable) -> adw::ActionRow {
+        let row = adw::ActionRow::new();
+        row.add_css_class("property");
+        row.set_subtitle_selectable(true);
+
+        // values are arbitrary strings that must not be interpreted as markup
+        row.set_use_markup(false);
+        row.set_title(&variable.name);
+        row.set_subtitle(&variable.value);
+
+        let copy_button = gtk::Button::builder()
+            .icon_name("edit-copy-symbolic")
+            .valign(gtk::Align::Center)
+            .tooltip_text(i18n("Copy"))
+            .css_classes(["flat"])
+            .build();
+
+        let text = format!("{}={}", variable.name, variable.value);
+        copy_button.connect_clicked(move |button| button.clipboard().set_text(&text));
+
+        row.add_suffix(&copy_button);
+
+        row
+    }
+
+    fn memory_map_totals_row(
+        totals: &MemoryMappingTotals,
+        mappings: Vec<MemoryMapping>,
+    ) -> adw::ExpanderRow {
+        let row = adw::ExpanderRow::new();
+
+        row.set_use_markup(false);
+        row.set_title(
+            &totals
+                .pathname
+                .clone()
+                .unwrap_or_else(|| i18n("Anonymous Memory")),
+        );
+        row.set_subtitle(&format!(
+            "{} · {}",
+            i18n_f(
+                "RSS {} · PSS {}",
+                &[
+                    &convert_storage(totals.rss as f64, false),
+                    &convert_storage(totals.pss as f64, false)
+                ]
+            ),
+            ni18n_f(
+                "{} mapping",
+                "{} mappings",
+                totals.mappings as u32,
+                &[&totals.mappings.to_string()]
+            )
+        ));
+
+        // a process can have thousands of mappings, so only create their rows once they are needed
+        let mappings = RefCell::new(Some(mappings));
+        row.connect_expanded_notify(move |row| {
+            if let Some(mappings) = mappings.take() {
+                for mapping in &mappings {
+                    row.add_row(&Self::memory_mapping_row(mapping));
+                }
+            }
+        });
+
+        row
+    }
+
+    fn memory_mapping_row(mapping: &MemoryMapping) -> adw::ActionRow {
+        let row = adw::ActionRow::new();
+        row.add_css_class("property");
+        row.set_subtitle_selectable(true);
+
+        row.set_title(&format!("{:x}–{:x}", mapping.start, mapping.end));
+
+        let mut subtitle = vec![
+            mapping.permissions.clone(),
+            i18n_f("Offset {}", &[&format!("{:#x}", mapping.offset)]),
+            i18n_f("Size {}", &[&convert_storage(mapping.size() as f64, false)]),
+        ];
+
+        if let (Some(rss), Some(pss)) = (mapping.rss, mapping.pss) {
+            subtitle.push(i18n_f(
+                "RSS {} · PSS {}",
+                &[
+                    &convert_storage(rss as f64, false),
+                    &convert_storage(pss as f64, false),
+                ],
+            ));
+        }
+
+        row.set_subtitle(&subtitle.join(" · "));
+
+        row
+    }
+
+    fn open_file_row(open_file: &OpenFile) -> adw::ActionRow {
+        let row = adw::ActionRow::new();
+        row.add_css_class("property");
+        row.set_subtitle_selectable(true);
+
+        // paths may contain characters that would be interpreted as markup
+        row.set_use_markup(false);
+        row.set_title(&open_file.target);
+        row.set_tooltip_text(Some(&open_file.target));
+
+        let kind = match open_file.kind {
+            FileDescriptorKind::File => i18n("File"),
+            FileDescriptorKind::Socket => i18n("Socket"),
+            FileDescriptorKind::Pipe => i18n("Pipe"),
+            FileDescriptorKind::AnonInode => i18n("Anonymous Inode"),
+            FileDescriptorKind::DrmDevice => i18n("Graphics Device"),
+        };
+
+        let mut subtitle = vec![i18n_f("FD {}", &[&open_file.fd.to_string()]), kind];
+
+        match (open_file.readable(), open_file.writable()) {
+            (Some(true), Some(true)) => subtitle.push(i18n("Read/Write")),
+            (Some(true), _) => subtitle.push(i18n("Read")),
+            (_, Some(true)) => subtitle.push(i18n("Write")),
+            _ => {}
+        }
+
+        if let Some(position) = open_file.position {
+            subtitle.push(i18n_f("Position {}", &[&position.to_string()]));
+        }
+
+        row.set_subtitle(&subtitle.join(" · "));
+
+        row
+    }
+
+    /// Shows the real user or group in `row`, along with the effective one if it differs (e.g. for setuid programs).
+    /// All numeric IDs are shown in the tooltip.
+    fn set_ids_row(row: &adw::ActionRow, real: &str, effective: &str, ids: Ids) {
+        if ids.effective == ids.real {
+            row.set_subtitle(real);
+        } else {
+            row.set_subtitle(&i18n_f("{} (effective: {})", &[real, effective]));
+        }
+
+        row.set_tooltip_text(Some(&i18n_f(
+            "Real: {}, effective: {}, saved: {}",
+            &[
+                &ids.real.to_string(),
+                &ids.effective.to_string(),
+                &ids.saved.to_string(),
+            ],
+        )));
     }
 
     pub fn setup_widgets(&self, process: &ProcessEntry, user: &str) {
@@ -117,7 +589,15 @@ impl ResProcessDialog {
 
         imp.name.set_label(&process.name());
 
-        imp.user.set_subtitle(user);
+        imp.process_pid.set(process.pid());
+
+        Self::set_ids_row(&imp.user, user, &process.effective_user(), process.uids());
+        Self::set_ids_row(
+            &imp.group,
+            &process.group(),
+            &process.effective_group(),
+            process.gids(),
+        );
 
         imp.pid.set_subtitle(&process.pid().to_string());
 
@@ -144,9 +624,60 @@ impl ResProcessDialog {
 
         imp.containerized.set_subtitle(&process.containerization());
 
+        imp.threads_list
+            .bind_model(Some(&process.threads()), |object| {
+                let thread = object.downcast_ref::<ThreadEntry>().unwrap();
+                Self::thread_row(thread).upcast()
+            });
+
         self.update(process);
     }
 
+    fn thread_row(thread: &ThreadEntry) -> adw::ActionRow {
+        let row = adw::ActionRow::new();
+        row.add_css_class("property");
+        row.set_subtitle_selectable(true);
+
+        let cpu_usage_label = gtk::Label::new(None);
+        cpu_usage_label.add_css_class("numeric");
+        row.add_suffix(&cpu_usage_label);
+
+        thread
+            .property_expression("name")
+            .bind(&row, "title", Widget::NONE);
+
+        gtk::ClosureExpression::new::<String>(
+            [
+                thread.property_expression("tid"),
+                thread.property_expression("state"),
+                thread.property_expression("last_cpu"),
+            ],
+            closure!(
+                |_: Option<Object>, tid: i32, state: GString, last_cpu: u32| {
+                    i18n_f(
+                        "TID {} · {} · Last on CPU {}",
+                        &[&tid.to_string(), &state, &last_cpu.to_string()],
+                    )
+                }
+            ),
+        )
+        .bind(&row, "subtitle", Widget::NONE);
+
+        thread
+            .property_expression("cpu_usage")
+            .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
+                let mut percentage = cpu_usage * 100.0;
+                if !SETTINGS.normalize_cpu_usage() {
+                    percentage *= *NUM_CPUS as f32;
+                }
+
+                format!("{percentage:.1} %")
+            }))
+            .bind(&cpu_usage_label, "label", Widget::NONE);
+
+        row
+    }
+
     pub fn update(&self, process: &ProcessEntry) {
         let imp = self.imp();
 
@@ -159,6 +690,22 @@ impl ResProcessDialog {
         imp.swap_usage
             .set_subtitle(&convert_storage(process.swap_usage() as f64, false));
 
+        // smaps_rollup is only readable for our own processes
+        let smaps_rollup = process.smaps_rollup();
+        let set_memory_subtitle = |row: &adw::ActionRow, value: Option<usize>| {
+            row.set_subtitle(
+                &value.map_or_else(|| i18n("N/A"), |value| convert_storage(value as f64, false)),
+            );
+        };
+        set_memory_subtitle(&imp.pss_usage, smaps_rollup.map(|smaps| smaps.pss));
+        set_memory_subtitle(&imp.uss_usage, smaps_rollup.map(|smaps| smaps.uss));
+        set_memory_subtitle(
+            &imp.anonymous_memory,
+            smaps_rollup.map(|smaps| smaps.anonymous),
+        );
+        set_memory_subtitle(&imp.file_memory, smaps_rollup.map(|smaps| smaps.file));
+        set_memory_subtitle(&imp.shmem_usage, smaps_rollup.map(|smaps| smaps.shmem));
+
         if process.read_speed() == -1.0 {
             imp.drive_read_speed.set_subtitle(&i18n("N/A"));
         } else {
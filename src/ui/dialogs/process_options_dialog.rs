@@ -10,7 +10,7 @@ use crate::{
 use adw::{prelude::*, subclass::prelude::*, ToastOverlay};
 use async_channel::Sender;
 use gtk::glib::{self, clone, MainContext};
-use process_data::Niceness;
+use process_data::{IoPriority, IoPriorityClass, Niceness, SchedulingPolicy};
 
 mod imp {
 
@@ -32,13 +32,27 @@ mod imp {
         #[template_child]
         pub priority_row: TemplateChild<adw::ComboRow>,
         #[template_child]
+        pub scheduling_policy_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub realtime_priority_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
         pub affinity_row: TemplateChild<adw::ExpanderRow>,
         #[template_child]
+        pub io_priority_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub io_priority_class_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub io_priority_level_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
         pub select_all_button: TemplateChild<gtk::Button>,
 
         pub cpu_rows: RefCell<Vec<adw::SwitchRow>>,
 
         pub pid: Cell<libc::pid_t>,
+
+        pub initial_scheduling: Cell<(SchedulingPolicy, u32)>,
+
+        pub initial_io_priority: Cell<Option<IoPriority>>,
     }
 
     #[glib::object_subclass]
@@ -86,6 +100,23 @@ impl Default for ResProcessOptionsDialog {
 }
 
 impl ResProcessOptionsDialog {
+    /// The scheduling policies in the order they appear in `scheduling_policy_row`
+    const SCHEDULING_POLICIES: [SchedulingPolicy; 5] = [
+        SchedulingPolicy::Other,
+        SchedulingPolicy::Batch,
+        SchedulingPolicy::Idle,
+        SchedulingPolicy::Fifo,
+        SchedulingPolicy::RoundRobin,
+    ];
+
+    /// The I/O priority classes in the order they appear in `io_priority_class_row`
+    const IO_PRIORITY_CLASSES: [IoPriorityClass; 4] = [
+        IoPriorityClass::None,
+        IoPriorityClass::RealTime,
+        IoPriorityClass::BestEffort,
+        IoPriorityClass::Idle,
+    ];
+
     pub fn new() -> Self {
         glib::Object::new::<Self>()
     }
@@ -117,6 +148,66 @@ impl ResProcessOptionsDialog {
         }
     }
 
+    fn get_selected_scheduling_policy(&self) -> SchedulingPolicy {
+        Self::SCHEDULING_POLICIES
+            .get(self.imp().scheduling_policy_row.selected() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the selected scheduling policy and real-time priority, or `None` if the user hasn't changed them
+    fn get_current_scheduling(&self) -> Option<(SchedulingPolicy, u32)> {
+        let imp = self.imp();
+
+        if !imp.scheduling_policy_row.is_visible() {
+            return None;
+        }
+
+        let policy = self.get_selected_scheduling_policy();
+
+        let priority = if policy.is_realtime() {
+            imp.realtime_priority_row.value() as u32
+        } else {
+            0
+        };
+
+        Some((policy, priority)).filter(|scheduling| *scheduling != imp.initial_scheduling.get())
+    }
+
+    fn get_selected_io_priority_class(&self) -> IoPriorityClass {
+        Self::IO_PRIORITY_CLASSES
+            .get(self.imp().io_priority_class_row.selected() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the selected I/O priority, or `None` if the user hasn't changed it
+    fn get_current_io_priority(&self) -> Option<IoPriority> {
+        let imp = self.imp();
+
+        if !imp.io_priority_group.is_visible() {
+            return None;
+        }
+
+        let class = self.get_selected_io_priority_class();
+
+        let level = if Self::io_priority_class_has_level(class) {
+            imp.io_priority_level_row.value() as u8
+        } else {
+            0
+        };
+
+        Some(IoPriority { class, level })
+            .filter(|io_priority| Some(*io_priority) != imp.initial_io_priority.get())
+    }
+
+    fn io_priority_class_has_level(class: IoPriorityClass) -> bool {
+        matches!(
+            class,
+            IoPriorityClass::RealTime | IoPriorityClass::BestEffort
+        )
+    }
+
     pub fn setup_widgets(&self, process: &ProcessEntry) {
         let imp = self.imp();
 
@@ -136,6 +227,48 @@ impl ResProcessOptionsDialog {
             imp.nice_row.set_visible(false);
         }
 
+        let scheduling_policy = process.scheduling_policy();
+
+        imp.initial_scheduling
+            .set((scheduling_policy, process.realtime_priority()));
+
+        if let Some(i) = Self::SCHEDULING_POLICIES
+            .iter()
+            .position(|policy| *policy == scheduling_policy)
+        {
+            imp.scheduling_policy_row.set_selected(i as u32);
+            imp.realtime_priority_row
+                .set_value(process.realtime_priority().max(1) as f64);
+            imp.realtime_priority_row
+                .set_visible(scheduling_policy.is_realtime());
+        } else {
+            // we can't switch away from SCHED_DEADLINE, so don't offer it in the first place
+            imp.scheduling_policy_row.set_visible(false);
+            imp.realtime_priority_row.set_visible(false);
+        }
+
+        imp.initial_io_priority.set(process.io_priority());
+
+        if let Some(io_priority) = process.io_priority() {
+            let class_has_level = Self::io_priority_class_has_level(io_priority.class);
+
+            imp.io_priority_class_row.set_selected(
+                Self::IO_PRIORITY_CLASSES
+                    .iter()
+                    .position(|class| *class == io_priority.class)
+                    .unwrap_or_default() as u32,
+            );
+            // best effort is what the kernel falls back to, so start with its default level of 4
+            imp.io_priority_level_row.set_value(if class_has_level {
+                io_priority.level as f64
+            } else {
+                4.0
+            });
+            imp.io_priority_level_row.set_visible(class_has_level);
+        } else {
+            imp.io_priority_group.set_visible(false);
+        }
+
         for (i, affinity) in process.affinity().iter().enumerate() {
             let switch_row = adw::SwitchRow::builder()
                 .title(i18n_f("CPU {}", &[&(i + 1).to_string()]))
@@ -174,6 +307,28 @@ impl ResProcessOptionsDialog {
     ) {
         let imp = self.imp();
 
+        imp.scheduling_policy_row.connect_selected_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                this.imp()
+                    .realtime_priority_row
+                    .set_visible(this.get_selected_scheduling_policy().is_realtime());
+            }
+        ));
+
+        imp.io_priority_class_row.connect_selected_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                this.imp()
+                    .io_priority_level_row
+                    .set_visible(Self::io_priority_class_has_level(
+                        this.get_selected_io_priority_class(),
+                    ));
+            }
+        ));
+
         imp.select_all_button.connect_clicked(clone!(
             #[weak(rename_to = this)]
             self,
@@ -219,6 +374,8 @@ impl ResProcessOptionsDialog {
                                 process.pid(),
                                 this.get_current_niceness(),
                                 affinity,
+                                this.get_current_scheduling(),
+                                this.get_current_io_priority(),
                                 process.name().to_string(),
                                 toast_overlay.clone(),
                             ))
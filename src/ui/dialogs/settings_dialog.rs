@@ -3,7 +3,9 @@ use gtk::glib;
 
 use crate::{
     config::PROFILE,
-    utils::settings::{Base, RefreshSpeed, SidebarMeterType, TemperatureUnit, SETTINGS},
+    utils::settings::{
+        Base, MemoryMetric, RefreshSpeed, SidebarMeterType, TemperatureUnit, SETTINGS,
+    },
 };
 
 mod imp {
@@ -38,6 +40,8 @@ mod imp {
         pub sidebar_meter_type_row: TemplateChild<adw::ComboRow>,
         #[template_child]
         pub normalize_cpu_usage_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub memory_metric_combo_row: TemplateChild<adw::ComboRow>,
 
         #[template_child]
         pub apps_show_memory_row: TemplateChild<adw::SwitchRow>,
@@ -98,6 +102,18 @@ mod imp {
         pub processes_show_priority_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub processes_show_swap_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_state_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_threads_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_process_group_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_session_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_tty_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_kernel_thread_row: TemplateChild<adw::SwitchRow>,
 
         #[template_child]
         pub show_virtual_drives_row: TemplateChild<adw::SwitchRow>,
@@ -187,6 +203,8 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.show_search_on_start());
         imp.normalize_cpu_usage_row
             .set_active(SETTINGS.normalize_cpu_usage());
+        imp.memory_metric_combo_row
+            .set_selected((SETTINGS.memory_metric() as u8) as u32);
 
         imp.apps_show_memory_row
             .set_active(SETTINGS.apps_show_memory());
@@ -244,6 +262,18 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.processes_show_system_cpu_time());
         imp.processes_show_swap_row
             .set_active(SETTINGS.processes_show_swap());
+        imp.processes_show_state_row
+            .set_active(SETTINGS.processes_show_state());
+        imp.processes_show_threads_row
+            .set_active(SETTINGS.processes_show_threads());
+        imp.processes_show_process_group_row
+            .set_active(SETTINGS.processes_show_process_group());
+        imp.processes_show_session_row
+            .set_active(SETTINGS.processes_show_session());
+        imp.processes_show_tty_row
+            .set_active(SETTINGS.processes_show_tty());
+        imp.processes_show_kernel_thread_row
+            .set_active(SETTINGS.processes_show_kernel_thread());
 
         imp.show_virtual_drives_row
             .set_active(SETTINGS.show_virtual_drives());
@@ -316,6 +346,13 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_normalize_cpu_usage(switch_row.is_active());
             });
 
+        imp.memory_metric_combo_row
+            .connect_selected_item_notify(|combo_row| {
+                if let Some(memory_metric) = MemoryMetric::from_repr(combo_row.selected() as u8) {
+                    let _ = SETTINGS.set_memory_metric(memory_metric);
+                }
+            });
+
         imp.apps_show_cpu_row.connect_active_notify(|switch_row| {
             let _ = SETTINGS.set_apps_show_cpu(switch_row.is_active());
         });
@@ -457,6 +494,36 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_processes_show_swap(switch_row.is_active());
             });
 
+        imp.processes_show_state_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_state(switch_row.is_active());
+            });
+
+        imp.processes_show_threads_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_threads(switch_row.is_active());
+            });
+
+        imp.processes_show_process_group_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_process_group(switch_row.is_active());
+            });
+
+        imp.processes_show_session_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_session(switch_row.is_active());
+            });
+
+        imp.processes_show_tty_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_tty(switch_row.is_active());
+            });
+
+        imp.processes_show_kernel_thread_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_kernel_thread(switch_row.is_active());
+            });
+
         imp.show_virtual_drives_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_show_virtual_drives(switch_row.is_active());
@@ -0,0 +1,195 @@
+use std::cell::RefCell;
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::pages::services::unit_entry::UnitEntry;
+use crate::utils::settings::SETTINGS;
+use crate::utils::unit::ProcessTreeNode;
+use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::NUM_CPUS;
+use adw::{prelude::*, subclass::prelude::*};
+use gtk::glib;
+
+mod imp {
+
+    use super::*;
+
+    use gtk::CompositeTemplate;
+
+    #[derive(Debug, CompositeTemplate, Default)]
+    #[template(resource = "/net/nokyan/Resources/ui/dialogs/unit_dialog.ui")]
+    pub struct ResUnitDialog {
+        #[template_child]
+        pub name: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub cpu_usage: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_usage: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_max: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub oom_kills: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub drive_read_speed: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub drive_read_total: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub drive_write_speed: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub drive_write_total: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub slice: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub cgroup: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub processes_amount: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub tasks: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub process_tree_list: TemplateChild<gtk::ListBox>,
+
+        pub process_tree: RefCell<Vec<ProcessTreeNode>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResUnitDialog {
+        const NAME: &'static str = "ResUnitDialog";
+        type Type = super::ResUnitDialog;
+        type ParentType = adw::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResUnitDialog {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+    }
+
+    impl WidgetImpl for ResUnitDialog {}
+    impl WindowImpl for ResUnitDialog {}
+    impl AdwDialogImpl for ResUnitDialog {}
+}
+
+glib::wrapper! {
+    pub struct ResUnitDialog(ObjectSubclass<imp::ResUnitDialog>)
+        @extends gtk::Widget, adw::Dialog;
+}
+
+impl Default for ResUnitDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResUnitDialog {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn init(&self, unit: &UnitEntry) {
+        self.setup_widgets(unit);
+    }
+
+    pub fn setup_widgets(&self, unit: &UnitEntry) {
+        let imp = self.imp();
+
+        imp.name.set_label(&unit.name());
+
+        if unit.slice().is_empty() {
+            imp.slice.set_visible(false);
+        } else {
+            imp.slice.set_subtitle(&unit.slice());
+        }
+
+        imp.cgroup.set_subtitle(&unit.cgroup());
+
+        self.update(unit);
+    }
+
+    pub fn update(&self, unit: &UnitEntry) {
+        let imp = self.imp();
+
+        let mut percentage = unit.cpu_usage() * 100.0;
+        if !SETTINGS.normalize_cpu_usage() {
+            percentage *= *NUM_CPUS as f32;
+        }
+        imp.cpu_usage.set_subtitle(&format!("{percentage:.1} %"));
+
+        imp.memory_usage
+            .set_subtitle(&convert_storage(unit.memory_usage() as f64, false));
+
+        if unit.memory_max() == 0 {
+            imp.memory_max.set_subtitle(&i18n("Unlimited"));
+        } else {
+            imp.memory_max
+                .set_subtitle(&convert_storage(unit.memory_max() as f64, false));
+        }
+
+        imp.oom_kills.set_subtitle(&unit.oom_kills().to_string());
+
+        imp.drive_read_speed
+            .set_subtitle(&convert_speed(unit.read_speed(), false));
+
+        imp.drive_read_total
+            .set_subtitle(&convert_storage(unit.read_total() as f64, false));
+
+        imp.drive_write_speed
+            .set_subtitle(&convert_speed(unit.write_speed(), false));
+
+        imp.drive_write_total
+            .set_subtitle(&convert_storage(unit.write_total() as f64, false));
+
+        imp.processes_amount
+            .set_subtitle(&unit.running_processes().to_string());
+
+        imp.tasks.set_subtitle(&unit.tasks().to_string());
+
+        self.update_process_tree(unit.process_tree());
+    }
+
+    /// Rebuilds the rows of the process tree, but only if the processes of the unit have changed since the last time.
+    fn update_process_tree(&self, process_tree: Vec<ProcessTreeNode>) {
+        let imp = self.imp();
+
+        if *imp.process_tree.borrow() == process_tree {
+            return;
+        }
+
+        imp.process_tree_list.remove_all();
+
+        for node in &process_tree {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&node.display_name))
+                .subtitle(i18n_f("PID {}", &[&node.pid.to_string()]))
+                .build();
+
+            // indent children below their parent
+            let indentation = gtk::Box::builder()
+                .width_request((node.depth * 24) as i32)
+                .build();
+            row.add_prefix(&indentation);
+
+            if node.depth > 0 {
+                row.add_prefix(&gtk::Image::from_icon_name("pan-end-symbolic"));
+            }
+
+            imp.process_tree_list.append(&row);
+        }
+
+        imp.process_tree.replace(process_tree);
+    }
+}
@@ -1,8 +1,8 @@
 use gtk::glib::{self};
-use process_data::Containerization;
+use process_data::pressure::PressureResource;
 
 use crate::{
-    i18n::i18n,
+    ui::pages::get_containerization_label,
     utils::app::{App, AppsContext},
 };
 
@@ -78,6 +78,15 @@ mod imp {
         #[property(get, set)]
         running_processes: Cell<u32>,
 
+        #[property(get, set)]
+        cpu_pressure: Cell<f32>, // will be -1.0 if pressure data is not available
+
+        #[property(get, set)]
+        memory_pressure: Cell<f32>, // will be -1.0 if pressure data is not available
+
+        #[property(get, set)]
+        io_pressure: Cell<f32>, // will be -1.0 if pressure data is not available
+
         // TODO: Make this properly dynamic, don't use a variable that's never read
         #[property(get = Self::symbolic)]
         #[allow(dead_code)]
@@ -106,6 +115,9 @@ mod imp {
                 running_since: Cell::new(None),
                 containerization: Cell::new(glib::GString::default()),
                 running_processes: Cell::new(0),
+                cpu_pressure: Cell::new(-1.0),
+                memory_pressure: Cell::new(-1.0),
+                io_pressure: Cell::new(-1.0),
             }
         }
     }
@@ -179,18 +191,15 @@ glib::wrapper! {
 
 impl ApplicationEntry {
     pub fn new(app: &App, apps_context: &AppsContext) -> Self {
-        let containerization = match app.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
-            Containerization::Snap => i18n("Yes (Snap)"),
-        };
-
         let this: Self = glib::Object::builder()
             .property("name", &app.display_name)
             .property("icon", &app.icon)
             .property("id", &app.id)
             .property("description", &app.description)
-            .property("containerization", containerization)
+            .property(
+                "containerization",
+                get_containerization_label(&app.containerization),
+            )
             .property("running_since", app.running_since(apps_context).ok())
             .build();
         this.update(app, apps_context);
@@ -210,5 +219,13 @@ impl ApplicationEntry {
         self.set_dec_usage(app.dec_usage(apps_context));
         self.set_gpu_mem_usage(app.gpu_mem_usage(apps_context));
         self.set_running_processes(app.running_processes() as u32);
+
+        let pressure = |resource| {
+            app.pressure(apps_context, resource)
+                .map_or(-1.0, |pressure| pressure.some.avg10)
+        };
+        self.set_cpu_pressure(pressure(PressureResource::Cpu));
+        self.set_memory_pressure(pressure(PressureResource::Memory));
+        self.set_io_pressure(pressure(PressureResource::Io));
     }
 }
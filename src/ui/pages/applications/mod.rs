@@ -11,6 +11,7 @@ use gtk::{
     gio, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, ListItem, NumericSorter,
     SortType, StringSorter, Widget,
 };
+use process_data::signal::{parse_signal, signal_name};
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
@@ -203,6 +204,27 @@ mod imp {
                 },
             );
 
+            klass.install_action(
+                "applications.context-send-signal",
+                Some(glib::VariantTy::STRING),
+                move |res_applications, _, parameter| {
+                    let Some(signal) = parameter
+                        .and_then(glib::Variant::str)
+                        .and_then(parse_signal)
+                    else {
+                        return;
+                    };
+                    if let Some(application_entry) =
+                        res_applications.imp().popped_over_app.borrow().as_ref()
+                    {
+                        res_applications.open_app_action_dialog(
+                            application_entry,
+                            ProcessAction::Signal(signal),
+                        );
+                    }
+                },
+            );
+
             klass.install_action(
                 "applications.context-information",
                 None,
@@ -245,6 +267,23 @@ mod imp {
                 },
             );
 
+            klass.install_action(
+                "applications.send-signal",
+                Some(glib::VariantTy::STRING),
+                move |res_applications, _, parameter| {
+                    let Some(signal) = parameter
+                        .and_then(glib::Variant::str)
+                        .and_then(parse_signal)
+                    else {
+                        return;
+                    };
+                    if let Some(app) = res_applications.get_selected_app_entry() {
+                        res_applications
+                            .open_app_action_dialog(&app, ProcessAction::Signal(signal));
+                    }
+                },
+            );
+
             Self::bind_template(klass);
         }
 
@@ -536,6 +575,13 @@ impl ResApplications {
         }
     }
 
+    /// Returns `true` if an info dialog is currently open, which shows more details than the columns do.
+    pub fn has_open_info_dialog(&self) -> bool {
+        let imp = self.imp();
+
+        imp.open_info_dialog.borrow().is_some() && !imp.info_dialog_closed.get()
+    }
+
     pub fn open_info_dialog(&self, app: &ApplicationEntry) {
         let imp = self.imp();
 
@@ -1478,6 +1524,8 @@ fn get_action_name(action: ProcessAction, name: &str) -> String {
         ProcessAction::STOP => i18n_f("Halt {}?", &[name]),
         ProcessAction::KILL => i18n_f("Kill {}?", &[name]),
         ProcessAction::CONT => i18n_f("Continue {}?", &[name]),
+        // Translators: The first placeholder is the name of a signal, e.g. "SIGHUP"
+        ProcessAction::Signal(signal) => i18n_f("Send {} to {}?", &[&signal_name(signal), name]),
     }
 }
 
@@ -1487,6 +1535,7 @@ fn get_action_warning(action: ProcessAction) -> String {
             ProcessAction::STOP => i18n("Halting an app can come with serious risks such as losing data and security implications. Use with caution."),
             ProcessAction::KILL => i18n("Killing an app can come with serious risks such as losing data and security implications. Use with caution."),
             ProcessAction::CONT => String::new(),
+            ProcessAction::Signal(_) => i18n("Processes of the app that don't handle this signal might be terminated."),
         }
 }
 
@@ -1496,5 +1545,6 @@ fn get_action_description(action: ProcessAction) -> String {
         ProcessAction::STOP => i18n("Halt App"),
         ProcessAction::KILL => i18n("Kill App"),
         ProcessAction::CONT => i18n("Continue App"),
+        ProcessAction::Signal(_) => i18n("Send Signal"),
     }
 }
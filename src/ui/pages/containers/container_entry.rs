@@ -0,0 +1,130 @@
+use gtk::glib::{self};
+
+use crate::{
+    i18n::i18n,
+    ui::pages::get_containerization_kind,
+    utils::{app::AppsContext, container::Container},
+};
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use crate::gstring_getter_setter;
+
+    use super::*;
+
+    #[derive(Properties)]
+    #[properties(wrapper_type = super::ContainerEntry)]
+    pub struct ContainerEntry {
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::cgroup, set = Self::set_cgroup, type = glib::GString)]
+        cgroup: Cell<glib::GString>,
+
+        #[property(get = Self::kind, set = Self::set_kind, type = glib::GString)]
+        kind: Cell<glib::GString>,
+
+        #[property(get, set)]
+        cpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        memory_usage: Cell<u64>,
+
+        #[property(get, set)]
+        read_speed: Cell<f64>,
+
+        #[property(get, set)]
+        read_total: Cell<u64>,
+
+        #[property(get, set)]
+        write_speed: Cell<f64>,
+
+        #[property(get, set)]
+        write_total: Cell<u64>,
+
+        #[property(get, set)]
+        running_processes: Cell<u32>,
+    }
+
+    impl Default for ContainerEntry {
+        fn default() -> Self {
+            Self {
+                name: Cell::new(glib::GString::default()),
+                cgroup: Cell::new(glib::GString::default()),
+                kind: Cell::new(glib::GString::default()),
+                cpu_usage: Cell::new(0.0),
+                memory_usage: Cell::new(0),
+                read_speed: Cell::new(0.0),
+                read_total: Cell::new(0),
+                write_speed: Cell::new(0.0),
+                write_total: Cell::new(0),
+                running_processes: Cell::new(0),
+            }
+        }
+    }
+
+    impl ContainerEntry {
+        gstring_getter_setter!(name, cgroup, kind);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ContainerEntry {
+        const NAME: &'static str = "ContainerEntry";
+        type Type = super::ContainerEntry;
+    }
+
+    impl ObjectImpl for ContainerEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct ContainerEntry(ObjectSubclass<imp::ContainerEntry>);
+}
+
+impl ContainerEntry {
+    pub fn new(container: &Container, apps_context: &AppsContext) -> Self {
+        let this: Self = glib::Object::builder()
+            .property("name", container.display_name())
+            .property("cgroup", &container.cgroup)
+            .property(
+                "kind",
+                get_containerization_kind(&container.containerization)
+                    .map_or_else(|| i18n("Unknown"), str::to_string),
+            )
+            .build();
+        this.update(container, apps_context);
+        this
+    }
+
+    pub fn update(&self, container: &Container, apps_context: &AppsContext) {
+        self.set_cpu_usage(container.cpu_time_ratio(apps_context));
+        self.set_memory_usage(container.memory_usage(apps_context) as u64);
+        self.set_read_speed(container.read_speed(apps_context));
+        self.set_read_total(container.read_total(apps_context));
+        self.set_write_speed(container.write_speed(apps_context));
+        self.set_write_total(container.write_total(apps_context));
+        self.set_running_processes(container.running_processes() as u32);
+    }
+}
@@ -0,0 +1,1161 @@
+pub mod container_entry;
+
+use std::collections::HashSet;
+
+use adw::ResponseAppearance;
+use adw::{prelude::*, subclass::prelude::*};
+use async_channel::Sender;
+use gtk::glib::{self, clone, closure, MainContext, Object};
+use gtk::{
+    gio, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, ListItem, NumericSorter,
+    SortType, StringSorter, Widget,
+};
+use process_data::signal::signal_name;
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::window::{Action, MainWindow};
+use crate::utils::app::AppsContext;
+use crate::utils::process::ProcessAction;
+use crate::utils::settings::SETTINGS;
+use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::NUM_CPUS;
+
+use self::container_entry::ContainerEntry;
+
+pub const TAB_ID: &str = "containers";
+
+mod imp {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::OnceLock,
+    };
+
+    use crate::ui::{pages::CONTAINERS_PRIMARY_ORD, window::Action};
+
+    use super::*;
+
+    use gtk::{
+        gio::{Icon, ThemedIcon},
+        glib::{ParamSpec, Properties, Value},
+        ColumnViewColumn, CompositeTemplate,
+    };
+
+    #[derive(CompositeTemplate, Properties)]
+    #[template(resource = "/net/nokyan/Resources/ui/pages/containers.ui")]
+    #[properties(wrapper_type = super::ResContainers)]
+    pub struct ResContainers {
+        #[template_child]
+        pub toast_overlay: TemplateChild<adw::ToastOverlay>,
+        #[template_child]
+        pub popover_menu: TemplateChild<gtk::PopoverMenu>,
+        #[template_child]
+        pub search_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
+        pub search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub containers_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub containers_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub end_container_button: TemplateChild<adw::SplitButton>,
+
+        pub store: RefCell<gio::ListStore>,
+        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub filter_model: RefCell<gtk::FilterListModel>,
+        pub sort_model: RefCell<gtk::SortListModel>,
+        pub column_view: RefCell<gtk::ColumnView>,
+
+        pub sender: OnceLock<Sender<Action>>,
+
+        pub popped_over_container: RefCell<Option<ContainerEntry>>,
+
+        pub columns: RefCell<Vec<ColumnViewColumn>>,
+
+        #[property(get)]
+        uses_progress_bar: Cell<bool>,
+
+        #[property(get)]
+        icon: RefCell<Icon>,
+
+        #[property(get = Self::tab_name, type = glib::GString)]
+        tab_name: Cell<glib::GString>,
+
+        #[property(get = Self::tab_detail_string, type = glib::GString)]
+        tab_detail_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_usage_string, set = Self::set_tab_usage_string, type = glib::GString)]
+        tab_usage_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_id, type = glib::GString)]
+        tab_id: Cell<glib::GString>,
+
+        #[property(get)]
+        graph_locked_max_y: Cell<bool>,
+
+        #[property(get)]
+        primary_ord: Cell<u32>,
+
+        #[property(get)]
+        secondary_ord: Cell<u32>,
+    }
+
+    impl ResContainers {
+        gstring_getter_setter!(tab_name, tab_detail_string, tab_usage_string, tab_id);
+    }
+
+    impl Default for ResContainers {
+        fn default() -> Self {
+            Self {
+                toast_overlay: Default::default(),
+                popover_menu: Default::default(),
+                search_revealer: Default::default(),
+                search_entry: Default::default(),
+                search_button: Default::default(),
+                store: gio::ListStore::new::<ContainerEntry>().into(),
+                selection_model: Default::default(),
+                filter_model: Default::default(),
+                sort_model: Default::default(),
+                column_view: Default::default(),
+                sender: Default::default(),
+                containers_stack: Default::default(),
+                containers_scrolled_window: Default::default(),
+                end_container_button: Default::default(),
+                uses_progress_bar: Cell::new(false),
+                icon: RefCell::new(ThemedIcon::new("generic-process-symbolic").into()),
+                tab_name: Cell::from(glib::GString::from(i18n("Containers"))),
+                tab_detail_string: Cell::new(glib::GString::new()),
+                tab_usage_string: Cell::new(glib::GString::new()),
+                tab_id: Cell::new(glib::GString::from(TAB_ID)),
+                popped_over_container: Default::default(),
+                columns: Default::default(),
+                graph_locked_max_y: Cell::new(true),
+                primary_ord: Cell::new(CONTAINERS_PRIMARY_ORD),
+                secondary_ord: Default::default(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResContainers {
+        const NAME: &'static str = "ResContainers";
+        type Type = super::ResContainers;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.install_action(
+                "containers.context-end-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container_entry) =
+                        res_containers.imp().popped_over_container.borrow().as_ref()
+                    {
+                        res_containers
+                            .open_container_action_dialog(container_entry, ProcessAction::TERM);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "containers.context-kill-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container_entry) =
+                        res_containers.imp().popped_over_container.borrow().as_ref()
+                    {
+                        res_containers
+                            .open_container_action_dialog(container_entry, ProcessAction::KILL);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "containers.context-halt-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container_entry) =
+                        res_containers.imp().popped_over_container.borrow().as_ref()
+                    {
+                        res_containers
+                            .open_container_action_dialog(container_entry, ProcessAction::STOP);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "containers.context-continue-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container_entry) =
+                        res_containers.imp().popped_over_container.borrow().as_ref()
+                    {
+                        res_containers
+                            .open_container_action_dialog(container_entry, ProcessAction::CONT);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "containers.kill-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container) = res_containers.get_selected_container_entry() {
+                        res_containers
+                            .open_container_action_dialog(&container, ProcessAction::KILL);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "containers.halt-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container) = res_containers.get_selected_container_entry() {
+                        res_containers
+                            .open_container_action_dialog(&container, ProcessAction::STOP);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "containers.continue-container",
+                None,
+                move |res_containers, _, _| {
+                    if let Some(container) = res_containers.get_selected_container_entry() {
+                        res_containers
+                            .open_container_action_dialog(&container, ProcessAction::CONT);
+                    }
+                },
+            );
+
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResContainers {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+
+    impl WidgetImpl for ResContainers {}
+    impl BinImpl for ResContainers {}
+}
+
+glib::wrapper! {
+    pub struct ResContainers(ObjectSubclass<imp::ResContainers>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl Default for ResContainers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResContainers {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn toggle_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(!imp.search_button.is_active());
+    }
+
+    pub fn close_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(false);
+    }
+
+    pub fn init(&self, sender: Sender<Action>) {
+        let imp = self.imp();
+        imp.sender.set(sender).unwrap();
+
+        self.setup_widgets();
+        self.setup_signals();
+    }
+
+    fn add_gestures(&self, item: &ListItem) {
+        let widget = item.child().unwrap();
+
+        let secondary_click = gtk::GestureClick::new();
+        secondary_click.set_button(3);
+        secondary_click.connect_released(clone!(
+            #[weak]
+            widget,
+            #[weak]
+            item,
+            #[weak(rename_to = this)]
+            self,
+            move |_, _, x, y| {
+                if let Some(entry) = item.item().and_downcast::<ContainerEntry>() {
+                    let imp = this.imp();
+                    let popover_menu = &imp.popover_menu;
+
+                    *imp.popped_over_container.borrow_mut() = Some(entry);
+
+                    let position = widget
+                        .compute_point(&this, &gtk::graphene::Point::new(x as _, y as _))
+                        .unwrap();
+
+                    popover_menu.set_pointing_to(Some(&gtk::gdk::Rectangle::new(
+                        position.x().round() as i32,
+                        position.y().round() as i32,
+                        1,
+                        1,
+                    )));
+
+                    popover_menu.popup();
+                }
+            }
+        ));
+
+        widget.add_controller(secondary_click);
+    }
+
+    pub fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        imp.popover_menu.set_parent(self);
+
+        *imp.column_view.borrow_mut() = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.column_view.borrow();
+
+        let mut columns = imp.columns.borrow_mut();
+
+        columns.push(self.add_name_column(&column_view));
+        columns.push(self.add_kind_column(&column_view));
+        columns.push(self.add_processes_column(&column_view));
+        columns.push(self.add_memory_column(&column_view));
+        columns.push(self.add_cpu_column(&column_view));
+        columns.push(self.add_read_speed_column(&column_view));
+        columns.push(self.add_read_total_column(&column_view));
+        columns.push(self.add_write_speed_column(&column_view));
+        columns.push(self.add_write_total_column(&column_view));
+
+        let store = gio::ListStore::new::<ContainerEntry>();
+
+        let filter_model = gtk::FilterListModel::new(
+            Some(store.clone()),
+            Some(gtk::CustomFilter::new(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |obj| this.search_filter(obj)
+            ))),
+        );
+
+        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+
+        let selection_model = gtk::SingleSelection::new(Some(sort_model.clone()));
+        selection_model.set_can_unselect(true);
+        selection_model.set_autoselect(false);
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(
+            columns
+                .get(SETTINGS.containers_sort_by() as usize)
+                .or_else(|| columns.first()),
+            SETTINGS.containers_sort_by_ascending(),
+        );
+
+        column_view.add_css_class("resources-columnview");
+
+        *imp.store.borrow_mut() = store;
+        *imp.selection_model.borrow_mut() = selection_model;
+        *imp.sort_model.borrow_mut() = sort_model;
+        *imp.filter_model.borrow_mut() = filter_model;
+
+        imp.containers_scrolled_window
+            .set_child(Some(&*column_view));
+    }
+
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.selection_model
+            .borrow()
+            .connect_selection_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |model, _, _| {
+                    this.imp()
+                        .end_container_button
+                        .set_sensitive(model.selected() != u32::MAX);
+                }
+            ));
+
+        imp.search_button.connect_toggled(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |button| {
+                let imp = this.imp();
+                imp.search_revealer.set_reveal_child(button.is_active());
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+                if button.is_active() {
+                    imp.search_entry.grab_focus();
+                }
+            }
+        ));
+
+        imp.search_entry.connect_search_changed(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }
+        ));
+
+        let event_controller = EventControllerKey::new();
+        event_controller.connect_key_released(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_, key, _, _| {
+                if key.name().unwrap_or_default() == "Escape" {
+                    this.close_search();
+                }
+            }
+        ));
+        imp.search_entry.add_controller(event_controller);
+
+        imp.end_container_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                if let Some(container) = this.get_selected_container_entry() {
+                    this.open_container_action_dialog(&container, ProcessAction::TERM);
+                }
+            }
+        ));
+
+        if let Some(column_view_sorter) = imp.column_view.borrow().sorter() {
+            column_view_sorter.connect_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |sorter, _| {
+                    if let Some(sorter) = sorter.downcast_ref::<gtk::ColumnViewSorter>() {
+                        let current_column = sorter
+                            .primary_sort_column()
+                            .map(|column| column.as_ptr() as usize)
+                            .unwrap_or_default();
+
+                        let current_column_number = this
+                            .imp()
+                            .columns
+                            .borrow()
+                            .iter()
+                            .enumerate()
+                            .find(|(_, column)| column.as_ptr() as usize == current_column)
+                            .map_or(0, |(i, _)| i as u32); // 0 corresponds to the name column
+
+                        if SETTINGS.containers_sort_by() != current_column_number {
+                            let _ = SETTINGS.set_containers_sort_by(current_column_number);
+                        }
+
+                        if SETTINGS.containers_sort_by_ascending() != sorter.primary_sort_order() {
+                            let _ = SETTINGS
+                                .set_containers_sort_by_ascending(sorter.primary_sort_order());
+                        }
+                    }
+                }
+            ));
+        }
+    }
+
+    fn search_filter(&self, obj: &Object) -> bool {
+        let imp = self.imp();
+        let item = obj.downcast_ref::<ContainerEntry>().unwrap();
+        let search_string = imp.search_entry.text().to_string().to_lowercase();
+        !imp.search_revealer.reveals_child()
+            || item.name().to_lowercase().contains(&search_string)
+            || item.kind().to_lowercase().contains(&search_string)
+            || item.cgroup().to_lowercase().contains(&search_string)
+    }
+
+    pub fn get_selected_container_entry(&self) -> Option<ContainerEntry> {
+        self.imp()
+            .selection_model
+            .borrow()
+            .selected_item()
+            .and_then(|object| object.downcast::<ContainerEntry>().ok())
+    }
+
+    pub fn refresh_containers_list(&self, apps_context: &AppsContext) {
+        let imp = self.imp();
+
+        let store = imp.store.borrow_mut();
+
+        let mut cgroups_to_remove = HashSet::new();
+        let mut already_existing_cgroups = HashSet::new();
+
+        // update the entries of containers that are still running
+        store.iter::<ContainerEntry>().flatten().for_each(|object| {
+            let cgroup = object.cgroup().to_string();
+
+            if let Some(container) = apps_context.get_container(&cgroup) {
+                object.update(container, apps_context);
+                already_existing_cgroups.insert(cgroup);
+            } else {
+                if imp
+                    .popped_over_container
+                    .borrow()
+                    .as_ref()
+                    .is_some_and(|popped_over| *popped_over == object)
+                {
+                    *imp.popped_over_container.borrow_mut() = None;
+                }
+                cgroups_to_remove.insert(cgroup);
+            }
+        });
+
+        // remove containers that recently have stopped running
+        store.retain(|object| {
+            !cgroups_to_remove.contains(
+                object
+                    .downcast_ref::<ContainerEntry>()
+                    .unwrap()
+                    .cgroup()
+                    .as_str(),
+            )
+        });
+
+        // add the newly started containers to the store
+        let items: Vec<ContainerEntry> = apps_context
+            .containers_iter()
+            .filter(|container| !already_existing_cgroups.contains(&container.cgroup))
+            .map(|new_item| ContainerEntry::new(new_item, apps_context))
+            .collect();
+        store.extend_from_slice(&items);
+
+        if let Some(sorter) = imp.column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+
+        imp.containers_stack
+            .set_visible_child_name(if store.n_items() == 0 {
+                "empty"
+            } else {
+                "containers"
+            });
+
+        self.set_tab_usage_string(i18n_f(
+            "Running Containers: {}",
+            &[&store.n_items().to_string()],
+        ));
+    }
+
+    pub fn open_container_action_dialog(&self, container: &ContainerEntry, action: ProcessAction) {
+        // Nothing too bad can happen on Continue so dont show the dialog
+        if action == ProcessAction::CONT {
+            let main_context = MainContext::default();
+            main_context.spawn_local(clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                container,
+                async move {
+                    let imp = this.imp();
+                    let _ = imp
+                        .sender
+                        .get()
+                        .unwrap()
+                        .send(Action::ManipulateContainer(
+                            action,
+                            container.cgroup().to_string(),
+                            imp.toast_overlay.get(),
+                        ))
+                        .await;
+                }
+            ));
+            return;
+        }
+
+        // Confirmation dialog & warning
+        let dialog = adw::AlertDialog::builder()
+            .heading(get_action_name(action, &container.name()))
+            .body(get_action_warning(action))
+            .build();
+
+        dialog.add_response("yes", &get_action_description(action));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        // Called when "yes" or "no" were clicked
+        dialog.connect_response(
+            None,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                container,
+                move |_, response| {
+                    if response == "yes" {
+                        let main_context = MainContext::default();
+                        main_context.spawn_local(clone!(
+                            #[weak]
+                            this,
+                            #[strong]
+                            container,
+                            async move {
+                                let imp = this.imp();
+                                let _ = imp
+                                    .sender
+                                    .get()
+                                    .unwrap()
+                                    .send(Action::ManipulateContainer(
+                                        action,
+                                        container.cgroup().to_string(),
+                                        imp.toast_overlay.get(),
+                                    ))
+                                    .await;
+                            }
+                        ));
+                    }
+                }
+            ),
+        );
+
+        dialog.present(Some(&MainWindow::default()));
+    }
+
+    fn add_name_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let name_col_factory = gtk::SignalListItemFactory::new();
+
+        let name_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Container")), Some(name_col_factory.clone()));
+
+        name_col.set_resizable(true);
+        name_col.set_expand(true);
+
+        name_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("name")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        name_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let name_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "name",
+            ))
+            .build();
+
+        name_col.set_sorter(Some(&name_col_sorter));
+
+        column_view.append_column(&name_col);
+
+        name_col
+    }
+
+    fn add_kind_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let kind_col_factory = gtk::SignalListItemFactory::new();
+
+        let kind_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Type")), Some(kind_col_factory.clone()));
+
+        kind_col.set_resizable(true);
+
+        kind_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(10);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("kind")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        kind_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let kind_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "kind",
+            ))
+            .build();
+
+        kind_col.set_sorter(Some(&kind_col_sorter));
+
+        column_view.append_column(&kind_col);
+
+        kind_col
+    }
+
+    fn add_processes_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let processes_col_factory = gtk::SignalListItemFactory::new();
+
+        let processes_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Processes")),
+            Some(processes_col_factory.clone()),
+        );
+
+        processes_col.set_resizable(true);
+
+        processes_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(5);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("running_processes")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, running_processes: u32| {
+                            running_processes.to_string()
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        processes_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let processes_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "running_processes",
+            ))
+            .build();
+
+        processes_col.set_sorter(Some(&processes_col_sorter));
+
+        column_view.append_column(&processes_col);
+
+        processes_col
+    }
+
+    fn add_memory_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let memory_col_factory = gtk::SignalListItemFactory::new();
+
+        let memory_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Memory")), Some(memory_col_factory.clone()));
+
+        memory_col.set_resizable(true);
+
+        memory_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("memory_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
+                        convert_storage(memory_usage as f64, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        memory_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let memory_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "memory_usage",
+            ))
+            .build();
+
+        memory_col.set_sorter(Some(&memory_col_sorter));
+
+        column_view.append_column(&memory_col);
+
+        memory_col
+    }
+
+    fn add_cpu_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let cpu_col_factory = gtk::SignalListItemFactory::new();
+
+        let cpu_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Processor")), Some(cpu_col_factory.clone()));
+
+        cpu_col.set_resizable(true);
+
+        cpu_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("cpu_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
+                        let mut percentage = cpu_usage * 100.0;
+                        if !SETTINGS.normalize_cpu_usage() {
+                            percentage *= *NUM_CPUS as f32;
+                        }
+                        format!("{percentage:.1} %")
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        cpu_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let cpu_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "cpu_usage",
+            ))
+            .build();
+
+        cpu_col.set_sorter(Some(&cpu_col_sorter));
+
+        column_view.append_column(&cpu_col);
+
+        cpu_col
+    }
+
+    fn add_read_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Read")),
+            Some(read_speed_col_factory.clone()),
+        );
+
+        read_speed_col.set_resizable(true);
+
+        read_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("read_speed")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
+                        convert_speed(read_speed, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_speed",
+            ))
+            .build();
+
+        read_speed_col.set_sorter(Some(&read_speed_col_sorter));
+
+        column_view.append_column(&read_speed_col);
+
+        read_speed_col
+    }
+
+    fn add_read_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Read Total")),
+            Some(read_total_col_factory.clone()),
+        );
+
+        read_total_col.set_resizable(true);
+
+        read_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("read_total")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, read_total: u64| {
+                        convert_storage(read_total as f64, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_total",
+            ))
+            .build();
+
+        read_total_col.set_sorter(Some(&read_total_col_sorter));
+
+        column_view.append_column(&read_total_col);
+
+        read_total_col
+    }
+
+    fn add_write_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Write")),
+            Some(write_speed_col_factory.clone()),
+        );
+
+        write_speed_col.set_resizable(true);
+
+        write_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("write_speed")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
+                        convert_speed(write_speed, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_speed",
+            ))
+            .build();
+
+        write_speed_col.set_sorter(Some(&write_speed_col_sorter));
+
+        column_view.append_column(&write_speed_col);
+
+        write_speed_col
+    }
+
+    fn add_write_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Write Total")),
+            Some(write_total_col_factory.clone()),
+        );
+
+        write_total_col.set_resizable(true);
+
+        write_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("write_total")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, write_total: u64| {
+                        convert_storage(write_total as f64, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_total",
+            ))
+            .build();
+
+        write_total_col.set_sorter(Some(&write_total_col_sorter));
+
+        column_view.append_column(&write_total_col);
+
+        write_total_col
+    }
+}
+
+fn get_action_name(action: ProcessAction, name: &str) -> String {
+    match action {
+        ProcessAction::TERM => i18n_f("End {}?", &[name]),
+        ProcessAction::STOP => i18n_f("Halt {}?", &[name]),
+        ProcessAction::KILL => i18n_f("Kill {}?", &[name]),
+        ProcessAction::CONT => i18n_f("Continue {}?", &[name]),
+        // Translators: The first placeholder is the name of a signal, e.g. "SIGHUP"
+        ProcessAction::Signal(signal) => i18n_f("Send {} to {}?", &[&signal_name(signal), name]),
+    }
+}
+
+fn get_action_warning(action: ProcessAction) -> String {
+    match action {
+            ProcessAction::TERM => i18n("Unsaved work inside of the container might be lost."),
+            ProcessAction::STOP => i18n("Halting a container can come with serious risks such as losing data and security implications. Use with caution."),
+            ProcessAction::KILL => i18n("Killing a container can come with serious risks such as losing data and security implications. Use with caution."),
+            ProcessAction::CONT => String::new(),
+            ProcessAction::Signal(_) => i18n("Processes inside of the container that don't handle this signal might be terminated."),
+        }
+}
+
+fn get_action_description(action: ProcessAction) -> String {
+    match action {
+        ProcessAction::TERM => i18n("End Container"),
+        ProcessAction::STOP => i18n("Halt Container"),
+        ProcessAction::KILL => i18n("Kill Container"),
+        ProcessAction::CONT => i18n("Continue Container"),
+        ProcessAction::Signal(_) => i18n("Send Signal"),
+    }
+}
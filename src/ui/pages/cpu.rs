@@ -4,6 +4,7 @@ use gtk::FlowBoxChild;
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
+use crate::ui::pages::{refresh_pressure_graphs, setup_pressure_graphs};
 use crate::ui::widgets::graph_box::ResGraphBox;
 use crate::utils::cpu::{CpuData, CpuInfo};
 use crate::utils::settings::SETTINGS;
@@ -15,7 +16,10 @@ pub const TAB_ID: &str = "cpu";
 mod imp {
     use std::cell::{Cell, RefCell};
 
-    use crate::ui::{pages::CPU_PRIMARY_ORD, widgets::graph_box::ResGraphBox};
+    use crate::ui::{
+        pages::CPU_PRIMARY_ORD,
+        widgets::{double_graph_box::ResDoubleGraphBox, graph_box::ResGraphBox},
+    };
 
     use super::*;
 
@@ -55,6 +59,8 @@ mod imp {
         pub architecture: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub temperature: TemplateChild<ResGraphBox>,
+        #[template_child]
+        pub pressure: TemplateChild<ResDoubleGraphBox>,
         pub thread_graphs: RefCell<Vec<ResGraphBox>>,
         pub old_total_usage: Cell<(u64, u64)>,
         pub old_thread_usages: RefCell<Vec<(u64, u64)>>,
@@ -114,6 +120,7 @@ mod imp {
                 virtualization: Default::default(),
                 architecture: Default::default(),
                 temperature: Default::default(),
+                pressure: Default::default(),
                 thread_graphs: Default::default(),
                 uses_progress_bar: Cell::new(true),
                 main_graph_color: glib::Bytes::from_static(&super::ResCPU::MAIN_GRAPH_COLOR),
@@ -246,6 +253,13 @@ impl ResCPU {
         imp.temperature.graph().set_graph_color(0x1a, 0x5f, 0xb4);
         imp.temperature.graph().set_locked_max_y(None);
 
+        setup_pressure_graphs(
+            &imp.pressure,
+            Self::MAIN_GRAPH_COLOR[0],
+            Self::MAIN_GRAPH_COLOR[1],
+            Self::MAIN_GRAPH_COLOR[2],
+        );
+
         imp.max_speed.set_subtitle(
             &cpu_info
                 .max_speed
@@ -306,6 +320,7 @@ impl ResCPU {
             new_thread_usages,
             temperature,
             frequencies,
+            pressure,
         } = cpu_data;
 
         let imp = self.imp();
@@ -387,6 +402,8 @@ impl ResCPU {
             imp.temperature.set_subtitle(&i18n("N/A"));
         }
 
+        refresh_pressure_graphs(&imp.pressure, pressure.as_ref());
+
         self.set_property("usage", total_fraction);
 
         self.set_property("tab_usage_string", percentage_string);
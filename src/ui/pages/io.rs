@@ -0,0 +1,183 @@
+use adw::{prelude::*, subclass::prelude::*};
+use gtk::glib;
+use process_data::pressure::Pressure;
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::pages::{refresh_pressure_graphs, setup_pressure_graphs};
+
+pub const TAB_ID: &str = "io";
+
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use crate::ui::{pages::IO_PRIMARY_ORD, widgets::double_graph_box::ResDoubleGraphBox};
+
+    use super::*;
+
+    use gtk::{
+        gio::{Icon, ThemedIcon},
+        glib::{ParamSpec, Properties, Value},
+        CompositeTemplate,
+    };
+
+    #[derive(CompositeTemplate, Properties)]
+    #[template(resource = "/net/nokyan/Resources/ui/pages/io.ui")]
+    #[properties(wrapper_type = super::ResIo)]
+    pub struct ResIo {
+        #[template_child]
+        pub pressure: TemplateChild<ResDoubleGraphBox>,
+
+        #[property(get)]
+        uses_progress_bar: Cell<bool>,
+
+        #[property(get)]
+        main_graph_color: glib::Bytes,
+
+        #[property(get)]
+        icon: RefCell<Icon>,
+
+        #[property(get, set)]
+        usage: Cell<f64>,
+
+        #[property(get = Self::tab_name, type = glib::GString)]
+        tab_name: Cell<glib::GString>,
+
+        #[property(get = Self::tab_detail_string, set = Self::set_tab_detail_string, type = glib::GString)]
+        tab_detail_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_usage_string, set = Self::set_tab_usage_string, type = glib::GString)]
+        tab_usage_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_id, type = glib::GString)]
+        tab_id: Cell<glib::GString>,
+
+        #[property(get)]
+        graph_locked_max_y: Cell<bool>,
+
+        #[property(get)]
+        primary_ord: Cell<u32>,
+
+        #[property(get)]
+        secondary_ord: Cell<u32>,
+    }
+
+    impl ResIo {
+        gstring_getter_setter!(tab_name, tab_detail_string, tab_usage_string, tab_id);
+    }
+
+    impl Default for ResIo {
+        fn default() -> Self {
+            Self {
+                pressure: Default::default(),
+                uses_progress_bar: Cell::new(true),
+                main_graph_color: glib::Bytes::from_static(&super::ResIo::MAIN_GRAPH_COLOR),
+                icon: RefCell::new(ThemedIcon::new("drive-harddisk-symbolic").into()),
+                usage: Default::default(),
+                tab_name: Cell::new(glib::GString::from(i18n("I/O"))),
+                tab_detail_string: Cell::new(glib::GString::new()),
+                tab_usage_string: Cell::new(glib::GString::new()),
+                tab_id: Cell::new(glib::GString::from(TAB_ID)),
+                graph_locked_max_y: Cell::new(true),
+                primary_ord: Cell::new(IO_PRIMARY_ORD),
+                secondary_ord: Default::default(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResIo {
+        const NAME: &'static str = "ResIo";
+        type Type = super::ResIo;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResIo {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+
+    impl WidgetImpl for ResIo {}
+    impl BinImpl for ResIo {}
+}
+
+glib::wrapper! {
+    pub struct ResIo(ObjectSubclass<imp::ResIo>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl Default for ResIo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResIo {
+    const MAIN_GRAPH_COLOR: [u8; 3] = [0xe6, 0x61, 0x00];
+
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn init(&self) {
+        self.setup_widgets();
+    }
+
+    pub fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        setup_pressure_graphs(
+            &imp.pressure,
+            Self::MAIN_GRAPH_COLOR[0],
+            Self::MAIN_GRAPH_COLOR[1],
+            Self::MAIN_GRAPH_COLOR[2],
+        );
+    }
+
+    pub fn refresh_page(&self, pressure: Option<&Pressure>) {
+        let imp = self.imp();
+
+        refresh_pressure_graphs(&imp.pressure, pressure);
+
+        let some_avg10 = pressure
+            .map(|pressure| pressure.some.avg10)
+            .unwrap_or_default();
+
+        self.set_property(
+            "tab_usage_string",
+            // Translators: This will be displayed in the sidebar, it's the share of time in which processes had to
+            // wait for drives
+            i18n_f("Stalled: {} %", &[&format!("{some_avg10:.1}")]),
+        );
+
+        self.set_property("usage", f64::from(some_avg10) / 100.0);
+    }
+}
@@ -3,6 +3,7 @@ use gtk::glib::{self, clone};
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
+use crate::ui::pages::{refresh_pressure_graphs, setup_pressure_graphs};
 use crate::utils::memory::{self, MemoryData, MemoryDevice};
 use crate::utils::units::convert_storage;
 use crate::utils::FiniteOr;
@@ -12,7 +13,10 @@ pub const TAB_ID: &str = "memory";
 mod imp {
     use std::cell::{Cell, RefCell};
 
-    use crate::ui::{pages::MEMORY_PRIMARY_ORD, widgets::graph_box::ResGraphBox};
+    use crate::ui::{
+        pages::MEMORY_PRIMARY_ORD,
+        widgets::{double_graph_box::ResDoubleGraphBox, graph_box::ResGraphBox},
+    };
 
     use super::*;
 
@@ -31,6 +35,8 @@ mod imp {
         #[template_child]
         pub swap: TemplateChild<ResGraphBox>,
         #[template_child]
+        pub pressure: TemplateChild<ResDoubleGraphBox>,
+        #[template_child]
         pub authentication_banner: TemplateChild<adw::Banner>,
         #[template_child]
         pub properties: TemplateChild<adw::PreferencesGroup>,
@@ -90,6 +96,7 @@ mod imp {
             Self {
                 memory: Default::default(),
                 swap: Default::default(),
+                pressure: Default::default(),
                 authentication_banner: Default::default(),
                 properties: Default::default(),
                 slots_used: Default::default(),
@@ -192,6 +199,13 @@ impl ResMemory {
         imp.swap.set_title_label(&i18n("Swap"));
         imp.swap.graph().set_graph_color(0x94, 0x29, 0x7c);
 
+        setup_pressure_graphs(
+            &imp.pressure,
+            Self::MAIN_GRAPH_COLOR[0],
+            Self::MAIN_GRAPH_COLOR[1],
+            Self::MAIN_GRAPH_COLOR[2],
+        );
+
         if let Ok(memory_devices) = memory::get_memory_devices() {
             self.setup_properties(memory_devices);
         } else {
@@ -292,6 +306,7 @@ impl ResMemory {
             available_mem,
             total_swap,
             free_swap,
+            pressure,
         } = memdata;
 
         let used_mem = total_mem.saturating_sub(available_mem);
@@ -343,6 +358,8 @@ impl ResMemory {
             );
         }
 
+        refresh_pressure_graphs(&imp.pressure, pressure.as_ref());
+
         let memory_devices = imp.memory_devices.borrow();
 
         let total_memory = memory_devices
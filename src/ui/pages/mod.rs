@@ -1,28 +1,124 @@
 use std::{collections::HashMap, sync::LazyLock};
 
-use process_data::Niceness;
+use adw::prelude::*;
+use process_data::{
+    pressure::{Pressure, PressureStall},
+    Containerization, Niceness,
+};
 
-use crate::i18n::pi18n;
+use crate::i18n::{i18n, i18n_f, pi18n};
+use crate::ui::widgets::double_graph_box::ResDoubleGraphBox;
 
 pub mod applications;
 pub mod battery;
+pub mod containers;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
+pub mod io;
 pub mod memory;
 pub mod network;
 pub mod npu;
+pub mod process_log;
 pub mod processes;
+pub mod services;
+
+/// Returns a user-facing description of how a process or app is containerized, including the name or ID of the
+/// container if it is known.
+pub fn get_containerization_label(containerization: &Containerization) -> String {
+    let Some(kind) = get_containerization_kind(containerization) else {
+        return i18n("No");
+    };
+
+    match containerization.name() {
+        // Translators: The first placeholder is the type of container, e.g. "Docker", the second one its name or ID
+        Some(name) => i18n_f("Yes ({}: {})", &[kind, name]),
+        None => i18n_f("Yes ({})", &[kind]),
+    }
+}
+
+/// Returns the name of the technology used to containerize a process or app, `None` if it's not containerized.
+pub fn get_containerization_kind(containerization: &Containerization) -> Option<&'static str> {
+    match containerization {
+        Containerization::None => None,
+        Containerization::Flatpak(_) => Some("Flatpak"),
+        Containerization::Snap(_) => Some("Snap"),
+        Containerization::Docker(_) => Some("Docker"),
+        Containerization::Podman(_) => Some("Podman"),
+        Containerization::Lxc(_) => Some("LXC"),
+        Containerization::Nspawn(_) => Some("systemd-nspawn"),
+        Containerization::Toolbox(_) => Some("Toolbx"),
+        Containerization::Distrobox(_) => Some("Distrobox"),
+        Containerization::AppImage(_) => Some("AppImage"),
+    }
+}
+
+/// Sets up `graph_box` to show the pressure stall information of a resource, the start graph showing the share of
+/// time in which some tasks were stalled and the end graph the share of time in which all tasks were stalled.
+pub fn setup_pressure_graphs(graph_box: &ResDoubleGraphBox, r: u8, g: u8, b: u8) {
+    graph_box.set_start_title_label(&i18n("Some Tasks Stalled"));
+    graph_box.start_graph().set_graph_color(r, g, b);
+    graph_box.set_end_title_label(&i18n("All Tasks Stalled"));
+    graph_box.end_graph().set_graph_color(r, g, b);
+}
+
+/// Pushes the current pressure stall information of a resource to the graphs of `graph_box`, which has to be set up
+/// using `setup_pressure_graphs()`. The preferences group of the graph box is hidden if the kernel doesn't support
+/// PSI.
+pub fn refresh_pressure_graphs(graph_box: &ResDoubleGraphBox, pressure: Option<&Pressure>) {
+    if let Some(group) = graph_box.ancestor(adw::PreferencesGroup::static_type()) {
+        group.set_visible(pressure.is_some());
+    }
+
+    let Some(pressure) = pressure else {
+        return;
+    };
+
+    graph_box
+        .start_graph()
+        .push_data_point(f64::from(pressure.some.avg10) / 100.0);
+    graph_box.set_start_subtitle(&format!("{:.1} %", pressure.some.avg10));
+    graph_box.set_start_tooltip(Some(&get_pressure_averages_label(&pressure.some)));
+
+    if let Some(full) = &pressure.full {
+        graph_box
+            .end_graph()
+            .push_data_point(f64::from(full.avg10) / 100.0);
+        graph_box.set_end_subtitle(&format!("{:.1} %", full.avg10));
+        graph_box.set_end_tooltip(Some(&get_pressure_averages_label(full)));
+    } else {
+        graph_box.end_graph().push_data_point(0.0);
+        graph_box.set_end_subtitle(&i18n("N/A"));
+        graph_box.set_end_tooltip(None);
+    }
+}
+
+/// Returns a user-facing label listing the averages of a pressure stall over the last 10 seconds, 1 minute and
+/// 5 minutes.
+pub fn get_pressure_averages_label(stall: &PressureStall) -> String {
+    i18n_f(
+        "Last 10 seconds: {} %\nLast minute: {} %\nLast 5 minutes: {} %",
+        &[
+            &format!("{:.1}", stall.avg10),
+            &format!("{:.1}", stall.avg60),
+            &format!("{:.1}", stall.avg300),
+        ],
+    )
+}
 
 const APPLICATIONS_PRIMARY_ORD: u32 = 0;
 const PROCESSES_PRIMARY_ORD: u32 = 1;
-const CPU_PRIMARY_ORD: u32 = 2;
-const MEMORY_PRIMARY_ORD: u32 = 3;
-const GPU_PRIMARY_ORD: u32 = 4;
-const NPU_PRIMARY_ORD: u32 = 5;
-const DRIVE_PRIMARY_ORD: u32 = 6;
-const NETWORK_PRIMARY_ORD: u32 = 7;
-const BATTERY_PRIMARY_ORD: u32 = 8;
+const CONTAINERS_PRIMARY_ORD: u32 = 2;
+const SERVICES_PRIMARY_ORD: u32 = 3;
+const PROCESS_LOG_PRIMARY_ORD: u32 = 4;
+const CPU_PRIMARY_ORD: u32 = 5;
+const MEMORY_PRIMARY_ORD: u32 = 6;
+const GPU_PRIMARY_ORD: u32 = 7;
+const NPU_PRIMARY_ORD: u32 = 8;
+const DRIVE_PRIMARY_ORD: u32 = 9;
+const IO_PRIMARY_ORD: u32 = 10;
+const NETWORK_PRIMARY_ORD: u32 = 11;
+const BATTERY_PRIMARY_ORD: u32 = 12;
 
 pub static NICE_TO_LABEL: LazyLock<HashMap<Niceness, (String, u32)>> = LazyLock::new(|| {
     let mut hash_map = HashMap::new();
@@ -0,0 +1,179 @@
+use gtk::glib::{self, DateTime};
+use process_data::{proc_connector::ExitStatus, signal::signal_name};
+
+use crate::{
+    i18n::{i18n, i18n_f},
+    utils::process_log::{ProcessLogEntry, ProcessLogEventKind},
+};
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use crate::gstring_getter_setter;
+
+    use super::*;
+
+    #[derive(Properties)]
+    #[properties(wrapper_type = super::LogEntry)]
+    pub struct LogEntry {
+        #[property(get, set)]
+        id: Cell<u64>,
+
+        #[property(get, set)]
+        timestamp: Cell<u64>,
+
+        #[property(get = Self::time, set = Self::set_time, type = glib::GString)]
+        time: Cell<glib::GString>,
+
+        #[property(get = Self::event, set = Self::set_event, type = glib::GString)]
+        event: Cell<glib::GString>,
+
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::commandline, set = Self::set_commandline, type = glib::GString)]
+        commandline: Cell<glib::GString>,
+
+        #[property(get, set)]
+        pid: Cell<i32>,
+
+        #[property(get = Self::app, set = Self::set_app, type = glib::GString)]
+        app: Cell<glib::GString>,
+
+        #[property(get = Self::user, set = Self::set_user, type = glib::GString)]
+        user: Cell<glib::GString>,
+
+        #[property(get = Self::exit_status, set = Self::set_exit_status, type = glib::GString)]
+        exit_status: Cell<glib::GString>,
+
+        /// In milliseconds, -1 if unknown
+        #[property(get, set)]
+        lifetime: Cell<i64>,
+
+        /// In bytes, -1 if unknown
+        #[property(get, set)]
+        peak_memory: Cell<i64>,
+
+        /// In seconds, -1.0 if unknown
+        #[property(get, set)]
+        cpu_time: Cell<f64>,
+    }
+
+    impl Default for LogEntry {
+        fn default() -> Self {
+            Self {
+                id: Cell::new(0),
+                timestamp: Cell::new(0),
+                time: Cell::new(glib::GString::default()),
+                event: Cell::new(glib::GString::default()),
+                name: Cell::new(glib::GString::default()),
+                commandline: Cell::new(glib::GString::default()),
+                pid: Cell::new(0),
+                app: Cell::new(glib::GString::default()),
+                user: Cell::new(glib::GString::default()),
+                exit_status: Cell::new(glib::GString::default()),
+                lifetime: Cell::new(-1),
+                peak_memory: Cell::new(-1),
+                cpu_time: Cell::new(-1.0),
+            }
+        }
+    }
+
+    impl LogEntry {
+        gstring_getter_setter!(time, event, name, commandline, app, user, exit_status);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for LogEntry {
+        const NAME: &'static str = "LogEntry";
+        type Type = super::LogEntry;
+    }
+
+    impl ObjectImpl for LogEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct LogEntry(ObjectSubclass<imp::LogEntry>);
+}
+
+impl LogEntry {
+    pub fn new(entry: &ProcessLogEntry) -> Self {
+        let time = DateTime::from_unix_local((entry.timestamp / 1000) as i64)
+            .ok()
+            .and_then(|time| time.format("%x %X").ok())
+            .unwrap_or_default();
+
+        let this: Self = glib::Object::builder()
+            .property("id", entry.id)
+            .property("timestamp", entry.timestamp)
+            .property("time", time)
+            .property("name", &entry.name)
+            .property("commandline", &entry.commandline)
+            .property("pid", entry.pid)
+            .property("app", entry.app.clone().unwrap_or_default())
+            .property("user", entry.user.clone().unwrap_or_default())
+            .build();
+
+        this.set_kind(&entry.kind);
+
+        this
+    }
+
+    /// Sets the event and, for exits, the details of how the process has exited.
+    pub fn set_kind(&self, kind: &ProcessLogEventKind) {
+        match kind {
+            ProcessLogEventKind::Started => {
+                self.set_event(&i18n("Started"));
+            }
+            ProcessLogEventKind::Exited {
+                status,
+                lifetime,
+                peak_memory,
+                cpu_time,
+            } => {
+                self.set_event(&i18n("Exited"));
+                self.set_exit_status(
+                    &status
+                        .map_or_else(|| i18n("Unknown"), |status| get_exit_status_label(&status)),
+                );
+                self.set_lifetime(lifetime.map_or(-1, |lifetime| lifetime as i64));
+                self.set_peak_memory(peak_memory.map_or(-1, |peak_memory| peak_memory as i64));
+                self.set_cpu_time(cpu_time.unwrap_or(-1.0));
+            }
+        }
+    }
+}
+
+/// Returns a user-facing description of how a process has exited.
+fn get_exit_status_label(status: &ExitStatus) -> String {
+    match status {
+        ExitStatus::Exited(code) => i18n_f("Exit Code {}", &[&code.to_string()]),
+        ExitStatus::Signaled {
+            signal,
+            core_dumped: true,
+        } => i18n_f("{} (Core Dumped)", &[&signal_name(*signal)]),
+        ExitStatus::Signaled { signal, .. } => signal_name(*signal),
+    }
+}
@@ -0,0 +1,900 @@
+pub mod log_entry;
+
+use std::collections::HashSet;
+
+use adw::{prelude::*, subclass::prelude::*};
+use gtk::glib::{self, clone, closure, Object};
+use gtk::{
+    gio, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, NumericSorter, SortType,
+    StringSorter, Widget,
+};
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f};
+use crate::utils::app::AppsContext;
+use crate::utils::units::{convert_storage, format_time};
+
+use self::log_entry::LogEntry;
+
+pub const TAB_ID: &str = "process-log";
+
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use crate::ui::pages::PROCESS_LOG_PRIMARY_ORD;
+
+    use super::*;
+
+    use gtk::{
+        gio::{Icon, ThemedIcon},
+        glib::{ParamSpec, Properties, Value},
+        CompositeTemplate,
+    };
+
+    #[derive(CompositeTemplate, Properties)]
+    #[template(resource = "/net/nokyan/Resources/ui/pages/process_log.ui")]
+    #[properties(wrapper_type = super::ResProcessLog)]
+    pub struct ResProcessLog {
+        #[template_child]
+        pub search_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
+        pub search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub app_filter_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub app_filter_model: TemplateChild<gtk::StringList>,
+        #[template_child]
+        pub user_filter_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub user_filter_model: TemplateChild<gtk::StringList>,
+        #[template_child]
+        pub process_log_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub process_log_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub clear_button: TemplateChild<gtk::Button>,
+
+        pub store: RefCell<gio::ListStore>,
+        pub filter_model: RefCell<gtk::FilterListModel>,
+        pub sort_model: RefCell<gtk::SortListModel>,
+        pub column_view: RefCell<gtk::ColumnView>,
+
+        /// ID of the newest entry of the process log that has been added to `store`
+        pub last_id: Cell<Option<u64>>,
+
+        /// Apps and users that are already offered by the filter dropdowns
+        pub filter_apps: RefCell<HashSet<String>>,
+        pub filter_users: RefCell<HashSet<String>>,
+
+        #[property(get)]
+        uses_progress_bar: Cell<bool>,
+
+        #[property(get)]
+        icon: RefCell<Icon>,
+
+        #[property(get = Self::tab_name, type = glib::GString)]
+        tab_name: Cell<glib::GString>,
+
+        #[property(get = Self::tab_detail_string, type = glib::GString)]
+        tab_detail_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_usage_string, set = Self::set_tab_usage_string, type = glib::GString)]
+        tab_usage_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_id, type = glib::GString)]
+        tab_id: Cell<glib::GString>,
+
+        #[property(get)]
+        graph_locked_max_y: Cell<bool>,
+
+        #[property(get)]
+        primary_ord: Cell<u32>,
+
+        #[property(get)]
+        secondary_ord: Cell<u32>,
+    }
+
+    impl ResProcessLog {
+        gstring_getter_setter!(tab_name, tab_detail_string, tab_usage_string, tab_id);
+    }
+
+    impl Default for ResProcessLog {
+        fn default() -> Self {
+            Self {
+                search_revealer: Default::default(),
+                search_entry: Default::default(),
+                app_filter_dropdown: Default::default(),
+                app_filter_model: Default::default(),
+                user_filter_dropdown: Default::default(),
+                user_filter_model: Default::default(),
+                process_log_stack: Default::default(),
+                process_log_scrolled_window: Default::default(),
+                search_button: Default::default(),
+                clear_button: Default::default(),
+                store: gio::ListStore::new::<LogEntry>().into(),
+                filter_model: Default::default(),
+                sort_model: Default::default(),
+                column_view: Default::default(),
+                last_id: Default::default(),
+                filter_apps: Default::default(),
+                filter_users: Default::default(),
+                uses_progress_bar: Cell::new(false),
+                icon: RefCell::new(ThemedIcon::new("document-open-recent-symbolic").into()),
+                tab_name: Cell::from(glib::GString::from(i18n("Process Log"))),
+                tab_detail_string: Cell::new(glib::GString::new()),
+                tab_usage_string: Cell::new(glib::GString::new()),
+                tab_id: Cell::new(glib::GString::from(TAB_ID)),
+                graph_locked_max_y: Cell::new(true),
+                primary_ord: Cell::new(PROCESS_LOG_PRIMARY_ORD),
+                secondary_ord: Default::default(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResProcessLog {
+        const NAME: &'static str = "ResProcessLog";
+        type Type = super::ResProcessLog;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResProcessLog {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+
+    impl WidgetImpl for ResProcessLog {}
+    impl BinImpl for ResProcessLog {}
+}
+
+glib::wrapper! {
+    pub struct ResProcessLog(ObjectSubclass<imp::ResProcessLog>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl Default for ResProcessLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResProcessLog {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn toggle_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(!imp.search_button.is_active());
+    }
+
+    pub fn close_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(false);
+    }
+
+    pub fn init(&self) {
+        self.setup_widgets();
+        self.setup_signals();
+    }
+
+    pub fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        *imp.column_view.borrow_mut() = gtk::ColumnView::new(None::<gtk::NoSelection>);
+        let column_view = imp.column_view.borrow();
+
+        let time_col = self.add_time_column(&column_view);
+        self.add_event_column(&column_view);
+        self.add_name_column(&column_view);
+        self.add_pid_column(&column_view);
+        self.add_app_column(&column_view);
+        self.add_user_column(&column_view);
+        self.add_exit_status_column(&column_view);
+        self.add_lifetime_column(&column_view);
+        self.add_peak_memory_column(&column_view);
+        self.add_cpu_time_column(&column_view);
+
+        let store = gio::ListStore::new::<LogEntry>();
+
+        let filter_model = gtk::FilterListModel::new(
+            Some(store.clone()),
+            Some(gtk::CustomFilter::new(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |obj| this.search_filter(obj)
+            ))),
+        );
+
+        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+
+        let selection_model = gtk::NoSelection::new(Some(sort_model.clone()));
+
+        column_view.set_model(Some(&selection_model));
+
+        // newest entries first
+        column_view.sort_by_column(Some(&time_col), SortType::Descending);
+
+        column_view.add_css_class("resources-columnview");
+
+        *imp.store.borrow_mut() = store;
+        *imp.sort_model.borrow_mut() = sort_model;
+        *imp.filter_model.borrow_mut() = filter_model;
+
+        imp.process_log_scrolled_window
+            .set_child(Some(&*column_view));
+    }
+
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.search_button.connect_toggled(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |button| {
+                let imp = this.imp();
+                imp.search_revealer.set_reveal_child(button.is_active());
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+                if button.is_active() {
+                    imp.search_entry.grab_focus();
+                }
+            }
+        ));
+
+        imp.search_entry.connect_search_changed(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }
+        ));
+
+        imp.app_filter_dropdown.connect_selected_notify(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }
+        ));
+
+        imp.user_filter_dropdown.connect_selected_notify(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }
+        ));
+
+        let event_controller = EventControllerKey::new();
+        event_controller.connect_key_released(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_, key, _, _| {
+                if key.name().unwrap_or_default() == "Escape" {
+                    this.close_search();
+                }
+            }
+        ));
+        imp.search_entry.add_controller(event_controller);
+
+        imp.clear_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                // the entries stay in the process log, but since they're older than `last_id`, they won't come back
+                imp.store.borrow().remove_all();
+                this.refresh_status();
+            }
+        ));
+    }
+
+    /// Returns the string selected in `dropdown`, `None` if its first item (i.e. no filtering) is selected.
+    fn selected_filter(dropdown: &gtk::DropDown) -> Option<glib::GString> {
+        if dropdown.selected() == 0 {
+            return None;
+        }
+
+        dropdown
+            .selected_item()
+            .and_downcast::<gtk::StringObject>()
+            .map(|string_object| string_object.string())
+    }
+
+    fn search_filter(&self, obj: &Object) -> bool {
+        let imp = self.imp();
+        let item = obj.downcast_ref::<LogEntry>().unwrap();
+        let search_string = imp.search_entry.text().to_string().to_lowercase();
+
+        if !imp.search_revealer.reveals_child() {
+            return true;
+        }
+
+        let matches_app =
+            Self::selected_filter(&imp.app_filter_dropdown).map_or(true, |app| item.app() == app);
+
+        let matches_user = Self::selected_filter(&imp.user_filter_dropdown)
+            .map_or(true, |user| item.user() == user);
+
+        matches_app
+            && matches_user
+            && (item.name().to_lowercase().contains(&search_string)
+                || item.commandline().to_lowercase().contains(&search_string)
+                || item.pid().to_string().contains(&search_string))
+    }
+
+    /// Offers `value` in the filter dropdown backed by `model` unless it's already there.
+    fn add_filter_option(model: &gtk::StringList, known: &mut HashSet<String>, value: &str) {
+        if !value.is_empty() && known.insert(value.to_string()) {
+            model.append(value);
+        }
+    }
+
+    pub fn refresh_process_log(&self, apps_context: &AppsContext) {
+        let imp = self.imp();
+
+        let process_log = apps_context.process_log();
+
+        let store = imp.store.borrow();
+
+        // drop the entries that have been dropped from the process log as well
+        if let Some(oldest_id) = process_log.oldest_id() {
+            let outdated = store
+                .iter::<LogEntry>()
+                .flatten()
+                .take_while(|entry| entry.id() < oldest_id)
+                .count();
+
+            if outdated > 0 {
+                store.splice(0, outdated as u32, &[] as &[LogEntry]);
+            }
+        }
+
+        // exits that have been logged before their exit status was known
+        for id in process_log.amended_ids() {
+            let (Some(entry), Some(position)) = (
+                process_log.entry(*id),
+                store.find_with_equal_func(|object| {
+                    object
+                        .downcast_ref::<LogEntry>()
+                        .is_some_and(|log_entry| log_entry.id() == *id)
+                }),
+            ) else {
+                continue;
+            };
+
+            if let Some(log_entry) = store.item(position).and_downcast::<LogEntry>() {
+                log_entry.set_kind(&entry.kind);
+            }
+        }
+
+        let new_entries: Vec<LogEntry> = process_log
+            .entries_after(imp.last_id.get())
+            .map(LogEntry::new)
+            .collect();
+
+        if let Some(newest) = new_entries.last() {
+            imp.last_id.set(Some(newest.id()));
+        }
+
+        let mut filter_apps = imp.filter_apps.borrow_mut();
+        let mut filter_users = imp.filter_users.borrow_mut();
+        for entry in &new_entries {
+            Self::add_filter_option(&imp.app_filter_model, &mut filter_apps, &entry.app());
+            Self::add_filter_option(&imp.user_filter_model, &mut filter_users, &entry.user());
+        }
+
+        store.extend_from_slice(&new_entries);
+
+        std::mem::drop(store);
+
+        self.refresh_status();
+    }
+
+    fn refresh_status(&self) {
+        let imp = self.imp();
+
+        let n_items = imp.store.borrow().n_items();
+
+        imp.process_log_stack
+            .set_visible_child_name(if n_items == 0 { "empty" } else { "process_log" });
+
+        imp.clear_button.set_sensitive(n_items > 0);
+
+        self.set_tab_usage_string(i18n_f("Logged Events: {}", &[&n_items.to_string()]));
+    }
+
+    fn add_time_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let time_col_factory = gtk::SignalListItemFactory::new();
+
+        let time_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Time")), Some(time_col_factory.clone()));
+
+        time_col.set_resizable(true);
+
+        time_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(18);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("time")
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        time_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let time_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "timestamp",
+            ))
+            .build();
+
+        time_col.set_sorter(Some(&time_col_sorter));
+
+        column_view.append_column(&time_col);
+
+        time_col
+    }
+
+    fn add_event_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let event_col_factory = gtk::SignalListItemFactory::new();
+
+        let event_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Event")), Some(event_col_factory.clone()));
+
+        event_col.set_resizable(true);
+
+        event_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(8);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("event")
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        event_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let event_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "event",
+            ))
+            .build();
+
+        event_col.set_sorter(Some(&event_col_sorter));
+
+        column_view.append_column(&event_col);
+
+        event_col
+    }
+
+    fn add_name_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let name_col_factory = gtk::SignalListItemFactory::new();
+
+        let name_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Process")), Some(name_col_factory.clone()));
+
+        name_col.set_resizable(true);
+        name_col.set_expand(true);
+
+        name_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("name")
+                .bind(&row, "text", Widget::NONE);
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("commandline")
+                .bind(&row, "tooltip-text", Widget::NONE);
+        });
+
+        name_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let name_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "name",
+            ))
+            .build();
+
+        name_col.set_sorter(Some(&name_col_sorter));
+
+        column_view.append_column(&name_col);
+
+        name_col
+    }
+
+    fn add_pid_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let pid_col_factory = gtk::SignalListItemFactory::new();
+
+        let pid_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Process ID")), Some(pid_col_factory.clone()));
+
+        pid_col.set_resizable(true);
+
+        pid_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(7);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("pid")
+                .chain_closure::<String>(closure!(|_: Option<Object>, pid: i32| {
+                    pid.to_string()
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        pid_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let pid_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "pid",
+            ))
+            .build();
+
+        pid_col.set_sorter(Some(&pid_col_sorter));
+
+        column_view.append_column(&pid_col);
+
+        pid_col
+    }
+
+    fn add_app_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let app_col_factory = gtk::SignalListItemFactory::new();
+
+        let app_col = gtk::ColumnViewColumn::new(Some(&i18n("App")), Some(app_col_factory.clone()));
+
+        app_col.set_resizable(true);
+
+        app_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(10);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("app")
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        app_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let app_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "app",
+            ))
+            .build();
+
+        app_col.set_sorter(Some(&app_col_sorter));
+
+        column_view.append_column(&app_col);
+
+        app_col
+    }
+
+    fn add_user_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let user_col_factory = gtk::SignalListItemFactory::new();
+
+        let user_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("User")), Some(user_col_factory.clone()));
+
+        user_col.set_resizable(true);
+
+        user_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(8);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("user")
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        user_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let user_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "user",
+            ))
+            .build();
+
+        user_col.set_sorter(Some(&user_col_sorter));
+
+        column_view.append_column(&user_col);
+
+        user_col
+    }
+
+    fn add_exit_status_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let exit_status_col_factory = gtk::SignalListItemFactory::new();
+
+        let exit_status_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Exit Status")),
+            Some(exit_status_col_factory.clone()),
+        );
+
+        exit_status_col.set_resizable(true);
+
+        exit_status_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(11);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("exit_status")
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        exit_status_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let exit_status_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "exit_status",
+            ))
+            .build();
+
+        exit_status_col.set_sorter(Some(&exit_status_col_sorter));
+
+        column_view.append_column(&exit_status_col);
+
+        exit_status_col
+    }
+
+    fn add_lifetime_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let lifetime_col_factory = gtk::SignalListItemFactory::new();
+
+        let lifetime_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Lifetime")), Some(lifetime_col_factory.clone()));
+
+        lifetime_col.set_resizable(true);
+
+        lifetime_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(11);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("lifetime")
+                .chain_closure::<String>(closure!(|_: Option<Object>, lifetime: i64| {
+                    if lifetime < 0 {
+                        String::new()
+                    } else {
+                        format_time(lifetime as f64 / 1000.0)
+                    }
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        lifetime_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let lifetime_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "lifetime",
+            ))
+            .build();
+
+        lifetime_col.set_sorter(Some(&lifetime_col_sorter));
+
+        column_view.append_column(&lifetime_col);
+
+        lifetime_col
+    }
+
+    fn add_peak_memory_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let peak_memory_col_factory = gtk::SignalListItemFactory::new();
+
+        let peak_memory_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Peak Memory")),
+            Some(peak_memory_col_factory.clone()),
+        );
+
+        peak_memory_col.set_resizable(true);
+
+        peak_memory_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(9);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("peak_memory")
+                .chain_closure::<String>(closure!(|_: Option<Object>, peak_memory: i64| {
+                    if peak_memory < 0 {
+                        String::new()
+                    } else {
+                        convert_storage(peak_memory as f64, false)
+                    }
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        peak_memory_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let peak_memory_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "peak_memory",
+            ))
+            .build();
+
+        peak_memory_col.set_sorter(Some(&peak_memory_col_sorter));
+
+        column_view.append_column(&peak_memory_col);
+
+        peak_memory_col
+    }
+
+    fn add_cpu_time_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let cpu_time_col_factory = gtk::SignalListItemFactory::new();
+
+        let cpu_time_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Total CPU Time")),
+            Some(cpu_time_col_factory.clone()),
+        );
+
+        cpu_time_col.set_resizable(true);
+
+        cpu_time_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(11);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<LogEntry>("cpu_time")
+                .chain_closure::<String>(closure!(|_: Option<Object>, cpu_time: f64| {
+                    if cpu_time < 0.0 {
+                        String::new()
+                    } else {
+                        format_time(cpu_time)
+                    }
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+
+        cpu_time_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let cpu_time_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                LogEntry::static_type(),
+                None::<&gtk::Expression>,
+                "cpu_time",
+            ))
+            .build();
+
+        cpu_time_col.set_sorter(Some(&cpu_time_col_sorter));
+
+        column_view.append_column(&cpu_time_col);
+
+        cpu_time_col
+    }
+}
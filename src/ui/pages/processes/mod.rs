@@ -1,7 +1,10 @@
 pub mod process_entry;
 mod process_name_cell;
+pub mod thread_entry;
 
+use std::cell::RefCell;
 use std::collections::HashSet;
+use std::rc::Rc;
 use std::sync::LazyLock;
 
 use adw::ResponseAppearance;
@@ -12,7 +15,10 @@ use gtk::{
     gio, BitsetIter, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, ListItem,
     NumericSorter, SortType, StringSorter, Widget,
 };
-use process_data::Niceness;
+use process_data::{
+    signal::{parse_signal, signal_name},
+    Containerization, Niceness, ProcessState,
+};
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f, ni18n_f};
@@ -28,6 +34,7 @@ use crate::utils::NUM_CPUS;
 
 use self::process_entry::ProcessEntry;
 use self::process_name_cell::ResProcessNameCell;
+use self::thread_entry::ThreadEntry;
 
 pub const TAB_ID: &str = "processes";
 
@@ -48,6 +55,15 @@ static LONGEST_PRIORITY_LABEL: LazyLock<u32> = LazyLock::new(|| {
     }
 });
 
+fn format_cpu_usage(cpu_usage: f32) -> String {
+    let mut percentage = cpu_usage * 100.0;
+    if !SETTINGS.normalize_cpu_usage() {
+        percentage *= *NUM_CPUS as f32;
+    }
+
+    format!("{percentage:.1} %")
+}
+
 mod imp {
     use std::{
         cell::{Cell, RefCell},
@@ -85,6 +101,10 @@ mod imp {
         #[template_child]
         pub search_entry: TemplateChild<gtk::SearchEntry>,
         #[template_child]
+        pub state_filter_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub containerization_filter_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
         pub processes_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
         #[template_child]
         pub search_button: TemplateChild<gtk::ToggleButton>,
@@ -116,6 +136,9 @@ mod imp {
 
         pub columns: RefCell<Vec<ColumnViewColumn>>,
 
+        /// Columns that are also filled in for thread rows
+        pub thread_columns: RefCell<Vec<ColumnViewColumn>>,
+
         #[property(get)]
         uses_progress_bar: Cell<bool>,
 
@@ -156,6 +179,8 @@ mod imp {
                 popover_menu_multiple: Default::default(),
                 search_revealer: Default::default(),
                 search_entry: Default::default(),
+                state_filter_dropdown: Default::default(),
+                containerization_filter_dropdown: Default::default(),
                 processes_scrolled_window: Default::default(),
                 search_button: Default::default(),
                 options_button: Default::default(),
@@ -181,6 +206,7 @@ mod imp {
                 tab_id: Cell::new(glib::GString::from(TAB_ID)),
                 popped_over_process: Default::default(),
                 columns: Default::default(),
+                thread_columns: Default::default(),
                 graph_locked_max_y: Cell::new(true),
                 primary_ord: Cell::new(PROCESSES_PRIMARY_ORD),
                 secondary_ord: Default::default(),
@@ -255,6 +281,27 @@ mod imp {
                 },
             );
 
+            klass.install_action(
+                "processes.context-send-signal",
+                Some(glib::VariantTy::STRING),
+                move |res_processes, _, parameter| {
+                    let Some(signal) = parameter
+                        .and_then(glib::Variant::str)
+                        .and_then(parse_signal)
+                    else {
+                        return;
+                    };
+                    if let Some(process_entry) =
+                        res_processes.imp().popped_over_process.borrow().as_ref()
+                    {
+                        res_processes.open_process_action_dialog(
+                            vec![process_entry.clone()],
+                            ProcessAction::Signal(signal),
+                        );
+                    }
+                },
+            );
+
             klass.install_action(
                 "processes.context-information",
                 None,
@@ -319,6 +366,24 @@ mod imp {
                 },
             );
 
+            klass.install_action(
+                "processes.send-signal",
+                Some(glib::VariantTy::STRING),
+                move |res_processes, _, parameter| {
+                    let Some(signal) = parameter
+                        .and_then(glib::Variant::str)
+                        .and_then(parse_signal)
+                    else {
+                        return;
+                    };
+                    let selected = res_processes.get_selected_process_entries();
+                    if !selected.is_empty() {
+                        res_processes
+                            .open_process_action_dialog(selected, ProcessAction::Signal(signal));
+                    }
+                },
+            );
+
             Self::bind_template(klass);
         }
 
@@ -403,7 +468,7 @@ impl ResProcesses {
             #[weak(rename_to = this)]
             self,
             move |_, _, x, y| {
-                if let Some(entry) = item.item().and_downcast::<ProcessEntry>() {
+                if let Some(entry) = Self::row_process_entry(item.item()) {
                     let imp = this.imp();
 
                     let selected = this.get_selected_process_entries();
@@ -465,6 +530,31 @@ impl ResProcesses {
         columns.push(self.add_system_cpu_time_column(&column_view));
         columns.push(self.add_priority_column(&column_view));
         columns.push(self.add_swap_column(&column_view));
+        columns.push(self.add_state_column(&column_view));
+        columns.push(self.add_threads_column(&column_view));
+        columns.push(self.add_process_group_column(&column_view));
+        columns.push(self.add_session_column(&column_view));
+        columns.push(self.add_tty_column(&column_view));
+        columns.push(self.add_kernel_thread_column(&column_view));
+
+        // threads only have a name, an ID, a CPU usage and a state, so leave the other cells empty for them
+        let thread_columns = imp.thread_columns.borrow();
+        for column in columns
+            .iter()
+            .filter(|column| !thread_columns.contains(column))
+        {
+            if let Some(factory) = column
+                .factory()
+                .and_downcast::<gtk::SignalListItemFactory>()
+            {
+                factory.connect_bind(|_factory, item| {
+                    let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+                    if let Some(child) = item.child() {
+                        child.set_visible(Self::row_process_entry(item.item()).is_some());
+                    }
+                });
+            }
+        }
 
         let store = gio::ListStore::new::<ProcessEntry>();
 
@@ -477,7 +567,18 @@ impl ResProcesses {
             ))),
         );
 
-        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+        // processes with more than one thread can be expanded into their threads
+        let tree_model = gtk::TreeListModel::new(filter_model.clone(), false, false, |object| {
+            object
+                .downcast_ref::<ProcessEntry>()
+                .filter(|entry| entry.num_threads() > 1)
+                .map(|entry| entry.threads().upcast())
+        });
+
+        let sort_model = gtk::SortListModel::new(
+            Some(tree_model),
+            Some(gtk::TreeListRowSorter::new(column_view.sorter())),
+        );
 
         let selection_model = gtk::MultiSelection::new(Some(sort_model.clone()));
 
@@ -511,15 +612,16 @@ impl ResProcesses {
             .connect_selection_changed(clone!(
                 #[weak(rename_to = this)]
                 self,
-                move |model, _, _| {
+                move |_, _, _| {
                     let imp = this.imp();
-                    let bitset = model.selection();
+                    // selected thread rows don't count as they can't be acted upon
+                    let selected = this.get_selected_process_entries().len();
 
-                    imp.information_button.set_sensitive(bitset.size() == 1);
-                    imp.options_button.set_sensitive(bitset.size() == 1);
-                    imp.end_process_button.set_sensitive(bitset.size() > 0);
+                    imp.information_button.set_sensitive(selected == 1);
+                    imp.options_button.set_sensitive(selected == 1);
+                    imp.end_process_button.set_sensitive(selected > 0);
 
-                    if bitset.size() <= 1 {
+                    if selected <= 1 {
                         imp.end_process_button.set_label(&i18n("End Process"));
                         imp.end_process_button
                             .set_menu_model(Some(&imp.end_process_menu.get()));
@@ -557,6 +659,29 @@ impl ResProcesses {
             }
         ));
 
+        imp.state_filter_dropdown.connect_selected_notify(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }
+        ));
+
+        imp.containerization_filter_dropdown
+            .connect_selected_notify(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |_| {
+                    let imp = this.imp();
+                    if let Some(filter) = imp.filter_model.borrow().filter() {
+                        filter.changed(FilterChange::Different);
+                    }
+                }
+            ));
+
         let event_controller = EventControllerKey::new();
         event_controller.connect_key_released(clone!(
             #[weak(rename_to = this)]
@@ -573,13 +698,8 @@ impl ResProcesses {
             #[weak(rename_to = this)]
             self,
             move |_| {
-                let imp = this.imp();
-                let bitset = imp.selection_model.borrow().selection();
-                let selection_option = imp
-                    .selection_model
-                    .borrow()
-                    .item(bitset.maximum()) // the info button is only available when only 1 item is selected, so this should be fine
-                    .map(|object| object.downcast::<ProcessEntry>().unwrap());
+                // the options button is only available when only 1 process is selected, so this should be fine
+                let selection_option = this.get_selected_process_entries().pop();
                 if let Some(selection) = selection_option {
                     this.open_options_dialog(&selection);
                 }
@@ -590,13 +710,8 @@ impl ResProcesses {
             #[weak(rename_to = this)]
             self,
             move |_| {
-                let imp = this.imp();
-                let bitset = imp.selection_model.borrow().selection();
-                let selection_option = imp
-                    .selection_model
-                    .borrow()
-                    .item(bitset.maximum()) // the info button is only available when only 1 item is selected, so this should be fine
-                    .map(|object| object.downcast::<ProcessEntry>().unwrap());
+                // the info button is only available when only 1 process is selected, so this should be fine
+                let selection_option = this.get_selected_process_entries().pop();
                 if let Some(selection) = selection_option {
                     this.open_info_dialog(&selection);
                 }
@@ -678,6 +793,13 @@ impl ResProcesses {
         *imp.open_options_dialog.borrow_mut() = Some((process.pid(), dialog));
     }
 
+    /// Returns `true` if an info dialog is currently open, which shows more details than the columns do.
+    pub fn has_open_info_dialog(&self) -> bool {
+        let imp = self.imp();
+
+        imp.open_info_dialog.borrow().is_some() && !imp.info_dialog_closed.get()
+    }
+
     pub fn open_info_dialog(&self, process: &ProcessEntry) {
         let imp = self.imp();
 
@@ -707,10 +829,119 @@ impl ResProcesses {
     fn search_filter(&self, obj: &Object) -> bool {
         let imp = self.imp();
         let item = obj.downcast_ref::<ProcessEntry>().unwrap();
+        // the order has to match the items of state_filter_dropdown
+        let matches_state = match imp.state_filter_dropdown.selected() {
+            1 => item.process_state() == ProcessState::Running,
+            2 => item.process_state() == ProcessState::Sleeping,
+            3 => item.process_state() == ProcessState::DiskSleep,
+            4 => item.process_state() == ProcessState::Zombie,
+            5 => matches!(
+                item.process_state(),
+                ProcessState::Stopped | ProcessState::TracingStop
+            ),
+            6 => item.process_state() == ProcessState::Idle,
+            _ => true,
+        };
+
+        let containerization = item.process_containerization();
+
+        // the order has to match the items of containerization_filter_dropdown
+        let matches_containerization = match imp.containerization_filter_dropdown.selected() {
+            1 => containerization == Containerization::None,
+            2 => containerization != Containerization::None,
+            3 => matches!(containerization, Containerization::Flatpak(_)),
+            4 => matches!(containerization, Containerization::Snap(_)),
+            5 => matches!(containerization, Containerization::Docker(_)),
+            6 => matches!(containerization, Containerization::Podman(_)),
+            7 => matches!(containerization, Containerization::Lxc(_)),
+            8 => matches!(containerization, Containerization::Nspawn(_)),
+            9 => matches!(containerization, Containerization::Toolbox(_)),
+            10 => matches!(containerization, Containerization::Distrobox(_)),
+            11 => matches!(containerization, Containerization::AppImage(_)),
+            _ => true,
+        };
+
+        if !matches_state || !matches_containerization {
+            return false;
+        }
+
+        // only the text search is reset by hiding the search bar, the dropdown filters stay in effect
+        if !imp.search_revealer.reveals_child() {
+            return true;
+        }
+
         let search_string = imp.search_entry.text().to_string().to_lowercase();
-        !imp.search_revealer.reveals_child()
-            || item.name().to_lowercase().contains(&search_string)
+
+        item.name().to_lowercase().contains(&search_string)
             || item.commandline().to_lowercase().contains(&search_string)
+            || containerization
+                .name()
+                .is_some_and(|name| name.to_lowercase().contains(&search_string))
+    }
+
+    /// Returns the `ProcessEntry` behind a row of the processes list, or `None` if the row belongs to a thread
+    fn row_process_entry(object: Option<Object>) -> Option<ProcessEntry> {
+        object
+            .and_downcast::<gtk::TreeListRow>()
+            .and_then(|row| row.item())
+            .and_downcast::<ProcessEntry>()
+    }
+
+    /// Binds the properties of the process or thread a cell currently shows to the cell's widgets. Cells get reused
+    /// for processes and threads alike, so `bind` is called again whenever the cell's item changes and the bindings it
+    /// returned for the previous item are removed.
+    fn bind_row_item<F>(item: &ListItem, bind: F)
+    where
+        F: Fn(&Object) -> Vec<glib::Binding> + 'static,
+    {
+        let bindings: Rc<RefCell<Vec<glib::Binding>>> = Rc::default();
+        item.connect_item_notify(move |item| {
+            for binding in bindings.take() {
+                binding.unbind();
+            }
+
+            if let Some(row_item) = item
+                .item()
+                .and_downcast::<gtk::TreeListRow>()
+                .and_then(|row| row.item())
+            {
+                *bindings.borrow_mut() = bind(&row_item);
+            }
+        });
+    }
+
+    /// Threads are only collected while they are needed, so a freshly expanded process may not have any yet. Ask for
+    /// an early refresh in that case instead of showing an empty list until the next regular one.
+    fn request_threads(&self, row: &gtk::TreeListRow) {
+        let missing_threads = row
+            .item()
+            .and_downcast::<ProcessEntry>()
+            .is_some_and(|entry| entry.threads().n_items() == 0);
+
+        if row.is_expanded() && missing_threads {
+            let _ = self
+                .imp()
+                .sender
+                .get()
+                .unwrap()
+                .try_send(Action::RefreshEarly);
+        }
+    }
+
+    /// Whether any process in the list is currently expanded into its threads
+    pub fn has_expanded_processes(&self) -> bool {
+        let imp = self.imp();
+        imp.sort_model
+            .borrow()
+            .model()
+            .and_downcast::<gtk::TreeListModel>()
+            .is_some_and(|tree_model| {
+                (0..imp.filter_model.borrow().n_items()).any(|position| {
+                    tree_model
+                        .child_row(position)
+                        .is_some_and(|row| row.is_expanded())
+                })
+            })
     }
 
     pub fn get_selected_process_entries(&self) -> Vec<ProcessEntry> {
@@ -721,18 +952,12 @@ impl ResProcesses {
         {
             let mut return_vec: Vec<_> = bitset_iter
                 .filter_map(|position| {
-                    imp.selection_model
-                        .borrow()
-                        .item(position)
-                        .map(|object| object.downcast::<ProcessEntry>().unwrap())
+                    Self::row_process_entry(imp.selection_model.borrow().item(position))
                 })
                 .collect();
 
-            if let Some(first_process) = imp
-                .selection_model
-                .borrow()
-                .item(first)
-                .map(|object| object.downcast::<ProcessEntry>().unwrap())
+            if let Some(first_process) =
+                Self::row_process_entry(imp.selection_model.borrow().item(first))
             {
                 return_vec.insert(0, first_process);
             }
@@ -930,23 +1155,77 @@ impl ResProcesses {
 
                 let row = ResProcessNameCell::new();
 
-                item.set_child(Some(&row));
-
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("name")
-                    .bind(&row, "name", Widget::NONE);
-
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("icon")
-                    .bind(&row, "icon", Widget::NONE);
+                let expander = gtk::TreeExpander::new();
+                expander.set_child(Some(&row));
+
+                item.set_child(Some(&expander));
+
+                let expanded_handler: Rc<
+                    RefCell<Option<(gtk::TreeListRow, glib::SignalHandlerId)>>,
+                > = Rc::default();
+                item.connect_item_notify(clone!(
+                    #[weak]
+                    this,
+                    #[weak]
+                    expander,
+                    move |item| {
+                        if let Some((list_row, handler_id)) = expanded_handler.take() {
+                            list_row.disconnect(handler_id);
+                        }
 
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("commandline")
-                    .bind(&row, "tooltip", Widget::NONE);
+                        let list_row = item.item().and_downcast::<gtk::TreeListRow>();
+                        expander.set_list_row(list_row.as_ref());
 
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("symbolic")
-                    .bind(&row, "symbolic", Widget::NONE);
+                        if let Some(list_row) = list_row {
+                            let handler_id = list_row.connect_expanded_notify(clone!(
+                                #[weak]
+                                this,
+                                move |list_row| this.request_threads(list_row)
+                            ));
+                            *expanded_handler.borrow_mut() = Some((list_row, handler_id));
+                        }
+                    }
+                ));
+
+                Self::bind_row_item(item, move |row_item| {
+                    if let Some(entry) = row_item.downcast_ref::<ProcessEntry>() {
+                        vec![
+                            entry
+                                .bind_property("name", &row, "name")
+                                .sync_create()
+                                .build(),
+                            entry
+                                .bind_property("icon", &row, "icon")
+                                .sync_create()
+                                .build(),
+                            entry
+                                .bind_property("commandline", &row, "tooltip")
+                                .sync_create()
+                                .build(),
+                            entry
+                                .bind_property("symbolic", &row, "symbolic")
+                                .sync_create()
+                                .build(),
+                        ]
+                    } else if let Some(entry) = row_item.downcast_ref::<ThreadEntry>() {
+                        // threads don't have icons of their own
+                        row.set_icon(&gio::ThemedIcon::new("generic-process").into());
+                        row.set_symbolic(true);
+
+                        vec![
+                            entry
+                                .bind_property("name", &row, "name")
+                                .sync_create()
+                                .build(),
+                            entry
+                                .bind_property("name", &row, "tooltip")
+                                .sync_create()
+                                .build(),
+                        ]
+                    } else {
+                        Vec::new()
+                    }
+                });
 
                 this.add_gestures(item);
             }
@@ -954,7 +1233,7 @@ impl ResProcesses {
 
         name_col_factory.connect_teardown(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            item.set_child(None::<&ResProcessNameCell>);
+            item.set_child(None::<&gtk::TreeExpander>);
         });
 
         let name_col_sorter = StringSorter::builder()
@@ -970,6 +1249,11 @@ impl ResProcesses {
 
         column_view.append_column(&name_col);
 
+        self.imp()
+            .thread_columns
+            .borrow_mut()
+            .push(name_col.clone());
+
         name_col
     }
 
@@ -990,11 +1274,24 @@ impl ResProcesses {
                 let row = gtk::Inscription::new(None);
 
                 item.set_child(Some(&row));
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("pid")
-                    .bind(&row, "text", Widget::NONE);
 
                 this.add_gestures(item);
+
+                Self::bind_row_item(item, move |row_item| {
+                    let property = if row_item.is::<ProcessEntry>() {
+                        "pid"
+                    } else if row_item.is::<ThreadEntry>() {
+                        "tid"
+                    } else {
+                        return Vec::new();
+                    };
+
+                    vec![row_item
+                        .bind_property(property, &row, "text")
+                        .transform_to(|_, id: i32| Some(id.to_string()))
+                        .sync_create()
+                        .build()]
+                });
             }
         ));
 
@@ -1017,6 +1314,8 @@ impl ResProcesses {
 
         column_view.append_column(&pid_col);
 
+        self.imp().thread_columns.borrow_mut().push(pid_col.clone());
+
         SETTINGS.connect_processes_show_id(clone!(
             #[weak]
             pid_col,
@@ -1045,6 +1344,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("user")
                     .bind(&row, "text", Widget::NONE);
 
@@ -1100,6 +1400,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("memory_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
                         convert_storage(memory_usage as f64, false)
@@ -1157,19 +1458,20 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
 
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("cpu_usage")
-                    .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
-                        let mut percentage = cpu_usage * 100.0;
-                        if !SETTINGS.normalize_cpu_usage() {
-                            percentage *= *NUM_CPUS as f32;
-                        }
+                this.add_gestures(item);
 
-                        format!("{percentage:.1} %")
-                    }))
-                    .bind(&row, "text", Widget::NONE);
+                // processes and threads both have a `cpu_usage` property
+                Self::bind_row_item(item, move |row_item| {
+                    if !row_item.is::<ProcessEntry>() && !row_item.is::<ThreadEntry>() {
+                        return Vec::new();
+                    }
 
-                this.add_gestures(item);
+                    vec![row_item
+                        .bind_property("cpu_usage", &row, "text")
+                        .transform_to(|_, cpu_usage: f32| Some(format_cpu_usage(cpu_usage)))
+                        .sync_create()
+                        .build()]
+                });
             }
         ));
 
@@ -1192,6 +1494,8 @@ impl ResProcesses {
 
         column_view.append_column(&cpu_col);
 
+        self.imp().thread_columns.borrow_mut().push(cpu_col.clone());
+
         SETTINGS.connect_processes_show_cpu(clone!(
             #[weak]
             cpu_col,
@@ -1223,6 +1527,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("read_speed")
                     .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
                         if read_speed == -1.0 {
@@ -1289,6 +1594,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("read_total")
                     .chain_closure::<String>(closure!(|_: Option<Object>, read_total: i64| {
                         if read_total == -1 {
@@ -1355,6 +1661,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("write_speed")
                     .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
                         if write_speed == -1.0 {
@@ -1421,6 +1728,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("write_total")
                     .chain_closure::<String>(closure!(|_: Option<Object>, write_total: i64| {
                         if write_total == -1 {
@@ -1484,6 +1792,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("gpu_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, gpu_usage: f32| {
                         format!("{:.1} %", gpu_usage * 100.0)
@@ -1544,6 +1853,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("enc_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, enc_usage: f32| {
                         format!("{:.1} %", enc_usage * 100.0)
@@ -1604,6 +1914,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("dec_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, dec_usage: f32| {
                         format!("{:.1} %", dec_usage * 100.0)
@@ -1663,6 +1974,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("gpu_mem_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, gpu_mem: u64| {
                         convert_storage(gpu_mem as f64, false)
@@ -1722,6 +2034,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("total_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, total_cpu_time: f64| {
                         format_time(total_cpu_time)
@@ -1781,6 +2094,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("user_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, user_cpu_time: f64| {
                         format_time(user_cpu_time)
@@ -1840,6 +2154,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("system_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, system_cpu_time: f64| {
                         format_time(system_cpu_time)
@@ -1897,6 +2212,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("niceness")
                     .chain_closure::<String>(closure!(|_: Option<Object>, niceness: i8| {
                         if SETTINGS.detailed_priority() {
@@ -1965,6 +2281,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("swap_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, swap_usage: u64| {
                         convert_storage(swap_usage as f64, false)
@@ -2002,6 +2319,358 @@ impl ResProcesses {
 
         swap_col
     }
+
+    fn add_state_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let state_col_factory = gtk::SignalListItemFactory::new();
+
+        let state_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("State")), Some(state_col_factory.clone()));
+
+        state_col.set_resizable(true);
+
+        state_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                item.set_child(Some(&row));
+
+                this.add_gestures(item);
+
+                // processes and threads both have a `state` property
+                Self::bind_row_item(item, move |row_item| {
+                    if !row_item.is::<ProcessEntry>() && !row_item.is::<ThreadEntry>() {
+                        return Vec::new();
+                    }
+
+                    vec![row_item
+                        .bind_property("state", &row, "text")
+                        .sync_create()
+                        .build()]
+                });
+            }
+        ));
+
+        state_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let state_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "state",
+            ))
+            .build();
+
+        state_col.set_sorter(Some(&state_col_sorter));
+        state_col.set_visible(SETTINGS.processes_show_state());
+
+        column_view.append_column(&state_col);
+
+        self.imp()
+            .thread_columns
+            .borrow_mut()
+            .push(state_col.clone());
+
+        SETTINGS.connect_processes_show_state(clone!(
+            #[weak]
+            state_col,
+            move |visible| state_col.set_visible(visible)
+        ));
+
+        state_col
+    }
+
+    fn add_threads_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let threads_col_factory = gtk::SignalListItemFactory::new();
+
+        let threads_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Threads")), Some(threads_col_factory.clone()));
+
+        threads_col.set_resizable(true);
+
+        threads_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("num_threads")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        threads_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let threads_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "num_threads",
+            ))
+            .build();
+
+        threads_col.set_sorter(Some(&threads_col_sorter));
+        threads_col.set_visible(SETTINGS.processes_show_threads());
+
+        column_view.append_column(&threads_col);
+
+        SETTINGS.connect_processes_show_threads(clone!(
+            #[weak]
+            threads_col,
+            move |visible| threads_col.set_visible(visible)
+        ));
+
+        threads_col
+    }
+
+    fn add_process_group_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let process_group_col_factory = gtk::SignalListItemFactory::new();
+
+        let process_group_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Process Group")),
+            Some(process_group_col_factory.clone()),
+        );
+
+        process_group_col.set_resizable(true);
+
+        process_group_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("process_group")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        process_group_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let process_group_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "process_group",
+            ))
+            .build();
+
+        process_group_col.set_sorter(Some(&process_group_col_sorter));
+        process_group_col.set_visible(SETTINGS.processes_show_process_group());
+
+        column_view.append_column(&process_group_col);
+
+        SETTINGS.connect_processes_show_process_group(clone!(
+            #[weak]
+            process_group_col,
+            move |visible| process_group_col.set_visible(visible)
+        ));
+
+        process_group_col
+    }
+
+    fn add_session_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let session_col_factory = gtk::SignalListItemFactory::new();
+
+        let session_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Session")), Some(session_col_factory.clone()));
+
+        session_col.set_resizable(true);
+
+        session_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("session")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        session_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let session_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "session",
+            ))
+            .build();
+
+        session_col.set_sorter(Some(&session_col_sorter));
+        session_col.set_visible(SETTINGS.processes_show_session());
+
+        column_view.append_column(&session_col);
+
+        SETTINGS.connect_processes_show_session(clone!(
+            #[weak]
+            session_col,
+            move |visible| session_col.set_visible(visible)
+        ));
+
+        session_col
+    }
+
+    fn add_tty_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let tty_col_factory = gtk::SignalListItemFactory::new();
+
+        let tty_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Terminal")), Some(tty_col_factory.clone()));
+
+        tty_col.set_resizable(true);
+
+        tty_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("tty")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, tty: Option<glib::GString>| {
+                            tty.map_or_else(|| i18n("None"), String::from)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        tty_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let tty_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "tty",
+            ))
+            .build();
+
+        tty_col.set_sorter(Some(&tty_col_sorter));
+        tty_col.set_visible(SETTINGS.processes_show_tty());
+
+        column_view.append_column(&tty_col);
+
+        SETTINGS.connect_processes_show_tty(clone!(
+            #[weak]
+            tty_col,
+            move |visible| tty_col.set_visible(visible)
+        ));
+
+        tty_col
+    }
+
+    fn add_kernel_thread_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let kernel_thread_col_factory = gtk::SignalListItemFactory::new();
+
+        let kernel_thread_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Kernel Thread")),
+            Some(kernel_thread_col_factory.clone()),
+        );
+
+        kernel_thread_col.set_resizable(true);
+
+        kernel_thread_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("kernel_thread")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, kernel_thread: bool| {
+                        if kernel_thread {
+                            i18n("Yes")
+                        } else {
+                            i18n("No")
+                        }
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        kernel_thread_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let kernel_thread_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "kernel_thread",
+            ))
+            .build();
+
+        kernel_thread_col.set_sorter(Some(&kernel_thread_col_sorter));
+        kernel_thread_col.set_visible(SETTINGS.processes_show_kernel_thread());
+
+        column_view.append_column(&kernel_thread_col);
+
+        SETTINGS.connect_processes_show_kernel_thread(clone!(
+            #[weak]
+            kernel_thread_col,
+            move |visible| kernel_thread_col.set_visible(visible)
+        ));
+
+        kernel_thread_col
+    }
 }
 
 fn get_action_name(action: ProcessAction, name: &str) -> String {
@@ -2010,6 +2679,8 @@ fn get_action_name(action: ProcessAction, name: &str) -> String {
         ProcessAction::STOP => i18n_f("Halt {}?", &[name]),
         ProcessAction::KILL => i18n_f("Kill {}?", &[name]),
         ProcessAction::CONT => i18n_f("Continue {}?", &[name]),
+        // Translators: The first placeholder is the name of a signal, e.g. "SIGHUP"
+        ProcessAction::Signal(signal) => i18n_f("Send {} to {}?", &[&signal_name(signal), name]),
     }
 }
 
@@ -2039,6 +2710,13 @@ fn get_action_name_multiple(action: ProcessAction, count: usize) -> String {
             count as u32,
             &[&count.to_string()],
         ),
+        // Translators: The first placeholder is the name of a signal, e.g. "SIGHUP"
+        ProcessAction::Signal(signal) => ni18n_f(
+            "Send {} to the process?",
+            "Send {} to {} processes?",
+            count as u32,
+            &[&signal_name(signal), &count.to_string()],
+        ),
     }
 }
 
@@ -2048,6 +2726,7 @@ fn get_action_warning(action: ProcessAction) -> String {
             ProcessAction::STOP => i18n("Halting a process can come with serious risks such as losing data and security implications. Use with caution."),
             ProcessAction::KILL => i18n("Killing a process can come with serious risks such as losing data and security implications. Use with caution."),
             ProcessAction::CONT => String::new(),
+            ProcessAction::Signal(_) => i18n("Processes that don't handle this signal might be terminated."),
         }
 }
 
@@ -2057,5 +2736,22 @@ fn get_action_description(action: ProcessAction) -> String {
         ProcessAction::STOP => i18n("Halt Process"),
         ProcessAction::KILL => i18n("Kill Process"),
         ProcessAction::CONT => i18n("Continue Process"),
+        ProcessAction::Signal(_) => i18n("Send Signal"),
+    }
+}
+
+fn get_state_label(state: ProcessState) -> String {
+    match state {
+        ProcessState::Running => i18n("Running"),
+        ProcessState::Sleeping => i18n("Sleeping"),
+        ProcessState::DiskSleep => i18n("Waiting"),
+        ProcessState::Zombie => i18n("Zombie"),
+        ProcessState::Stopped => i18n("Stopped"),
+        ProcessState::TracingStop => i18n("Traced"),
+        ProcessState::Dead => i18n("Dead"),
+        ProcessState::Idle => i18n("Idle"),
+        ProcessState::Parked => i18n("Parked"),
+        ProcessState::Waking => i18n("Waking"),
+        ProcessState::Unknown => i18n("Unknown"),
     }
 }
@@ -1,14 +1,19 @@
 use gtk::{
+    gio,
     glib::{self, GString},
+    prelude::{CastNone, ListModelExt, ListModelExtManual},
     subclass::prelude::ObjectSubclassIsExt,
 };
-use process_data::Containerization;
-
-use crate::{
-    i18n::i18n,
-    utils::{process::Process, TICK_RATE},
+use process_data::{
+    users::Ids, Containerization, IoPriority, ProcessState, SchedulingPolicy, SmapsRollup,
 };
 
+use crate::utils::{process::Process, TICK_RATE};
+
+use crate::ui::pages::get_containerization_label;
+
+use super::{get_state_label, thread_entry::ThreadEntry};
+
 mod imp {
     use std::cell::{Cell, RefCell};
 
@@ -33,6 +38,15 @@ mod imp {
         #[property(get = Self::user, set = Self::set_user, type = glib::GString)]
         user: Cell<glib::GString>,
 
+        #[property(get = Self::effective_user, set = Self::set_effective_user, type = glib::GString)]
+        effective_user: Cell<glib::GString>,
+
+        #[property(get = Self::group, set = Self::set_group, type = glib::GString)]
+        group: Cell<glib::GString>,
+
+        #[property(get = Self::effective_group, set = Self::set_effective_group, type = glib::GString)]
+        effective_group: Cell<glib::GString>,
+
         #[property(get = Self::icon, set = Self::set_icon, type = Icon)]
         icon: Cell<Icon>,
 
@@ -84,6 +98,27 @@ mod imp {
         #[property(get, set)]
         niceness: Cell<i8>,
 
+        #[property(get, set)]
+        realtime_priority: Cell<u32>,
+
+        #[property(get = Self::state, set = Self::set_state, type = glib::GString)]
+        state: Cell<glib::GString>,
+
+        #[property(get, set)]
+        num_threads: Cell<u64>,
+
+        #[property(get, set)]
+        process_group: Cell<i32>,
+
+        #[property(get, set)]
+        session: Cell<i32>,
+
+        #[property(get = Self::tty, set = Self::set_tty)]
+        tty: Cell<Option<glib::GString>>,
+
+        #[property(get, set)]
+        kernel_thread: Cell<bool>,
+
         #[property(get = Self::cgroup, set = Self::set_cgroup)]
         cgroup: Cell<Option<glib::GString>>,
 
@@ -99,6 +134,22 @@ mod imp {
         symbolic: Cell<bool>,
 
         pub affinity: RefCell<Vec<bool>>,
+
+        pub process_state: Cell<ProcessState>,
+
+        pub uids: Cell<Ids>,
+
+        pub gids: Cell<Ids>,
+
+        pub scheduling_policy: Cell<SchedulingPolicy>,
+
+        pub io_priority: Cell<Option<IoPriority>>,
+
+        pub process_containerization: RefCell<Containerization>,
+
+        pub smaps_rollup: Cell<Option<SmapsRollup>>,
+
+        pub threads: gio::ListStore,
     }
 
     impl Default for ProcessEntry {
@@ -107,6 +158,9 @@ mod imp {
                 name: Cell::new(glib::GString::default()),
                 commandline: Cell::new(glib::GString::default()),
                 user: Cell::new(glib::GString::default()),
+                effective_user: Cell::new(glib::GString::default()),
+                group: Cell::new(glib::GString::default()),
+                effective_group: Cell::new(glib::GString::default()),
                 icon: Cell::new(ThemedIcon::new("generic-process").into()),
                 pid: Cell::new(0),
                 cpu_usage: Cell::new(0.0),
@@ -124,18 +178,42 @@ mod imp {
                 user_cpu_time: Cell::new(0.0),
                 system_cpu_time: Cell::new(0.0),
                 niceness: Cell::new(0),
+                realtime_priority: Cell::new(0),
+                state: Cell::new(glib::GString::default()),
+                num_threads: Cell::new(0),
+                process_group: Cell::new(0),
+                session: Cell::new(0),
+                tty: Cell::new(None),
+                kernel_thread: Cell::new(false),
                 cgroup: Cell::new(None),
                 containerization: Cell::new(glib::GString::default()),
                 running_since: Cell::new(None),
                 symbolic: Cell::new(false),
                 affinity: Default::default(),
+                process_state: Cell::new(ProcessState::default()),
+                uids: Cell::new(Ids::default()),
+                gids: Cell::new(Ids::default()),
+                scheduling_policy: Cell::new(SchedulingPolicy::default()),
+                io_priority: Cell::new(None),
+                process_containerization: RefCell::new(Containerization::default()),
+                smaps_rollup: Cell::new(None),
+                threads: gio::ListStore::new::<ThreadEntry>(),
             }
         }
     }
 
     impl ProcessEntry {
-        gstring_getter_setter!(user, commandline, name, containerization);
-        gstring_option_getter_setter!(cgroup, running_since);
+        gstring_getter_setter!(
+            user,
+            effective_user,
+            group,
+            effective_group,
+            commandline,
+            name,
+            containerization,
+            state
+        );
+        gstring_option_getter_setter!(cgroup, running_since, tty);
 
         pub fn icon(&self) -> Icon {
             let icon = self.icon.replace(ThemedIcon::new("generic-process").into());
@@ -200,29 +278,35 @@ glib::wrapper! {
 
 impl ProcessEntry {
     pub fn new(process: &Process) -> Self {
-        let containerization = match process.data.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
-            Containerization::Snap => i18n("Yes (Snap)"),
-        };
-
         let this: Self = glib::Object::builder()
             .property("name", &process.display_name)
             .property("commandline", process.data.commandline.replace('\0', " "))
-            .property("user", &process.data.user)
             .property("icon", &process.icon)
             .property("pid", process.data.pid)
             .property("cgroup", process.data.cgroup.clone().map(GString::from))
-            .property("containerization", containerization)
+            .property(
+                "containerization",
+                get_containerization_label(&process.data.containerization),
+            )
             .property("running_since", process.running_since().ok())
+            .property("tty", process.data.tty.clone().map(GString::from))
+            .property("kernel_thread", process.data.kernel_thread)
             .build();
+        this.imp()
+            .process_containerization
+            .replace(process.data.containerization.clone());
         this.update(process);
         this
     }
 
     pub fn update(&self, process: &Process) {
+        // user and group names are resolved again every now and then, so they might have changed
+        self.set_user(&process.data.user);
+        self.set_effective_user(&process.data.effective_user);
+        self.set_group(&process.data.group);
+        self.set_effective_group(&process.data.effective_group);
         self.set_cpu_usage(process.cpu_time_ratio());
-        self.set_memory_usage(process.data.memory_usage as u64);
+        self.set_memory_usage(process.memory_usage() as u64);
         self.set_swap_usage(process.data.swap_usage as u64);
         self.set_read_speed(process.read_speed().unwrap_or(-1.0));
         self.set_read_total(
@@ -246,10 +330,91 @@ impl ProcessEntry {
         self.set_system_cpu_time((process.data.system_cpu_time as f64) / (*TICK_RATE as f64));
         self.set_total_cpu_time(self.user_cpu_time() + self.system_cpu_time());
         self.set_niceness(*process.data.niceness);
+        self.set_realtime_priority(process.data.realtime_priority);
+        self.set_state(&get_state_label(process.data.state));
+        self.set_num_threads(process.data.num_threads);
+        self.set_process_group(process.data.process_group);
+        self.set_session(process.data.session);
+        self.set_tty(process.data.tty.as_deref());
+        self.imp().process_state.set(process.data.state);
+        self.imp().uids.set(process.data.uids);
+        self.imp().gids.set(process.data.gids);
+        self.imp()
+            .scheduling_policy
+            .set(process.data.scheduling_policy);
+        self.imp().io_priority.set(process.data.io_priority);
+        self.imp().smaps_rollup.set(process.data.smaps_rollup);
         *self.imp().affinity.borrow_mut() = process.data.affinity.clone();
+        self.update_threads(process);
+    }
+
+    fn update_threads(&self, process: &Process) {
+        let threads = &self.imp().threads;
+
+        let mut i = 0;
+        while i < threads.n_items() {
+            let thread_entry = threads.item(i).and_downcast::<ThreadEntry>().unwrap();
+            if let Some(thread) = process
+                .data
+                .threads
+                .iter()
+                .find(|thread| thread.tid == thread_entry.tid())
+            {
+                thread_entry.update(thread, process);
+                i += 1;
+            } else {
+                threads.remove(i);
+            }
+        }
+
+        let new_threads: Vec<_> = process
+            .data
+            .threads
+            .iter()
+            .filter(|thread| {
+                !threads
+                    .iter::<ThreadEntry>()
+                    .flatten()
+                    .any(|thread_entry| thread_entry.tid() == thread.tid)
+            })
+            .map(|thread| ThreadEntry::new(thread, process))
+            .collect();
+        threads.extend_from_slice(&new_threads);
     }
 
     pub fn affinity(&self) -> Vec<bool> {
         self.imp().affinity.borrow().clone()
     }
+
+    pub fn process_state(&self) -> ProcessState {
+        self.imp().process_state.get()
+    }
+
+    pub fn uids(&self) -> Ids {
+        self.imp().uids.get()
+    }
+
+    pub fn gids(&self) -> Ids {
+        self.imp().gids.get()
+    }
+
+    pub fn scheduling_policy(&self) -> SchedulingPolicy {
+        self.imp().scheduling_policy.get()
+    }
+
+    pub fn io_priority(&self) -> Option<IoPriority> {
+        self.imp().io_priority.get()
+    }
+
+    pub fn process_containerization(&self) -> Containerization {
+        self.imp().process_containerization.borrow().clone()
+    }
+
+    pub fn smaps_rollup(&self) -> Option<SmapsRollup> {
+        self.imp().smaps_rollup.get()
+    }
+
+    pub fn threads(&self) -> gio::ListStore {
+        self.imp().threads.clone()
+    }
 }
@@ -0,0 +1,88 @@
+use gtk::glib;
+use process_data::ThreadData;
+
+use crate::utils::process::Process;
+
+use super::get_state_label;
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties, Default)]
+    #[properties(wrapper_type = super::ThreadEntry)]
+    pub struct ThreadEntry {
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::state, set = Self::set_state, type = glib::GString)]
+        state: Cell<glib::GString>,
+
+        #[property(get, set)]
+        tid: Cell<i32>,
+
+        #[property(get, set)]
+        cpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        last_cpu: Cell<u32>,
+
+        #[property(get, set)]
+        niceness: Cell<i8>,
+    }
+
+    impl ThreadEntry {
+        gstring_getter_setter!(name, state);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ThreadEntry {
+        const NAME: &'static str = "ThreadEntry";
+        type Type = super::ThreadEntry;
+    }
+
+    impl ObjectImpl for ThreadEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct ThreadEntry(ObjectSubclass<imp::ThreadEntry>);
+}
+
+impl ThreadEntry {
+    pub fn new(thread: &ThreadData, process: &Process) -> Self {
+        let this: Self = glib::Object::builder().property("tid", thread.tid).build();
+        this.update(thread, process);
+        this
+    }
+
+    pub fn update(&self, thread: &ThreadData, process: &Process) {
+        self.set_name(&thread.comm);
+        self.set_state(&get_state_label(thread.state));
+        self.set_cpu_usage(process.thread_cpu_time_ratio(thread));
+        self.set_last_cpu(thread.last_cpu as u32);
+        self.set_niceness(*thread.niceness);
+    }
+}
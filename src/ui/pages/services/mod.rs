@@ -0,0 +1,1024 @@
+pub mod unit_entry;
+
+use std::collections::HashSet;
+
+use adw::{prelude::*, subclass::prelude::*};
+use async_channel::Sender;
+use gtk::glib::{self, clone, closure, Object};
+use gtk::{
+    gio, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, ListItem, NumericSorter,
+    SortType, StringSorter, Widget,
+};
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::dialogs::unit_dialog::ResUnitDialog;
+use crate::ui::window::{Action, MainWindow};
+use crate::utils::app::AppsContext;
+use crate::utils::settings::SETTINGS;
+use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::NUM_CPUS;
+
+use self::unit_entry::UnitEntry;
+
+pub const TAB_ID: &str = "services";
+
+mod imp {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::OnceLock,
+    };
+
+    use crate::ui::{pages::SERVICES_PRIMARY_ORD, window::Action};
+
+    use super::*;
+
+    use gtk::{
+        gio::{Icon, ThemedIcon},
+        glib::{ParamSpec, Properties, Value},
+        ColumnViewColumn, CompositeTemplate,
+    };
+
+    #[derive(CompositeTemplate, Properties)]
+    #[template(resource = "/net/nokyan/Resources/ui/pages/services.ui")]
+    #[properties(wrapper_type = super::ResServices)]
+    pub struct ResServices {
+        #[template_child]
+        pub toast_overlay: TemplateChild<adw::ToastOverlay>,
+        #[template_child]
+        pub popover_menu: TemplateChild<gtk::PopoverMenu>,
+        #[template_child]
+        pub search_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
+        pub search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub services_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub services_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub information_button: TemplateChild<gtk::Button>,
+
+        pub store: RefCell<gio::ListStore>,
+        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub filter_model: RefCell<gtk::FilterListModel>,
+        pub sort_model: RefCell<gtk::SortListModel>,
+        pub column_view: RefCell<gtk::ColumnView>,
+
+        pub sender: OnceLock<Sender<Action>>,
+
+        pub popped_over_unit: RefCell<Option<UnitEntry>>,
+
+        pub open_info_dialog: RefCell<Option<(String, ResUnitDialog)>>,
+        pub info_dialog_closed: Cell<bool>,
+
+        pub columns: RefCell<Vec<ColumnViewColumn>>,
+
+        #[property(get)]
+        uses_progress_bar: Cell<bool>,
+
+        #[property(get)]
+        icon: RefCell<Icon>,
+
+        #[property(get = Self::tab_name, type = glib::GString)]
+        tab_name: Cell<glib::GString>,
+
+        #[property(get = Self::tab_detail_string, type = glib::GString)]
+        tab_detail_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_usage_string, set = Self::set_tab_usage_string, type = glib::GString)]
+        tab_usage_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_id, type = glib::GString)]
+        tab_id: Cell<glib::GString>,
+
+        #[property(get)]
+        graph_locked_max_y: Cell<bool>,
+
+        #[property(get)]
+        primary_ord: Cell<u32>,
+
+        #[property(get)]
+        secondary_ord: Cell<u32>,
+    }
+
+    impl ResServices {
+        gstring_getter_setter!(tab_name, tab_detail_string, tab_usage_string, tab_id);
+    }
+
+    impl Default for ResServices {
+        fn default() -> Self {
+            Self {
+                toast_overlay: Default::default(),
+                popover_menu: Default::default(),
+                search_revealer: Default::default(),
+                search_entry: Default::default(),
+                search_button: Default::default(),
+                store: gio::ListStore::new::<UnitEntry>().into(),
+                selection_model: Default::default(),
+                filter_model: Default::default(),
+                sort_model: Default::default(),
+                column_view: Default::default(),
+                sender: Default::default(),
+                services_stack: Default::default(),
+                services_scrolled_window: Default::default(),
+                information_button: Default::default(),
+                uses_progress_bar: Cell::new(false),
+                icon: RefCell::new(ThemedIcon::new("generic-process-symbolic").into()),
+                tab_name: Cell::from(glib::GString::from(i18n("Services"))),
+                tab_detail_string: Cell::new(glib::GString::new()),
+                tab_usage_string: Cell::new(glib::GString::new()),
+                tab_id: Cell::new(glib::GString::from(TAB_ID)),
+                popped_over_unit: Default::default(),
+                open_info_dialog: Default::default(),
+                info_dialog_closed: Default::default(),
+                columns: Default::default(),
+                graph_locked_max_y: Cell::new(true),
+                primary_ord: Cell::new(SERVICES_PRIMARY_ORD),
+                secondary_ord: Default::default(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResServices {
+        const NAME: &'static str = "ResServices";
+        type Type = super::ResServices;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.install_action(
+                "services.context-information",
+                None,
+                move |res_services, _, _| {
+                    if let Some(unit_entry) = res_services.imp().popped_over_unit.borrow().as_ref()
+                    {
+                        res_services.open_info_dialog(unit_entry);
+                    }
+                },
+            );
+
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResServices {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+
+    impl WidgetImpl for ResServices {}
+    impl BinImpl for ResServices {}
+}
+
+glib::wrapper! {
+    pub struct ResServices(ObjectSubclass<imp::ResServices>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl Default for ResServices {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResServices {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn toggle_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(!imp.search_button.is_active());
+    }
+
+    pub fn close_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(false);
+    }
+
+    pub fn init(&self, sender: Sender<Action>) {
+        let imp = self.imp();
+        imp.sender.set(sender).unwrap();
+
+        self.setup_widgets();
+        self.setup_signals();
+    }
+
+    fn add_gestures(&self, item: &ListItem) {
+        let widget = item.child().unwrap();
+
+        let secondary_click = gtk::GestureClick::new();
+        secondary_click.set_button(3);
+        secondary_click.connect_released(clone!(
+            #[weak]
+            widget,
+            #[weak]
+            item,
+            #[weak(rename_to = this)]
+            self,
+            move |_, _, x, y| {
+                if let Some(entry) = item.item().and_downcast::<UnitEntry>() {
+                    let imp = this.imp();
+                    let popover_menu = &imp.popover_menu;
+
+                    *imp.popped_over_unit.borrow_mut() = Some(entry);
+
+                    let position = widget
+                        .compute_point(&this, &gtk::graphene::Point::new(x as _, y as _))
+                        .unwrap();
+
+                    popover_menu.set_pointing_to(Some(&gtk::gdk::Rectangle::new(
+                        position.x().round() as i32,
+                        position.y().round() as i32,
+                        1,
+                        1,
+                    )));
+
+                    popover_menu.popup();
+                }
+            }
+        ));
+
+        widget.add_controller(secondary_click);
+    }
+
+    pub fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        imp.popover_menu.set_parent(self);
+
+        *imp.column_view.borrow_mut() = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.column_view.borrow();
+
+        let mut columns = imp.columns.borrow_mut();
+
+        columns.push(self.add_name_column(&column_view));
+        columns.push(self.add_slice_column(&column_view));
+        columns.push(self.add_processes_column(&column_view));
+        columns.push(self.add_memory_column(&column_view));
+        columns.push(self.add_cpu_column(&column_view));
+        columns.push(self.add_read_speed_column(&column_view));
+        columns.push(self.add_read_total_column(&column_view));
+        columns.push(self.add_write_speed_column(&column_view));
+        columns.push(self.add_write_total_column(&column_view));
+
+        let store = gio::ListStore::new::<UnitEntry>();
+
+        let filter_model = gtk::FilterListModel::new(
+            Some(store.clone()),
+            Some(gtk::CustomFilter::new(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |obj| this.search_filter(obj)
+            ))),
+        );
+
+        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+
+        let selection_model = gtk::SingleSelection::new(Some(sort_model.clone()));
+        selection_model.set_can_unselect(true);
+        selection_model.set_autoselect(false);
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(
+            columns
+                .get(SETTINGS.services_sort_by() as usize)
+                .or_else(|| columns.first()),
+            SETTINGS.services_sort_by_ascending(),
+        );
+
+        column_view.add_css_class("resources-columnview");
+
+        *imp.store.borrow_mut() = store;
+        *imp.selection_model.borrow_mut() = selection_model;
+        *imp.sort_model.borrow_mut() = sort_model;
+        *imp.filter_model.borrow_mut() = filter_model;
+
+        imp.services_scrolled_window.set_child(Some(&*column_view));
+    }
+
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.selection_model
+            .borrow()
+            .connect_selection_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |model, _, _| {
+                    this.imp()
+                        .information_button
+                        .set_sensitive(model.selected() != u32::MAX);
+                }
+            ));
+
+        imp.search_button.connect_toggled(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |button| {
+                let imp = this.imp();
+                imp.search_revealer.set_reveal_child(button.is_active());
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+                if button.is_active() {
+                    imp.search_entry.grab_focus();
+                }
+            }
+        ));
+
+        imp.search_entry.connect_search_changed(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }
+        ));
+
+        let event_controller = EventControllerKey::new();
+        event_controller.connect_key_released(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_, key, _, _| {
+                if key.name().unwrap_or_default() == "Escape" {
+                    this.close_search();
+                }
+            }
+        ));
+        imp.search_entry.add_controller(event_controller);
+
+        imp.information_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                if let Some(unit) = this.get_selected_unit_entry() {
+                    this.open_info_dialog(&unit);
+                }
+            }
+        ));
+
+        if let Some(column_view_sorter) = imp.column_view.borrow().sorter() {
+            column_view_sorter.connect_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |sorter, _| {
+                    if let Some(sorter) = sorter.downcast_ref::<gtk::ColumnViewSorter>() {
+                        let current_column = sorter
+                            .primary_sort_column()
+                            .map(|column| column.as_ptr() as usize)
+                            .unwrap_or_default();
+
+                        let current_column_number = this
+                            .imp()
+                            .columns
+                            .borrow()
+                            .iter()
+                            .enumerate()
+                            .find(|(_, column)| column.as_ptr() as usize == current_column)
+                            .map_or(0, |(i, _)| i as u32); // 0 corresponds to the name column
+
+                        if SETTINGS.services_sort_by() != current_column_number {
+                            let _ = SETTINGS.set_services_sort_by(current_column_number);
+                        }
+
+                        if SETTINGS.services_sort_by_ascending() != sorter.primary_sort_order() {
+                            let _ = SETTINGS
+                                .set_services_sort_by_ascending(sorter.primary_sort_order());
+                        }
+                    }
+                }
+            ));
+        }
+    }
+
+    fn search_filter(&self, obj: &Object) -> bool {
+        let imp = self.imp();
+        let item = obj.downcast_ref::<UnitEntry>().unwrap();
+        let search_string = imp.search_entry.text().to_string().to_lowercase();
+        !imp.search_revealer.reveals_child()
+            || item.name().to_lowercase().contains(&search_string)
+            || item.slice().to_lowercase().contains(&search_string)
+            || item.cgroup().to_lowercase().contains(&search_string)
+    }
+
+    pub fn get_selected_unit_entry(&self) -> Option<UnitEntry> {
+        self.imp()
+            .selection_model
+            .borrow()
+            .selected_item()
+            .and_then(|object| object.downcast::<UnitEntry>().ok())
+    }
+
+    pub fn refresh_services_list(&self, apps_context: &AppsContext) {
+        let imp = self.imp();
+
+        if imp.info_dialog_closed.get() {
+            let _ = imp.open_info_dialog.take();
+            imp.info_dialog_closed.set(false);
+        }
+
+        let store = imp.store.borrow_mut();
+        let mut dialog_opt = &*imp.open_info_dialog.borrow_mut();
+
+        let mut cgroups_to_remove = HashSet::new();
+        let mut already_existing_cgroups = HashSet::new();
+
+        // update the entries of units that are still running
+        store.iter::<UnitEntry>().flatten().for_each(|object| {
+            let cgroup = object.cgroup().to_string();
+
+            if let Some(unit) = apps_context.get_unit(&cgroup) {
+                object.update(unit, apps_context);
+                if let Some((dialog_cgroup, dialog)) = dialog_opt {
+                    if *dialog_cgroup == cgroup {
+                        dialog.update(&object);
+                    }
+                }
+                already_existing_cgroups.insert(cgroup);
+            } else {
+                if let Some((dialog_cgroup, dialog)) = dialog_opt {
+                    if *dialog_cgroup == cgroup {
+                        dialog.close();
+                        dialog_opt = &None;
+                    }
+                }
+                if imp
+                    .popped_over_unit
+                    .borrow()
+                    .as_ref()
+                    .is_some_and(|popped_over| *popped_over == object)
+                {
+                    *imp.popped_over_unit.borrow_mut() = None;
+                }
+                cgroups_to_remove.insert(cgroup);
+            }
+        });
+
+        // remove units that recently have stopped running
+        store.retain(|object| {
+            !cgroups_to_remove.contains(
+                object
+                    .downcast_ref::<UnitEntry>()
+                    .unwrap()
+                    .cgroup()
+                    .as_str(),
+            )
+        });
+
+        // add the newly started units to the store
+        let items: Vec<UnitEntry> = apps_context
+            .units_iter()
+            .filter(|unit| !already_existing_cgroups.contains(&unit.unit.cgroup))
+            .map(|new_item| UnitEntry::new(new_item, apps_context))
+            .collect();
+        store.extend_from_slice(&items);
+
+        if let Some(sorter) = imp.column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+
+        imp.services_stack
+            .set_visible_child_name(if store.n_items() == 0 {
+                "empty"
+            } else {
+                "services"
+            });
+
+        self.set_tab_usage_string(i18n_f(
+            "Running Services: {}",
+            &[&store.n_items().to_string()],
+        ));
+    }
+
+    pub fn open_info_dialog(&self, unit: &UnitEntry) {
+        let imp = self.imp();
+
+        if imp.open_info_dialog.borrow().is_some() {
+            return;
+        }
+
+        imp.info_dialog_closed.set(false);
+
+        let dialog = ResUnitDialog::new();
+
+        dialog.init(unit);
+
+        dialog.present(Some(&MainWindow::default()));
+
+        dialog.connect_closed(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                this.imp().info_dialog_closed.set(true);
+            }
+        ));
+
+        *imp.open_info_dialog.borrow_mut() = Some((unit.cgroup().to_string(), dialog));
+    }
+
+    fn add_name_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let name_col_factory = gtk::SignalListItemFactory::new();
+
+        let name_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Unit")), Some(name_col_factory.clone()));
+
+        name_col.set_resizable(true);
+        name_col.set_expand(true);
+
+        name_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("name")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        name_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let name_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "name",
+            ))
+            .build();
+
+        name_col.set_sorter(Some(&name_col_sorter));
+
+        column_view.append_column(&name_col);
+
+        name_col
+    }
+
+    fn add_slice_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let slice_col_factory = gtk::SignalListItemFactory::new();
+
+        let slice_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Slice")), Some(slice_col_factory.clone()));
+
+        slice_col.set_resizable(true);
+
+        slice_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(12);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("slice")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        slice_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let slice_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "slice",
+            ))
+            .build();
+
+        slice_col.set_sorter(Some(&slice_col_sorter));
+
+        column_view.append_column(&slice_col);
+
+        slice_col
+    }
+
+    fn add_processes_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let processes_col_factory = gtk::SignalListItemFactory::new();
+
+        let processes_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Processes")),
+            Some(processes_col_factory.clone()),
+        );
+
+        processes_col.set_resizable(true);
+
+        processes_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(5);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("running_processes")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, running_processes: u32| {
+                            running_processes.to_string()
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        processes_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let processes_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "running_processes",
+            ))
+            .build();
+
+        processes_col.set_sorter(Some(&processes_col_sorter));
+
+        column_view.append_column(&processes_col);
+
+        processes_col
+    }
+
+    fn add_memory_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let memory_col_factory = gtk::SignalListItemFactory::new();
+
+        let memory_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Memory")), Some(memory_col_factory.clone()));
+
+        memory_col.set_resizable(true);
+
+        memory_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("memory_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
+                        convert_storage(memory_usage as f64, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        memory_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let memory_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "memory_usage",
+            ))
+            .build();
+
+        memory_col.set_sorter(Some(&memory_col_sorter));
+
+        column_view.append_column(&memory_col);
+
+        memory_col
+    }
+
+    fn add_cpu_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let cpu_col_factory = gtk::SignalListItemFactory::new();
+
+        let cpu_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Processor")), Some(cpu_col_factory.clone()));
+
+        cpu_col.set_resizable(true);
+
+        cpu_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("cpu_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
+                        let mut percentage = cpu_usage * 100.0;
+                        if !SETTINGS.normalize_cpu_usage() {
+                            percentage *= *NUM_CPUS as f32;
+                        }
+                        format!("{percentage:.1} %")
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        cpu_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let cpu_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "cpu_usage",
+            ))
+            .build();
+
+        cpu_col.set_sorter(Some(&cpu_col_sorter));
+
+        column_view.append_column(&cpu_col);
+
+        cpu_col
+    }
+
+    fn add_read_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Read")),
+            Some(read_speed_col_factory.clone()),
+        );
+
+        read_speed_col.set_resizable(true);
+
+        read_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("read_speed")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
+                        convert_speed(read_speed, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_speed",
+            ))
+            .build();
+
+        read_speed_col.set_sorter(Some(&read_speed_col_sorter));
+
+        column_view.append_column(&read_speed_col);
+
+        read_speed_col
+    }
+
+    fn add_read_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Read Total")),
+            Some(read_total_col_factory.clone()),
+        );
+
+        read_total_col.set_resizable(true);
+
+        read_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("read_total")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, read_total: u64| {
+                        convert_storage(read_total as f64, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_total",
+            ))
+            .build();
+
+        read_total_col.set_sorter(Some(&read_total_col_sorter));
+
+        column_view.append_column(&read_total_col);
+
+        read_total_col
+    }
+
+    fn add_write_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Write")),
+            Some(write_speed_col_factory.clone()),
+        );
+
+        write_speed_col.set_resizable(true);
+
+        write_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("write_speed")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
+                        convert_speed(write_speed, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_speed",
+            ))
+            .build();
+
+        write_speed_col.set_sorter(Some(&write_speed_col_sorter));
+
+        column_view.append_column(&write_speed_col);
+
+        write_speed_col
+    }
+
+    fn add_write_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Write Total")),
+            Some(write_total_col_factory.clone()),
+        );
+
+        write_total_col.set_resizable(true);
+
+        write_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<UnitEntry>("write_total")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, write_total: u64| {
+                        convert_storage(write_total as f64, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                UnitEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_total",
+            ))
+            .build();
+
+        write_total_col.set_sorter(Some(&write_total_col_sorter));
+
+        column_view.append_column(&write_total_col);
+
+        write_total_col
+    }
+}
@@ -0,0 +1,167 @@
+use gtk::{
+    glib::{self},
+    subclass::prelude::ObjectSubclassIsExt,
+};
+
+use crate::utils::{
+    app::AppsContext,
+    unit::{ProcessTreeNode, Unit},
+};
+
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use crate::gstring_getter_setter;
+
+    use super::*;
+
+    #[derive(Properties)]
+    #[properties(wrapper_type = super::UnitEntry)]
+    pub struct UnitEntry {
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::cgroup, set = Self::set_cgroup, type = glib::GString)]
+        cgroup: Cell<glib::GString>,
+
+        #[property(get = Self::slice, set = Self::set_slice, type = glib::GString)]
+        slice: Cell<glib::GString>,
+
+        #[property(get, set)]
+        cpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        memory_usage: Cell<u64>,
+
+        /// 0 if the memory of the unit isn't limited
+        #[property(get, set)]
+        memory_max: Cell<u64>,
+
+        #[property(get, set)]
+        oom_kills: Cell<u64>,
+
+        #[property(get, set)]
+        read_speed: Cell<f64>,
+
+        #[property(get, set)]
+        read_total: Cell<u64>,
+
+        #[property(get, set)]
+        write_speed: Cell<f64>,
+
+        #[property(get, set)]
+        write_total: Cell<u64>,
+
+        #[property(get, set)]
+        running_processes: Cell<u32>,
+
+        /// Processes and threads of the unit
+        #[property(get, set)]
+        tasks: Cell<u64>,
+
+        pub process_tree: RefCell<Vec<ProcessTreeNode>>,
+    }
+
+    impl Default for UnitEntry {
+        fn default() -> Self {
+            Self {
+                name: Cell::new(glib::GString::default()),
+                cgroup: Cell::new(glib::GString::default()),
+                slice: Cell::new(glib::GString::default()),
+                cpu_usage: Cell::new(0.0),
+                memory_usage: Cell::new(0),
+                memory_max: Cell::new(0),
+                oom_kills: Cell::new(0),
+                read_speed: Cell::new(0.0),
+                read_total: Cell::new(0),
+                write_speed: Cell::new(0.0),
+                write_total: Cell::new(0),
+                running_processes: Cell::new(0),
+                tasks: Cell::new(0),
+                process_tree: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl UnitEntry {
+        gstring_getter_setter!(name, cgroup, slice);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for UnitEntry {
+        const NAME: &'static str = "UnitEntry";
+        type Type = super::UnitEntry;
+    }
+
+    impl ObjectImpl for UnitEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct UnitEntry(ObjectSubclass<imp::UnitEntry>);
+}
+
+impl UnitEntry {
+    pub fn new(unit: &Unit, apps_context: &AppsContext) -> Self {
+        let this: Self = glib::Object::builder()
+            .property("name", &unit.unit.name)
+            .property("cgroup", &unit.unit.cgroup)
+            .property("slice", unit.unit.slice.as_deref().unwrap_or_default())
+            .build();
+        this.update(unit, apps_context);
+        this
+    }
+
+    pub fn update(&self, unit: &Unit, apps_context: &AppsContext) {
+        self.set_cpu_usage(unit.cpu_time_ratio(apps_context));
+        self.set_memory_usage(unit.memory_usage(apps_context) as u64);
+        self.set_read_speed(unit.read_speed(apps_context));
+        self.set_read_total(unit.read_total(apps_context));
+        self.set_write_speed(unit.write_speed(apps_context));
+        self.set_write_total(unit.write_total(apps_context));
+        self.set_running_processes(unit.running_processes() as u32);
+
+        let stats = apps_context.get_cgroup_stats(&unit.unit.cgroup);
+        self.set_memory_max(stats.and_then(|stats| stats.memory_max).unwrap_or_default());
+        self.set_oom_kills(
+            stats
+                .and_then(|stats| stats.memory_events)
+                .map(|memory_events| memory_events.oom_kill)
+                .unwrap_or_default(),
+        );
+        self.set_tasks(
+            stats
+                .and_then(|stats| stats.pids_current)
+                .unwrap_or(unit.running_processes() as u64),
+        );
+
+        self.imp()
+            .process_tree
+            .replace(unit.process_tree(apps_context));
+    }
+
+    pub fn process_tree(&self) -> Vec<ProcessTreeNode> {
+        self.imp().process_tree.borrow().clone()
+    }
+}
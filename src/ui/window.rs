@@ -1,4 +1,14 @@
-use process_data::{Niceness, ProcessData};
+use process_data::{
+    cgroup::CgroupStats,
+    delta::ProcessDataUpdate,
+    pressure::{Pressure, PressureResource},
+    proc_connector::ProcessEvents,
+    protocol::PROTOCOL_VERSION,
+    scanner::ProcessFields,
+    signal::signal_name,
+    IoPriority, Niceness, SchedulingPolicy,
+};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -15,8 +25,12 @@ use crate::gui::ARGS;
 use crate::i18n::{i18n, i18n_f, ni18n_f};
 use crate::ui::pages::applications::ResApplications;
 use crate::ui::pages::battery::ResBattery;
+use crate::ui::pages::containers::ResContainers;
 use crate::ui::pages::drive::ResDrive;
+use crate::ui::pages::io::ResIo;
+use crate::ui::pages::process_log::ResProcessLog;
 use crate::ui::pages::processes::ResProcesses;
+use crate::ui::pages::services::ResServices;
 use crate::utils::app::AppsContext;
 use crate::utils::battery::{Battery, BatteryData};
 use crate::utils::cpu::{self, CpuData};
@@ -26,7 +40,7 @@ use crate::utils::memory::MemoryData;
 use crate::utils::network::{NetworkData, NetworkInterface};
 use crate::utils::npu::{Npu, NpuData};
 use crate::utils::process::{Process, ProcessAction};
-use crate::utils::settings::SETTINGS;
+use crate::utils::settings::{MemoryMetric, SETTINGS};
 
 use super::pages::gpu::ResGPU;
 use super::pages::network::ResNetwork;
@@ -37,7 +51,17 @@ use super::pages::{applications, processes};
 pub enum Action {
     ManipulateProcesses(ProcessAction, Vec<libc::pid_t>, ToastOverlay),
     ManipulateApp(ProcessAction, String, ToastOverlay),
-    AdjustProcess(libc::pid_t, Niceness, Vec<bool>, String, ToastOverlay),
+    ManipulateContainer(ProcessAction, String, ToastOverlay),
+    AdjustProcess(
+        libc::pid_t,
+        Niceness,
+        Vec<bool>,
+        Option<(SchedulingPolicy, u32)>,
+        Option<IoPriority>,
+        String,
+        ToastOverlay,
+    ),
+    RefreshEarly,
 }
 
 mod imp {
@@ -46,8 +70,9 @@ mod imp {
     use crate::{
         ui::{
             pages::{
-                applications::ResApplications, cpu::ResCPU, memory::ResMemory,
-                processes::ResProcesses,
+                applications::ResApplications, containers::ResContainers, cpu::ResCPU, io::ResIo,
+                memory::ResMemory, process_log::ResProcessLog, processes::ResProcesses,
+                services::ResServices,
             },
             widgets::stack_sidebar::ResStackSidebar,
         },
@@ -56,13 +81,15 @@ mod imp {
 
     use super::*;
 
-    use async_channel::{unbounded, Receiver, Sender};
+    use async_channel::{bounded, unbounded, Receiver, Sender};
     use gtk::CompositeTemplate;
     use process_data::pci_slot::PciSlot;
 
     #[derive(Debug, CompositeTemplate)]
     #[template(resource = "/net/nokyan/Resources/ui/window.ui")]
     pub struct MainWindow {
+        #[template_child]
+        pub process_protocol_banner: TemplateChild<adw::Banner>,
         #[template_child]
         pub split_view: TemplateChild<adw::OverlaySplitView>,
         #[template_child]
@@ -84,9 +111,25 @@ mod imp {
         #[template_child]
         pub processes_page: TemplateChild<gtk::StackPage>,
         #[template_child]
+        pub containers: TemplateChild<ResContainers>,
+        #[template_child]
+        pub containers_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
+        pub services: TemplateChild<ResServices>,
+        #[template_child]
+        pub services_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
+        pub process_log: TemplateChild<ResProcessLog>,
+        #[template_child]
+        pub process_log_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
         pub memory: TemplateChild<ResMemory>,
         #[template_child]
         pub memory_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
+        pub io: TemplateChild<ResIo>,
+        #[template_child]
+        pub io_page: TemplateChild<gtk::StackPage>,
 
         pub drive_pages: RefCell<HashMap<PathBuf, adw::ToolbarView>>,
 
@@ -102,6 +145,8 @@ mod imp {
 
         pub sender: Sender<Action>,
         pub receiver: RefCell<Option<Receiver<Action>>>,
+
+        pub early_refresh: (Sender<()>, Receiver<()>),
     }
 
     impl Default for MainWindow {
@@ -113,6 +158,7 @@ mod imp {
                 drive_pages: RefCell::default(),
                 network_pages: RefCell::default(),
                 battery_pages: RefCell::default(),
+                process_protocol_banner: TemplateChild::default(),
                 split_view: TemplateChild::default(),
                 resources_sidebar: TemplateChild::default(),
                 content_stack: TemplateChild::default(),
@@ -120,13 +166,22 @@ mod imp {
                 applications_page: TemplateChild::default(),
                 processes: TemplateChild::default(),
                 processes_page: TemplateChild::default(),
+                containers: TemplateChild::default(),
+                containers_page: TemplateChild::default(),
+                services: TemplateChild::default(),
+                services_page: TemplateChild::default(),
+                process_log: TemplateChild::default(),
+                process_log_page: TemplateChild::default(),
                 cpu: TemplateChild::default(),
                 cpu_page: TemplateChild::default(),
                 memory: TemplateChild::default(),
                 memory_page: TemplateChild::default(),
+                io: TemplateChild::default(),
+                io_page: TemplateChild::default(),
                 apps_context: Default::default(),
                 sender,
                 receiver,
+                early_refresh: bounded(1),
                 processor_window_title: TemplateChild::default(),
                 gpu_pages: RefCell::default(),
                 npu_pages: RefCell::default(),
@@ -193,6 +248,7 @@ glib::wrapper! {
 struct RefreshData {
     cpu_data: Option<CpuData>,
     mem_data: Option<Result<MemoryData>>,
+    io_pressure: Option<Pressure>,
     gpu_data: Vec<GpuData>,
     npu_data: Vec<NpuData>,
     drive_paths: Vec<PathBuf>,
@@ -201,7 +257,9 @@ struct RefreshData {
     network_data: Vec<NetworkData>,
     battery_paths: Vec<PathBuf>,
     battery_data: Vec<BatteryData>,
-    process_data: Vec<ProcessData>,
+    process_data: Option<ProcessDataUpdate>,
+    process_events: Option<ProcessEvents>,
+    cgroup_stats: Option<HashMap<String, CgroupStats>>,
 }
 
 impl MainWindow {
@@ -245,6 +303,12 @@ impl MainWindow {
             imp.applications.toggle_search();
         } else if selected_page.is::<ResProcesses>() {
             imp.processes.toggle_search();
+        } else if selected_page.is::<ResContainers>() {
+            imp.containers.toggle_search();
+        } else if selected_page.is::<ResServices>() {
+            imp.services.toggle_search();
+        } else if selected_page.is::<ResProcessLog>() {
+            imp.process_log.toggle_search();
         }
     }
 
@@ -264,6 +328,11 @@ impl MainWindow {
                 imp.processes
                     .open_process_action_dialog(selected, process_action);
             }
+        } else if selected_page.is::<ResContainers>() {
+            if let Some(container_item) = imp.containers.get_selected_container_entry() {
+                imp.containers
+                    .open_container_action_dialog(&container_item, process_action);
+            }
         }
     }
 
@@ -281,6 +350,10 @@ impl MainWindow {
             if selected.len() == 1 {
                 imp.processes.open_info_dialog(&selected[0]);
             }
+        } else if selected_page.is::<ResServices>() {
+            if let Some(unit_item) = imp.services.get_selected_unit_entry() {
+                imp.services.open_info_dialog(&unit_item);
+            }
         }
     }
 
@@ -383,6 +456,9 @@ impl MainWindow {
         if ARGS.disable_process_monitoring {
             self.remove_page(imp.applications_page.child().downcast_ref().unwrap());
             self.remove_page(imp.processes_page.child().downcast_ref().unwrap());
+            self.remove_page(imp.containers_page.child().downcast_ref().unwrap());
+            self.remove_page(imp.services_page.child().downcast_ref().unwrap());
+            self.remove_page(imp.process_log_page.child().downcast_ref().unwrap());
         } else {
             *imp.apps_context.borrow_mut() = AppsContext::new(
                 gpus.iter()
@@ -392,6 +468,9 @@ impl MainWindow {
             );
             imp.applications.init(imp.sender.clone());
             imp.processes.init(imp.sender.clone());
+            imp.containers.init(imp.sender.clone());
+            imp.services.init(imp.sender.clone());
+            imp.process_log.init();
         }
 
         if ARGS.disable_cpu_monitoring {
@@ -411,6 +490,13 @@ impl MainWindow {
             imp.memory.init();
         }
 
+        // the I/O page only shows pressure stall information so far, which isn't available on every kernel
+        if ARGS.disable_drive_monitoring || Pressure::system(PressureResource::Io).is_err() {
+            self.remove_page(imp.io_page.child().downcast_ref().unwrap());
+        } else {
+            imp.io.init();
+        }
+
         if !ARGS.disable_gpu_monitoring {
             self.init_gpu_pages();
         }
@@ -430,7 +516,15 @@ impl MainWindow {
         ));
     }
 
-    fn gather_refresh_data(logical_cpus: usize, gpus: &[Gpu], npus: &[Npu]) -> RefreshData {
+    fn gather_refresh_data(
+        logical_cpus: usize,
+        gpus: &[Gpu],
+        npus: &[Npu],
+        process_monitoring: bool,
+        process_data_sequence: u64,
+        cgroups: &[String],
+        process_events_available: &mut bool,
+    ) -> RefreshData {
         let cpu_data = if ARGS.disable_cpu_monitoring {
             None
         } else {
@@ -443,6 +537,12 @@ impl MainWindow {
             Some(MemoryData::new())
         };
 
+        let io_pressure = if ARGS.disable_drive_monitoring {
+            None
+        } else {
+            Pressure::system(PressureResource::Io).ok()
+        };
+
         let mut gpu_data = Vec::with_capacity(gpus.len());
         for gpu in gpus {
             let data = GpuData::new(gpu);
@@ -487,22 +587,49 @@ impl MainWindow {
             battery_data.push(BatteryData::new(path));
         }
 
-        let process_data = if ARGS.disable_process_monitoring {
-            Vec::new()
-        } else {
-            Process::all_data()
+        // if updating fails, keep the processes we know of instead of making them all look dead
+        let process_data = if process_monitoring {
+            Process::all_data_update(process_data_sequence)
                 .inspect_err(|e| {
                     warn!(
                         "Unable to update process and app data!\n{e}\n{}",
                         e.backtrace()
                     );
                 })
-                .unwrap_or_default()
+                .ok()
+        } else {
+            Some(ProcessDataUpdate::Full {
+                sequence: 0,
+                processes: Vec::new(),
+            })
+        };
+
+        // the events have to be taken right after the process data so that they cover the same period of time
+        let process_events = if !process_monitoring || !*process_events_available {
+            None
+        } else {
+            Process::process_events()
+                .inspect_err(|e| {
+                    // this is expected when resources-processes is running without elevated privileges, so there's
+                    // no need to keep on trying
+                    info!("Process events are unavailable, exit statuses and short-lived processes won't be logged\n{e}");
+                    *process_events_available = false;
+                })
+                .ok()
+        };
+
+        let cgroup_stats = if !process_monitoring || cgroups.is_empty() {
+            Some(HashMap::new())
+        } else {
+            Process::cgroup_stats(cgroups)
+                .inspect_err(|e| warn!("Unable to update container statistics!\n{e}"))
+                .ok()
         };
 
         RefreshData {
             cpu_data,
             mem_data,
+            io_pressure,
             gpu_data,
             npu_data,
             drive_paths,
@@ -512,6 +639,8 @@ impl MainWindow {
             battery_paths,
             battery_data,
             process_data,
+            process_events,
+            cgroup_stats,
         }
     }
 
@@ -521,6 +650,7 @@ impl MainWindow {
         let RefreshData {
             cpu_data,
             mem_data,
+            io_pressure,
             gpu_data,
             npu_data,
             drive_paths,
@@ -530,6 +660,8 @@ impl MainWindow {
             battery_paths,
             battery_data,
             process_data,
+            process_events,
+            cgroup_stats,
         } = refresh_data;
 
         /*
@@ -537,10 +669,19 @@ impl MainWindow {
          */
 
         let mut apps_context = imp.apps_context.borrow_mut();
-        apps_context.refresh(process_data);
+        if let Some(process_events) = process_events {
+            apps_context.refresh_process_events(process_events);
+        }
+        if let Some(process_data) = process_data {
+            apps_context.refresh(process_data);
+        }
+        apps_context.refresh_cgroup_stats(cgroup_stats);
 
         imp.applications.refresh_apps_list(&apps_context);
         imp.processes.refresh_processes_list(&apps_context);
+        imp.containers.refresh_containers_list(&apps_context);
+        imp.services.refresh_services_list(&apps_context);
+        imp.process_log.refresh_process_log(&apps_context);
 
         /*
          *  Gpu
@@ -605,6 +746,11 @@ impl MainWindow {
             }
         }
 
+        /*
+         * I/O
+         */
+        imp.io.refresh_page(io_pressure.as_ref());
+
         /*
          *  Drives
          */
@@ -659,6 +805,58 @@ impl MainWindow {
         }
     }
 
+    /// Returns the optional parts of the process data that are needed by the columns the user has chosen to show,
+    /// the open info dialogs and the GPU pages.
+    fn needed_process_fields(&self) -> ProcessFields {
+        let imp = self.imp();
+
+        let app_dialog_open = imp.applications.has_open_info_dialog();
+        let process_dialog_open = imp.processes.has_open_info_dialog();
+
+        let mut fields = ProcessFields::NONE;
+
+        if app_dialog_open
+            || process_dialog_open
+            || SETTINGS.apps_show_drive_read_speed()
+            || SETTINGS.apps_show_drive_read_total()
+            || SETTINGS.apps_show_drive_write_speed()
+            || SETTINGS.apps_show_drive_write_total()
+            || SETTINGS.processes_show_drive_read_speed()
+            || SETTINGS.processes_show_drive_read_total()
+            || SETTINGS.processes_show_drive_write_speed()
+            || SETTINGS.processes_show_drive_write_total()
+        {
+            fields |= ProcessFields::IO;
+        }
+
+        // the GPU pages use the statistics of the processes to determine the usage of the GPUs
+        if app_dialog_open
+            || process_dialog_open
+            || !imp.gpu_pages.borrow().is_empty()
+            || SETTINGS.apps_show_gpu()
+            || SETTINGS.apps_show_gpu_memory()
+            || SETTINGS.apps_show_encoder()
+            || SETTINGS.apps_show_decoder()
+            || SETTINGS.processes_show_gpu()
+            || SETTINGS.processes_show_gpu_memory()
+            || SETTINGS.processes_show_encoder()
+            || SETTINGS.processes_show_decoder()
+        {
+            fields |= ProcessFields::GPU;
+        }
+
+        // the list of threads is shown in the process dialog and in expanded rows of the processes list
+        if process_dialog_open || imp.processes.has_expanded_processes() {
+            fields |= ProcessFields::THREADS;
+        }
+
+        if process_dialog_open || SETTINGS.memory_metric() != MemoryMetric::Resident {
+            fields |= ProcessFields::SMAPS_ROLLUP;
+        }
+
+        fields
+    }
+
     pub async fn periodic_refresh_all(&self) {
         let imp = self.imp();
 
@@ -686,20 +884,52 @@ impl MainWindow {
 
         let logical_cpus = imp.cpu.imp().logical_cpus_amount.get();
 
+        let mut process_fields = self.needed_process_fields();
+
         let (tx_data, rx_data) = std::sync::mpsc::sync_channel(1);
         let (tx_wait, rx_wait) = std::sync::mpsc::sync_channel(1);
 
         std::thread::spawn(move || {
+            let mut process_data_sequence = 0;
+            let mut cgroups = Vec::new();
+            let mut selected_process_fields = None;
+            let mut process_events_available = true;
+
+            // there's no point in asking resources-processes for anything if we can't understand its answers, the UI
+            // tells the user about that
+            let process_monitoring = !ARGS.disable_process_monitoring
+                && Process::incompatible_protocol_version().is_none();
+
             loop {
-                let data = Self::gather_refresh_data(logical_cpus, &gpus, &npus);
+                if process_monitoring && selected_process_fields != Some(process_fields) {
+                    // if this fails, resources-processes keeps collecting everything, so there's no need to retry
+                    if let Err(e) = Process::select_fields(process_fields) {
+                        warn!("Unable to select the process data fields to collect\n{e}");
+                    }
+                    selected_process_fields = Some(process_fields);
+                }
+
+                let data = Self::gather_refresh_data(
+                    logical_cpus,
+                    &gpus,
+                    &npus,
+                    process_monitoring,
+                    process_data_sequence,
+                    &cgroups,
+                    &mut process_events_available,
+                );
                 tx_data.send(data).unwrap();
 
                 // Wait on delay so we don't gather data multiple times in a short time span
                 // Which usually just yields the same data and makes changes appear delayed by (up to) multiple refreshes
-                rx_wait.recv().unwrap();
+                // The UI thread also tells us which process data update it has applied last, which cgroups it
+                // has seen and which parts of the process data it needs
+                (process_data_sequence, cgroups, process_fields) = rx_wait.recv().unwrap();
             }
         });
 
+        let early_refresh = imp.early_refresh.1.clone();
+
         let mut first_refresh = true;
 
         loop {
@@ -730,6 +960,18 @@ impl MainWindow {
                     }
                 }
 
+                // the gather thread has already found out by now, so this doesn't block
+                if !ARGS.disable_process_monitoring {
+                    if let Some(version) = Process::incompatible_protocol_version() {
+                        // Translators: Both placeholders are version numbers
+                        imp.process_protocol_banner.set_title(&i18n_f(
+                            "Processes can't be shown because Resources expects version {} of its helper's protocol but the helper speaks version {}",
+                            &[&PROTOCOL_VERSION.to_string(), &version.to_string()],
+                        ));
+                        imp.process_protocol_banner.set_revealed(true);
+                    }
+                }
+
                 first_refresh = false;
             }
 
@@ -739,10 +981,24 @@ impl MainWindow {
             // Reasonable timespan before total_delay ends to gather all data
             let gather_time = 0.2;
 
-            timeout_future(Duration::from_secs_f32(total_delay - gather_time)).await;
+            // an early refresh is requested when the UI needs process data that hasn't been collected so far
+            let _ = glib::future_with_timeout(
+                Duration::from_secs_f32(total_delay - gather_time),
+                early_refresh.recv(),
+            )
+            .await;
 
             // Tell other threads to start gethering data
-            tx_wait.send(()).unwrap();
+            {
+                let apps_context = imp.apps_context.borrow();
+                tx_wait
+                    .send((
+                        apps_context.process_data_sequence(),
+                        apps_context.seen_cgroups(),
+                        self.needed_process_fields(),
+                    ))
+                    .unwrap();
+            }
 
             timeout_future(Duration::from_secs_f32(gather_time)).await;
         }
@@ -1031,9 +1287,36 @@ impl MainWindow {
                 toast_overlay.add_toast(Toast::new(&toast_message));
             }
 
-            Action::AdjustProcess(pid, niceness, affinity, display_name, toast_overlay) => {
+            Action::ManipulateContainer(action, cgroup, toast_overlay) => {
+                let Some(container) = apps_context.get_container(&cgroup) else {
+                    return;
+                };
+                let result = container.execute_process_action(&apps_context, action);
+
+                let processes_tried = result.len();
+                let processes_successful = result.iter().flatten().count();
+                let processes_unsuccessful = processes_tried - processes_successful;
+
+                let toast_message = if processes_unsuccessful > 0 {
+                    get_action_failure(action, processes_unsuccessful)
+                } else {
+                    get_action_success(action, &container.display_name())
+                };
+
+                toast_overlay.add_toast(Toast::new(&toast_message));
+            }
+
+            Action::AdjustProcess(
+                pid,
+                niceness,
+                affinity,
+                scheduling,
+                io_priority,
+                display_name,
+                toast_overlay,
+            ) => {
                 if let Some(process) = apps_context.get_process(pid) {
-                    let result = process.adjust(niceness, affinity);
+                    let result = process.adjust(niceness, affinity, scheduling, io_priority);
 
                     let toast_message = match result {
                         Ok(()) => i18n_f("Successfully adjusted {}", &[&display_name]),
@@ -1042,6 +1325,11 @@ impl MainWindow {
                     toast_overlay.add_toast(Toast::new(&toast_message));
                 }
             }
+
+            Action::RefreshEarly => {
+                // a full channel means that an early refresh is already pending
+                let _ = self.imp().early_refresh.0.try_send(());
+            }
         };
     }
 
@@ -1121,6 +1409,10 @@ fn get_action_success(action: ProcessAction, name: &str) -> String {
         ProcessAction::STOP => i18n_f("Successfully halted {}", &[name]),
         ProcessAction::KILL => i18n_f("Successfully killed {}", &[name]),
         ProcessAction::CONT => i18n_f("Successfully continued {}", &[name]),
+        // Translators: The first placeholder is the name of a signal, e.g. "SIGHUP"
+        ProcessAction::Signal(signal) => {
+            i18n_f("Successfully sent {} to {}", &[&signal_name(signal), name])
+        }
     }
 }
 
@@ -1150,6 +1442,13 @@ fn get_processes_success(action: ProcessAction, count: usize) -> String {
             count as u32,
             &[&count.to_string()],
         ),
+        // Translators: The first placeholder is the name of a signal, e.g. "SIGHUP"
+        ProcessAction::Signal(signal) => ni18n_f(
+            "Successfully sent {} to the process",
+            "Successfully sent {} to {} processes",
+            count as u32,
+            &[&signal_name(signal), &count.to_string()],
+        ),
     }
 }
 
@@ -1179,6 +1478,13 @@ fn get_action_failure(action: ProcessAction, count: usize) -> String {
             count as u32,
             &[&count.to_string()],
         ),
+        // Translators: The first placeholder is the name of a signal, e.g. "SIGHUP"
+        ProcessAction::Signal(signal) => ni18n_f(
+            "There was a problem sending {} to a process",
+            "There were problems sending {} to {} processes",
+            count as u32,
+            &[&signal_name(signal), &count.to_string()],
+        ),
     }
 }
 
@@ -1188,5 +1494,10 @@ pub fn get_named_action_failure(action: ProcessAction, name: &str) -> String {
         ProcessAction::STOP => i18n_f("There was a problem halting {}", &[name]),
         ProcessAction::KILL => i18n_f("There was a problem killing {}", &[name]),
         ProcessAction::CONT => i18n_f("There was a problem continuing {}", &[name]),
+        // Translators: The first placeholder is the name of a signal, e.g. "SIGHUP"
+        ProcessAction::Signal(signal) => i18n_f(
+            "There was a problem sending {} to {}",
+            &[&signal_name(signal), name],
+        ),
     }
 }
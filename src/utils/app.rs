@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     path::{Path, PathBuf},
     sync::LazyLock,
     time::Instant,
@@ -12,14 +12,24 @@ use gtk::{
 };
 use lazy_regex::{lazy_regex, Lazy, Regex};
 use log::{debug, info};
-use process_data::{pci_slot::PciSlot, Containerization, ProcessData};
+use process_data::{
+    cgroup::{CgroupStats, SystemdUnit},
+    delta::ProcessDataUpdate,
+    pci_slot::PciSlot,
+    pressure::{Pressure, PressureResource},
+    proc_connector::ProcessEvents,
+    unix_as_millis, Containerization, GpuEngineClass, ProcessData,
+};
 
 use crate::i18n::i18n;
 
 use super::{
     boot_time,
-    process::{Process, ProcessAction},
-    FiniteOr,
+    container::Container,
+    process::{engine_class_fraction, Process, ProcessAction},
+    process_log::{LoggedProcess, ProcessLog, ProcessUsage},
+    unit::Unit,
+    FiniteOr, NUM_CPUS,
 };
 
 /// This contains the cgroups of desktop environments. If a process has this as its cgroup, its parent's cgroup will be
@@ -163,7 +173,13 @@ static MESSAGE_LOCALES: LazyLock<Vec<String>> = LazyLock::new(|| {
 pub struct AppsContext {
     apps: HashMap<Option<String>, App>,
     processes: HashMap<i32, Process>,
+    containers: HashMap<String, Container>,
+    units: HashMap<String, Unit>,
+    cgroup_stats: HashMap<String, CgroupStats>,
+    cgroup_stats_last: HashMap<String, CgroupStats>,
     gpus_with_combined_media_engine: Vec<PciSlot>,
+    process_data_sequence: u64,
+    process_log: ProcessLog,
 }
 
 /// Represents an application installed on the system. It doesn't
@@ -179,6 +195,9 @@ pub struct App {
     pub id: Option<String>,
     pub read_bytes_from_dead_processes: u64,
     pub write_bytes_from_dead_processes: u64,
+    /// cgroups that only contain processes of this app, the kernel's accounting of these is used instead of summing
+    /// up the values of their processes
+    pub cgroups: Vec<String>,
     pub containerization: Containerization,
 }
 
@@ -226,6 +245,7 @@ impl App {
             id: None,
             read_bytes_from_dead_processes: 0,
             write_bytes_from_dead_processes: 0,
+            cgroups: Vec::new(),
             containerization: Containerization::None,
         });
 
@@ -326,7 +346,7 @@ impl App {
             .or_else(|| desktop_entry.get("Comment"))
             .map(str::to_string);
 
-        let is_snap = desktop_entry.get("X-SnapInstanceName").is_some();
+        let snap_name = desktop_entry.get("X-SnapInstanceName");
 
         let containerization = if is_flatpak {
             debug!(
@@ -335,15 +355,15 @@ impl App {
                 commandline.as_ref().unwrap_or(&"<None>".into()),
                 executable_name.as_ref().unwrap_or(&"<None>".into()),
             );
-            Containerization::Flatpak
-        } else if is_snap {
+            Containerization::Flatpak(Some(id.clone()))
+        } else if let Some(snap_name) = snap_name {
             debug!(
                 "Found Snap app \"{display_name}\" (ID: {id}) at {} with commandline `{}` (detected executable name: {})",
                 file_path.to_string_lossy(),
                 commandline.as_ref().unwrap_or(&"<None>".into()),
                 executable_name.as_ref().unwrap_or(&"<None>".into()),
             );
-            Containerization::Snap
+            Containerization::Snap(Some(snap_name.to_string()))
         } else {
             debug!(
                 "Found native app \"{display_name}\" (ID: {id}) at {} with commandline `{}` (detected executable name: {})",
@@ -366,6 +386,7 @@ impl App {
             id,
             read_bytes_from_dead_processes: 0,
             write_bytes_from_dead_processes: 0,
+            cgroups: Vec::new(),
             containerization,
         })
     }
@@ -402,9 +423,7 @@ impl App {
 
     #[must_use]
     pub fn memory_usage(&self, apps: &AppsContext) -> usize {
-        self.processes_iter(apps)
-            .map(|process| process.data.memory_usage)
-            .sum()
+        apps.cgroups_memory_usage(&self.cgroups, self.processes_iter(apps))
     }
 
     #[must_use]
@@ -416,39 +435,36 @@ impl App {
 
     #[must_use]
     pub fn cpu_time_ratio(&self, apps: &AppsContext) -> f32 {
-        self.processes_iter(apps).map(Process::cpu_time_ratio).sum()
+        apps.cgroups_cpu_time_ratio(&self.cgroups, self.processes_iter(apps))
     }
 
     #[must_use]
     pub fn read_speed(&self, apps: &AppsContext) -> f64 {
-        self.processes_iter(apps)
-            .filter_map(Process::read_speed)
-            .sum()
+        apps.cgroups_read_speed(&self.cgroups, self.processes_iter(apps))
     }
 
     #[must_use]
     pub fn read_total(&self, apps: &AppsContext) -> u64 {
-        self.read_bytes_from_dead_processes.saturating_add(
-            self.processes_iter(apps)
-                .filter_map(|process| process.data.read_bytes)
-                .sum::<u64>(),
-        )
+        self.read_bytes_from_dead_processes
+            .saturating_add(apps.cgroups_read_total(&self.cgroups, self.processes_iter(apps)))
     }
 
     #[must_use]
     pub fn write_speed(&self, apps: &AppsContext) -> f64 {
-        self.processes_iter(apps)
-            .filter_map(Process::write_speed)
-            .sum()
+        apps.cgroups_write_speed(&self.cgroups, self.processes_iter(apps))
     }
 
     #[must_use]
     pub fn write_total(&self, apps: &AppsContext) -> u64 {
-        self.write_bytes_from_dead_processes.saturating_add(
-            self.processes_iter(apps)
-                .filter_map(|process| process.data.write_bytes)
-                .sum::<u64>(),
-        )
+        self.write_bytes_from_dead_processes
+            .saturating_add(apps.cgroups_write_total(&self.cgroups, self.processes_iter(apps)))
+    }
+
+    /// Returns the pressure stall information of `resource` for this app, `None` if the app doesn't have any cgroups
+    /// of its own or the kernel doesn't support PSI.
+    #[must_use]
+    pub fn pressure(&self, apps: &AppsContext, resource: PressureResource) -> Option<Pressure> {
+        apps.cgroups_pressure(&self.cgroups, resource)
     }
 
     #[must_use]
@@ -517,122 +533,50 @@ impl AppsContext {
         AppsContext {
             apps,
             processes: HashMap::new(),
+            containers: HashMap::new(),
+            units: HashMap::new(),
+            cgroup_stats: HashMap::new(),
+            cgroup_stats_last: HashMap::new(),
             gpus_with_combined_media_engine,
+            process_data_sequence: 0,
+            process_log: ProcessLog::new(),
         }
     }
 
+    /// Returns the usage of every engine of the GPU at `pci_slot`, summed up across all processes.
+    ///
+    /// Key: Name of the engine, Value: Usage from 0.0 to 1.0
+    pub fn gpu_engine_fractions(&self, pci_slot: PciSlot) -> BTreeMap<String, f32> {
+        let mut fractions = BTreeMap::new();
+
+        for engines in self
+            .processes_iter()
+            .filter_map(|process| process.gpu_engine_usage().remove(&pci_slot))
+        {
+            for (engine, fraction) in engines {
+                *fractions.entry(engine).or_default() += fraction;
+            }
+        }
+
+        fractions
+    }
+
     pub fn gpu_fraction(&self, pci_slot: PciSlot) -> f32 {
-        self.processes_iter()
-            .map(|process| {
-                (
-                    &process.data.gpu_usage_stats,
-                    &process.gpu_usage_stats_last,
-                    process.data.timestamp,
-                    process.timestamp_last,
-                )
-            })
-            .map(|(new, old, timestamp, timestamp_last)| {
-                (
-                    new.get(&pci_slot),
-                    old.get(&pci_slot),
-                    timestamp,
-                    timestamp_last,
-                )
-            })
-            .filter_map(|(new, old, timestamp, timestamp_last)| match (new, old) {
-                (Some(new), Some(old)) => Some((new, old, timestamp, timestamp_last)),
-                _ => None,
-            })
-            .map(|(new, old, timestamp, timestamp_last)| {
-                if new.nvidia {
-                    new.gfx as f32 / 100.0
-                } else if old.gfx == 0 {
-                    0.0
-                } else {
-                    ((new.gfx.saturating_sub(old.gfx) as f32)
-                        / (timestamp.saturating_sub(timestamp_last) as f32))
-                        .finite_or_default()
-                        / 1_000_000.0
-                }
-            })
-            .sum::<f32>()
-            .clamp(0.0, 1.0)
+        engine_class_fraction(&self.gpu_engine_fractions(pci_slot), |class| {
+            class == GpuEngineClass::Graphics
+        })
     }
 
     pub fn encoder_fraction(&self, pci_slot: PciSlot) -> f32 {
-        self.processes_iter()
-            .map(|process| {
-                (
-                    &process.data.gpu_usage_stats,
-                    &process.gpu_usage_stats_last,
-                    process.data.timestamp,
-                    process.timestamp_last,
-                )
-            })
-            .map(|(new, old, timestamp, timestamp_last)| {
-                (
-                    new.get(&pci_slot),
-                    old.get(&pci_slot),
-                    timestamp,
-                    timestamp_last,
-                )
-            })
-            .filter_map(|(new, old, timestamp, timestamp_last)| match (new, old) {
-                (Some(new), Some(old)) => Some((new, old, timestamp, timestamp_last)),
-                _ => None,
-            })
-            .map(|(new, old, timestamp, timestamp_last)| {
-                if new.nvidia {
-                    new.enc as f32 / 100.0
-                } else if old.enc == 0 {
-                    0.0
-                } else {
-                    ((new.enc.saturating_sub(old.enc) as f32)
-                        / (timestamp.saturating_sub(timestamp_last) as f32))
-                        .finite_or_default()
-                        / 1_000_000.0
-                }
-            })
-            .sum::<f32>()
-            .clamp(0.0, 1.0)
+        engine_class_fraction(&self.gpu_engine_fractions(pci_slot), |class| {
+            class.is_encode()
+        })
     }
 
     pub fn decoder_fraction(&self, pci_slot: PciSlot) -> f32 {
-        self.processes_iter()
-            .map(|process| {
-                (
-                    &process.data.gpu_usage_stats,
-                    &process.gpu_usage_stats_last,
-                    process.data.timestamp,
-                    process.timestamp_last,
-                )
-            })
-            .map(|(new, old, timestamp, timestamp_last)| {
-                (
-                    new.get(&pci_slot),
-                    old.get(&pci_slot),
-                    timestamp,
-                    timestamp_last,
-                )
-            })
-            .filter_map(|(new, old, timestamp, timestamp_last)| match (new, old) {
-                (Some(new), Some(old)) => Some((new, old, timestamp, timestamp_last)),
-                _ => None,
-            })
-            .map(|(new, old, timestamp, timestamp_last)| {
-                if new.nvidia {
-                    new.dec as f32 / 100.0
-                } else if old.dec == 0 {
-                    0.0
-                } else {
-                    ((new.dec.saturating_sub(old.dec) as f32)
-                        / (timestamp.saturating_sub(timestamp_last) as f32))
-                        .finite_or_default()
-                        / 1_000_000.0
-                }
-            })
-            .sum::<f32>()
-            .clamp(0.0, 1.0)
+        engine_class_fraction(&self.gpu_engine_fractions(pci_slot), |class| {
+            class.is_decode()
+        })
     }
 
     fn app_associated_with_process(&self, process: &Process) -> Option<String> {
@@ -731,6 +675,25 @@ impl AppsContext {
         self.apps.get(id)
     }
 
+    pub fn process_log(&self) -> &ProcessLog {
+        &self.process_log
+    }
+
+    /// Describes `process` for the process log, `app_id` being the ID of the app it belongs to.
+    fn logged_process(&self, process: &Process, app_id: &Option<String>) -> LoggedProcess {
+        LoggedProcess {
+            pid: process.data.pid,
+            name: process.display_name.clone(),
+            commandline: Process::sanitize_cmdline(&process.data.commandline).unwrap_or_default(),
+            user: Some(process.data.user.clone()),
+            app: app_id
+                .as_ref()
+                .and_then(|_| self.apps.get(app_id))
+                .map(|app| app.display_name.clone()),
+            started: process.start_timestamp(),
+        }
+    }
+
     #[must_use]
     pub fn processes_iter(&self) -> impl Iterator<Item = &Process> {
         self.processes.values()
@@ -741,6 +704,213 @@ impl AppsContext {
         self.processes.values_mut()
     }
 
+    pub fn get_container(&self, cgroup: &str) -> Option<&Container> {
+        self.containers.get(cgroup)
+    }
+
+    pub fn containers_iter(&self) -> impl Iterator<Item = &Container> {
+        self.containers.values()
+    }
+
+    pub fn get_unit(&self, cgroup: &str) -> Option<&Unit> {
+        self.units.get(cgroup)
+    }
+
+    pub fn units_iter(&self) -> impl Iterator<Item = &Unit> {
+        self.units.values()
+    }
+
+    pub fn get_cgroup_stats(&self, cgroup: &str) -> Option<&CgroupStats> {
+        self.cgroup_stats.get(cgroup)
+    }
+
+    /// Returns the cgroups of all running processes, containers and systemd units, these are the cgroups whose statistics should be
+    /// passed to `refresh_cgroup_stats()`.
+    pub fn seen_cgroups(&self) -> Vec<String> {
+        let mut cgroups: Vec<String> = self
+            .processes
+            .values()
+            .filter_map(|process| process.data.cgroup_path.clone())
+            .chain(self.containers.keys().cloned())
+            .chain(self.units.keys().cloned())
+            .collect();
+
+        cgroups.sort_unstable();
+        cgroups.dedup();
+        cgroups
+    }
+
+    /// Refreshes the statistics of the cgroups seen in the last refresh and determines which of them can be used to
+    /// account for the resource usage of apps.
+    ///
+    /// `cgroup_stats` is `None` if the statistics couldn't be fetched. In that case, the last known statistics are
+    /// kept so that the cgroups of apps aren't mistaken for being gone, but no rates are derived from them.
+    pub fn refresh_cgroup_stats(&mut self, cgroup_stats: Option<HashMap<String, CgroupStats>>) {
+        if let Some(cgroup_stats) = cgroup_stats {
+            self.cgroup_stats_last = std::mem::replace(&mut self.cgroup_stats, cgroup_stats);
+            self.refresh_app_cgroups();
+        } else {
+            self.cgroup_stats_last = self.cgroup_stats.clone();
+        }
+    }
+
+    /// Sums up a metric of `processes`, but whenever the kernel accounts for this metric in one of `cgroups`, the
+    /// whole cgroup's value is used instead of the values of its processes. This way, processes that have exited
+    /// already and kernel overhead are included.
+    ///
+    /// `cgroup_value` gets the current and the previous statistics of a cgroup.
+    fn cgroups_accounted_sum<'a, T, C, P>(
+        &self,
+        cgroups: &[String],
+        processes: impl Iterator<Item = &'a Process>,
+        cgroup_value: C,
+        process_value: P,
+    ) -> T
+    where
+        T: std::iter::Sum + std::ops::Add<Output = T>,
+        C: Fn(&CgroupStats, &CgroupStats) -> Option<T>,
+        P: Fn(&Process) -> T,
+    {
+        let mut accounted_cgroups = Vec::new();
+
+        let from_cgroups = cgroups
+            .iter()
+            .filter_map(|cgroup| {
+                let stats = self.cgroup_stats.get(cgroup)?;
+                let stats_last = self.cgroup_stats_last.get(cgroup)?;
+                let value = cgroup_value(stats, stats_last)?;
+                accounted_cgroups.push(cgroup.as_str());
+                Some(value)
+            })
+            .sum::<T>();
+
+        let from_processes = processes
+            .filter(|process| {
+                !accounted_cgroups
+                    .iter()
+                    .any(|cgroup| process.in_cgroup(cgroup))
+            })
+            .map(process_value)
+            .sum::<T>();
+
+        from_cgroups + from_processes
+    }
+
+    /// Returns the rate of change (per second) of a counter in the statistics of a cgroup.
+    fn cgroup_rate<F: Fn(&CgroupStats) -> Option<u64>>(
+        stats: &CgroupStats,
+        stats_last: &CgroupStats,
+        counter: F,
+    ) -> Option<f64> {
+        let delta = counter(stats)?.saturating_sub(counter(stats_last)?) as f64;
+        let time_delta = stats.timestamp.saturating_sub(stats_last.timestamp) as f64;
+        (time_delta > 0.0).then(|| (delta / time_delta) * 1000.0)
+    }
+
+    /// Returns the pressure stall information of `resource` of the most stalled cgroup in `cgroups`. The pressure of
+    /// several cgroups can't be summed up since it's a share of time, so the worst one is used instead.
+    pub fn cgroups_pressure(
+        &self,
+        cgroups: &[String],
+        resource: PressureResource,
+    ) -> Option<Pressure> {
+        cgroups
+            .iter()
+            .filter_map(|cgroup| self.cgroup_stats.get(cgroup))
+            .filter_map(|stats| match resource {
+                PressureResource::Cpu => stats.cpu_pressure,
+                PressureResource::Memory => stats.memory_pressure,
+                PressureResource::Io => stats.io_pressure,
+            })
+            .max_by(|a, b| a.some.avg10.total_cmp(&b.some.avg10))
+    }
+
+    pub fn cgroups_cpu_time_ratio<'a>(
+        &self,
+        cgroups: &[String],
+        processes: impl Iterator<Item = &'a Process>,
+    ) -> f32 {
+        self.cgroups_accounted_sum(
+            cgroups,
+            processes,
+            |stats, stats_last| {
+                // cpu_usage is in microseconds
+                Self::cgroup_rate(stats, stats_last, |stats| Some(stats.cpu_usage)).map(
+                    |usec_per_sec| {
+                        ((usec_per_sec / (1_000_000.0 * *NUM_CPUS as f64)) as f32)
+                            .finite_or_default()
+                    },
+                )
+            },
+            Process::cpu_time_ratio,
+        )
+    }
+
+    pub fn cgroups_memory_usage<'a>(
+        &self,
+        cgroups: &[String],
+        processes: impl Iterator<Item = &'a Process>,
+    ) -> usize {
+        self.cgroups_accounted_sum(
+            cgroups,
+            processes,
+            |stats, _| stats.memory_current.map(|memory| memory as usize),
+            Process::memory_usage,
+        )
+    }
+
+    pub fn cgroups_read_speed<'a>(
+        &self,
+        cgroups: &[String],
+        processes: impl Iterator<Item = &'a Process>,
+    ) -> f64 {
+        self.cgroups_accounted_sum(
+            cgroups,
+            processes,
+            |stats, stats_last| Self::cgroup_rate(stats, stats_last, |stats| stats.read_bytes),
+            |process| process.read_speed().unwrap_or_default(),
+        )
+    }
+
+    pub fn cgroups_read_total<'a>(
+        &self,
+        cgroups: &[String],
+        processes: impl Iterator<Item = &'a Process>,
+    ) -> u64 {
+        self.cgroups_accounted_sum(
+            cgroups,
+            processes,
+            |stats, _| stats.read_bytes,
+            |process| process.data.read_bytes.unwrap_or_default(),
+        )
+    }
+
+    pub fn cgroups_write_speed<'a>(
+        &self,
+        cgroups: &[String],
+        processes: impl Iterator<Item = &'a Process>,
+    ) -> f64 {
+        self.cgroups_accounted_sum(
+            cgroups,
+            processes,
+            |stats, stats_last| Self::cgroup_rate(stats, stats_last, |stats| stats.write_bytes),
+            |process| process.write_speed().unwrap_or_default(),
+        )
+    }
+
+    pub fn cgroups_write_total<'a>(
+        &self,
+        cgroups: &[String],
+        processes: impl Iterator<Item = &'a Process>,
+    ) -> u64 {
+        self.cgroups_accounted_sum(
+            cgroups,
+            processes,
+            |stats, _| stats.write_bytes,
+            |process| process.data.write_bytes.unwrap_or_default(),
+        )
+    }
+
     pub fn apps_iter(&self) -> impl Iterator<Item = &App> {
         self.apps.values()
     }
@@ -755,8 +925,82 @@ impl AppsContext {
         })
     }
 
+    /// Returns the sequence number of the last process data update that has been applied, this is what the next
+    /// delta from `resources-processes` needs to be based on. 0 means that a full update is needed.
+    pub fn process_data_sequence(&self) -> u64 {
+        self.process_data_sequence
+    }
+
+    /// Turns `update` into the data of all currently running processes. Returns `None` if `update` is a delta that
+    /// is not based on the last update we've applied, in that case the next update will be a full one.
+    fn process_data_from_update(&mut self, update: ProcessDataUpdate) -> Option<Vec<ProcessData>> {
+        match update {
+            ProcessDataUpdate::Full {
+                sequence,
+                processes,
+            } => {
+                self.process_data_sequence = sequence;
+                Some(processes)
+            }
+            ProcessDataUpdate::Delta(delta) => {
+                if delta.base_sequence != self.process_data_sequence {
+                    debug!(
+                        "Process data delta is based on {} but we're at {}, requesting full resync",
+                        delta.base_sequence, self.process_data_sequence
+                    );
+                    self.process_data_sequence = 0;
+                    return None;
+                }
+
+                self.process_data_sequence = delta.sequence;
+
+                let exited: HashSet<_> = delta.exited.into_iter().collect();
+
+                let mut changed: HashMap<_, _> = delta
+                    .changed
+                    .into_iter()
+                    .map(|process_data| (process_data.pid, process_data))
+                    .collect();
+
+                let mut process_data: Vec<_> = self
+                    .processes
+                    .values()
+                    .filter(|process| !exited.contains(&process.data.pid))
+                    .map(|process| {
+                        changed
+                            .remove(&process.data.pid)
+                            .unwrap_or_else(|| ProcessData {
+                                timestamp: delta.timestamp,
+                                ..process.data.clone()
+                            })
+                    })
+                    .collect();
+
+                // whatever is left are new processes
+                process_data.extend(changed.into_values());
+
+                Some(process_data)
+            }
+        }
+    }
+
+    /// Passes the process events that have been received since the last refresh on to the process log. This has to
+    /// be called before `refresh()` is called with the process data gathered at the same time as `events`.
+    pub fn refresh_process_events(&mut self, events: ProcessEvents) {
+        self.process_log.record_events(events);
+    }
+
     /// Refreshes the statistics about the running applications and processes.
-    pub fn refresh(&mut self, new_process_data: Vec<ProcessData>) {
+    pub fn refresh(&mut self, update: ProcessDataUpdate) {
+        let Some(new_process_data) = self.process_data_from_update(update) else {
+            return;
+        };
+
+        // the first refresh (and the one after a failed one) would see every process being started, which isn't
+        // worth logging
+        let log_changes = !self.processes.is_empty() && !new_process_data.is_empty();
+        let now = unix_as_millis();
+
         let mut updated_processes = HashSet::new();
 
         for mut process_data in new_process_data {
@@ -769,10 +1013,7 @@ impl AppsContext {
                 .gpu_usage_stats
                 .iter_mut()
                 .filter(|(pci_slot, _)| self.gpus_with_combined_media_engine.contains(pci_slot))
-                .for_each(|(_, stats)| {
-                    stats.dec = u64::max(stats.dec, stats.enc);
-                    stats.enc = u64::max(stats.dec, stats.enc);
-                });
+                .for_each(|(_, stats)| stats.combine_media_engines());
 
             // refresh our old processes
             if let Some(old_process) = self.processes.get_mut(&process_data.pid) {
@@ -784,22 +1025,68 @@ impl AppsContext {
                 old_process.read_bytes_last = old_process.data.read_bytes;
                 old_process.write_bytes_last = old_process.data.write_bytes;
                 old_process.gpu_usage_stats_last = old_process.data.gpu_usage_stats.clone();
+                old_process.thread_cpu_times_last = old_process
+                    .data
+                    .threads
+                    .iter()
+                    .map(|thread| {
+                        (
+                            thread.tid,
+                            thread.user_cpu_time.saturating_add(thread.system_cpu_time),
+                        )
+                    })
+                    .collect();
 
                 old_process.data = process_data.clone();
+                old_process.update_memory_usage_peak();
             } else {
                 // this is a new process, see if it belongs to a graphical app
 
                 let mut new_process = Process::from_process_data(process_data);
+                new_process.update_memory_usage_peak();
+
+                let app_id = self.app_associated_with_process(&new_process);
 
                 self.apps
-                    .get_mut(&self.app_associated_with_process(&new_process))
+                    .get_mut(&app_id)
                     .unwrap()
                     .add_process(&mut new_process);
 
+                if log_changes {
+                    let logged_process = self.logged_process(&new_process, &app_id);
+                    self.process_log.record_start(logged_process, now);
+                }
+
                 self.processes.insert(new_process.data.pid, new_process);
             }
         }
 
+        if log_changes {
+            let died_processes: Vec<_> = self
+                .processes
+                .values()
+                .filter(|process| !updated_processes.contains(&process.data.pid))
+                .map(|process| {
+                    let app_id = self
+                        .apps
+                        .values()
+                        .find(|app| app.processes.contains(&process.data.pid))
+                        .and_then(|app| app.id.clone());
+
+                    let usage = ProcessUsage {
+                        peak_memory: process.memory_usage_peak,
+                        cpu_time: process.total_cpu_time(),
+                    };
+
+                    (self.logged_process(process, &app_id), usage)
+                })
+                .collect();
+
+            for (logged_process, usage) in died_processes {
+                self.process_log.record_exit(logged_process, usage, now);
+            }
+        }
+
         // collect the I/O stats for died app processes so an app doesn't suddenly have less total disk I/O
         self.apps.values_mut().for_each(|app| {
             let (read_dead, write_dead) = app
@@ -807,6 +1094,16 @@ impl AppsContext {
                 .iter()
                 .filter(|pid| !updated_processes.contains(*pid)) // only dead processes
                 .filter_map(|pid| self.processes.get(pid)) // ignore about non-existing processes
+                .filter(|process| {
+                    // the I/O of processes in cgroups with I/O accounting is still part of their cgroup's statistics
+                    !app.cgroups.iter().any(|cgroup| {
+                        process.in_cgroup(cgroup)
+                            && self
+                                .cgroup_stats
+                                .get(cgroup)
+                                .is_some_and(|stats| stats.read_bytes.is_some())
+                    })
+                })
                 .map(|process| (process.data.read_bytes, process.data.write_bytes)) // get their read_bytes and write_bytes
                 .filter_map(
                     // filter out any processes whose IO stats we were not allowed to see
@@ -838,5 +1135,164 @@ impl AppsContext {
         // all the not-updated processes have unfortunately died, probably
         self.processes
             .retain(|pid, _| updated_processes.contains(pid));
+
+        self.process_log
+            .record_unseen_exits(|pid| updated_processes.contains(&pid), now);
+
+        self.refresh_containers();
+        self.refresh_units();
+    }
+
+    /// Groups the running processes by the container whose cgroup they're in.
+    fn refresh_containers(&mut self) {
+        self.containers
+            .values_mut()
+            .for_each(Container::clear_processes);
+
+        for process in self.processes.values() {
+            let Some((containerization, cgroup)) = process
+                .data
+                .cgroup_path
+                .as_deref()
+                .and_then(Containerization::from_cgroup_path)
+            else {
+                continue;
+            };
+
+            // the process itself may know more about its container, e.g. that it's a Toolbx
+            let containerization = if process.data.containerization == Containerization::None {
+                containerization
+            } else {
+                process.data.containerization.clone()
+            };
+
+            self.containers
+                .entry(cgroup)
+                .or_insert_with_key(|cgroup| Container::new(cgroup.clone(), containerization))
+                .add_process(process.data.pid);
+        }
+
+        self.containers
+            .retain(|_, container| container.is_running());
+    }
+
+    /// Groups the running processes by the systemd unit whose cgroup they're in.
+    fn refresh_units(&mut self) {
+        self.units.values_mut().for_each(Unit::clear_processes);
+
+        for process in self.processes.values() {
+            let Some(unit) = process
+                .data
+                .cgroup_path
+                .as_deref()
+                .and_then(SystemdUnit::from_cgroup_path)
+            else {
+                continue;
+            };
+
+            self.units
+                .entry(unit.cgroup.clone())
+                .or_insert_with(|| Unit::new(unit))
+                .add_process(process.data.pid);
+        }
+
+        self.units.retain(|_, unit| unit.is_running());
+    }
+
+    /// Finds the cgroups that only contain processes of a single app (e.g. the `app-*.scope` that systemd creates for
+    /// launched apps) so that their accounting can be attributed to that app.
+    fn refresh_app_cgroups(&mut self) {
+        let owners: HashMap<libc::pid_t, &Option<String>> = self
+            .apps
+            .values()
+            .flat_map(|app| app.processes.iter().map(move |pid| (*pid, &app.id)))
+            .collect();
+
+        let mut app_cgroups: HashMap<Option<String>, Vec<String>> = HashMap::new();
+
+        // System Processes share their cgroups with all kinds of things, so don't even try
+        for app in self
+            .apps
+            .values()
+            .filter(|app| app.id.is_some() && app.is_running())
+        {
+            let mut candidates: Vec<&str> = app
+                .processes_iter(self)
+                .filter_map(|process| process.data.cgroup_path.as_deref())
+                .filter(|cgroup| self.cgroup_stats.contains_key(*cgroup))
+                .collect();
+            candidates.sort_unstable();
+            candidates.dedup();
+
+            let cgroups = candidates
+                .iter()
+                // a cgroup's accounting includes its descendants, so only the outermost cgroups are needed
+                .filter(|cgroup| {
+                    !candidates.iter().any(|other| {
+                        cgroup
+                            .strip_prefix(other)
+                            .is_some_and(|rest| rest.starts_with('/'))
+                    })
+                })
+                .filter(|cgroup| {
+                    self.processes
+                        .values()
+                        .filter(|process| process.in_cgroup(cgroup))
+                        .all(|process| owners.get(&process.data.pid) == Some(&&app.id))
+                })
+                .map(|cgroup| cgroup.to_string())
+                .collect();
+
+            app_cgroups.insert(app.id.clone(), cgroups);
+        }
+
+        for app in self.apps.values_mut() {
+            let cgroups = app_cgroups.remove(&app.id).unwrap_or_default();
+
+            // keep the I/O of dead processes in cgroups that the app has lost, either because the cgroup has been
+            // removed in the meantime or because the app isn't its sole owner anymore. Their processes have been
+            // skipped when collecting the I/O of dead processes, while the remaining processes are accounted for
+            // individually from now on.
+            for lost_cgroup in app.cgroups.iter().filter(|cgroup| {
+                !cgroups.iter().any(|new_cgroup| {
+                    cgroup
+                        .strip_prefix(new_cgroup.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+                })
+            }) {
+                let Some(stats) = self.cgroup_stats_last.get(lost_cgroup) else {
+                    continue;
+                };
+
+                let (read_alive, write_alive) = app
+                    .processes
+                    .iter()
+                    .filter_map(|pid| self.processes.get(pid))
+                    .filter(|process| process.in_cgroup(lost_cgroup))
+                    .fold((0_u64, 0_u64), |(read, write), process| {
+                        (
+                            read.saturating_add(process.data.read_bytes.unwrap_or_default()),
+                            write.saturating_add(process.data.write_bytes.unwrap_or_default()),
+                        )
+                    });
+
+                app.read_bytes_from_dead_processes =
+                    app.read_bytes_from_dead_processes.saturating_add(
+                        stats
+                            .read_bytes
+                            .unwrap_or_default()
+                            .saturating_sub(read_alive),
+                    );
+                app.write_bytes_from_dead_processes =
+                    app.write_bytes_from_dead_processes.saturating_add(
+                        stats
+                            .write_bytes
+                            .unwrap_or_default()
+                            .saturating_sub(write_alive),
+                    );
+            }
+
+            app.cgroups = cgroups;
+        }
     }
 }
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use process_data::Containerization;
+
+use super::{
+    app::AppsContext,
+    process::{Process, ProcessAction},
+};
+
+/// Represents a container (e.g. of Docker or Podman) that has at least one running process. Its resource usage is
+/// taken from the kernel's accounting of the container's cgroup whenever it's available.
+#[derive(Debug, Clone)]
+pub struct Container {
+    processes: Vec<libc::pid_t>,
+    /// cgroups v2 path of the container
+    pub cgroup: String,
+    pub containerization: Containerization,
+}
+
+impl Container {
+    pub fn new(cgroup: String, containerization: Containerization) -> Self {
+        Self {
+            processes: Vec::new(),
+            cgroup,
+            containerization,
+        }
+    }
+
+    /// Returns the name or ID of the container, falling back to the name of its cgroup if neither is known.
+    pub fn display_name(&self) -> String {
+        self.containerization
+            .name()
+            .map(str::to_string)
+            .or_else(|| self.cgroup.rsplit('/').next().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    pub fn add_process(&mut self, pid: libc::pid_t) {
+        self.processes.push(pid);
+    }
+
+    pub fn clear_processes(&mut self) {
+        self.processes.clear();
+    }
+
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        !self.processes.is_empty()
+    }
+
+    pub fn running_processes(&self) -> usize {
+        self.processes.len()
+    }
+
+    pub fn processes_iter<'a>(&'a self, apps: &'a AppsContext) -> impl Iterator<Item = &Process> {
+        apps.processes_iter()
+            .filter(move |process| self.processes.contains(&process.data.pid))
+    }
+
+    #[must_use]
+    pub fn cpu_time_ratio(&self, apps: &AppsContext) -> f32 {
+        apps.cgroups_cpu_time_ratio(
+            std::slice::from_ref(&self.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn memory_usage(&self, apps: &AppsContext) -> usize {
+        apps.cgroups_memory_usage(
+            std::slice::from_ref(&self.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn read_speed(&self, apps: &AppsContext) -> f64 {
+        apps.cgroups_read_speed(
+            std::slice::from_ref(&self.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn read_total(&self, apps: &AppsContext) -> u64 {
+        apps.cgroups_read_total(
+            std::slice::from_ref(&self.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn write_speed(&self, apps: &AppsContext) -> f64 {
+        apps.cgroups_write_speed(
+            std::slice::from_ref(&self.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn write_total(&self, apps: &AppsContext) -> u64 {
+        apps.cgroups_write_total(
+            std::slice::from_ref(&self.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    pub fn execute_process_action(
+        &self,
+        apps: &AppsContext,
+        action: ProcessAction,
+    ) -> Vec<Result<()>> {
+        self.processes_iter(apps)
+            .map(|process| process.execute_process_action(action))
+            .collect()
+    }
+}
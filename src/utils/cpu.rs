@@ -2,6 +2,7 @@ use anyhow::{anyhow, bail, Context, Result};
 use glob::glob;
 use lazy_regex::{lazy_regex, Lazy, Regex};
 use log::{debug, warn};
+use process_data::pressure::{Pressure, PressureResource};
 use std::{
     path::{Path, PathBuf},
     sync::LazyLock,
@@ -82,6 +83,7 @@ pub struct CpuData {
     pub new_thread_usages: Vec<(u64, u64)>,
     pub temperature: Result<f32, anyhow::Error>,
     pub frequencies: Vec<Option<u64>>,
+    pub pressure: Option<Pressure>,
 }
 
 impl CpuData {
@@ -106,6 +108,7 @@ impl CpuData {
             new_thread_usages,
             temperature,
             frequencies,
+            pressure: Pressure::system(PressureResource::Cpu).ok(),
         }
     }
 }
@@ -1,5 +1,6 @@
 mod amd;
 mod intel;
+#[cfg(feature = "nvidia")]
 mod nvidia;
 mod other;
 
@@ -19,12 +20,15 @@ use crate::{
     utils::{pci::Device, read_uevent},
 };
 
-use self::{amd::AmdGpu, intel::IntelGpu, nvidia::NvidiaGpu, other::OtherGpu};
+#[cfg(feature = "nvidia")]
+use self::nvidia::NvidiaGpu;
+use self::{amd::AmdGpu, intel::IntelGpu, other::OtherGpu};
 
 use super::pci::Vendor;
 
 pub const VID_AMD: u16 = 4098;
 pub const VID_INTEL: u16 = 32902;
+#[cfg(feature = "nvidia")]
 pub const VID_NVIDIA: u16 = 4318;
 
 #[derive(Debug)]
@@ -83,7 +87,10 @@ impl GpuData {
         let power_cap = gpu.power_cap().ok();
         let power_cap_max = gpu.power_cap_max().ok();
 
+        #[cfg(feature = "nvidia")]
         let nvidia = matches!(gpu, Gpu::Nvidia(_));
+        #[cfg(not(feature = "nvidia"))]
+        let nvidia = false;
 
         Self {
             pci_slot,
@@ -106,6 +113,7 @@ impl GpuData {
 #[derive(Debug, Clone)]
 pub enum Gpu {
     Amd(AmdGpu),
+    #[cfg(feature = "nvidia")]
     Nvidia(NvidiaGpu),
     Intel(IntelGpu),
     Other(OtherGpu),
@@ -278,6 +286,20 @@ impl Gpu {
 
         let path = path.as_ref().to_path_buf();
 
+        // without NVML support, NVIDIA GPUs are treated like any other GPU and use the generic DRM interfaces
+        #[cfg(feature = "nvidia")]
+        if vid == VID_NVIDIA || driver == "nvidia" {
+            let gpu = Gpu::Nvidia(NvidiaGpu::new(
+                device,
+                pci_slot,
+                driver,
+                path,
+                hwmon_vec.first().cloned(),
+            ));
+            Self::log_found_gpu(&gpu, vid, pid, "NVIDIA");
+            return Ok(gpu);
+        }
+
         let (gpu, gpu_category) = if vid == VID_AMD || driver == "amdgpu" {
             (
                 Gpu::Amd(AmdGpu::new(
@@ -300,17 +322,6 @@ impl Gpu {
                 )),
                 "Intel",
             )
-        } else if vid == VID_NVIDIA || driver == "nvidia" {
-            (
-                Gpu::Nvidia(NvidiaGpu::new(
-                    device,
-                    pci_slot,
-                    driver,
-                    path,
-                    hwmon_vec.first().cloned(),
-                )),
-                "NVIDIA",
-            )
         } else {
             (
                 Gpu::Other(OtherGpu::new(
@@ -324,18 +335,23 @@ impl Gpu {
             )
         };
 
+        Self::log_found_gpu(&gpu, vid, pid, gpu_category);
+
+        Ok(gpu)
+    }
+
+    fn log_found_gpu(gpu: &Gpu, vid: u16, pid: u16, gpu_category: &str) {
         info!(
             "Found GPU \"{}\" (PCI slot: {} · PCI ID: {vid:x}:{pid:x} · Category: {gpu_category})",
             gpu.name().unwrap_or("<unknown name>".into()),
             gpu.pci_slot(),
         );
-
-        Ok(gpu)
     }
 
     pub fn get_vendor(&self) -> Result<&'static Vendor> {
         Ok(match self {
             Gpu::Amd(gpu) => gpu.device(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.device(),
             Gpu::Intel(gpu) => gpu.device(),
             Gpu::Other(gpu) => gpu.device(),
@@ -347,6 +363,7 @@ impl Gpu {
     pub fn pci_slot(&self) -> PciSlot {
         match self {
             Gpu::Amd(gpu) => gpu.pci_slot(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.pci_slot(),
             Gpu::Intel(gpu) => gpu.pci_slot(),
             Gpu::Other(gpu) => gpu.pci_slot(),
@@ -356,6 +373,7 @@ impl Gpu {
     pub fn driver(&self) -> String {
         match self {
             Gpu::Amd(gpu) => gpu.driver(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.driver(),
             Gpu::Intel(gpu) => gpu.driver(),
             Gpu::Other(gpu) => gpu.driver(),
@@ -365,6 +383,7 @@ impl Gpu {
     pub fn name(&self) -> Result<String> {
         match self {
             Gpu::Amd(gpu) => gpu.name(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.name(),
             Gpu::Intel(gpu) => gpu.name(),
             Gpu::Other(gpu) => gpu.name(),
@@ -374,6 +393,7 @@ impl Gpu {
     pub fn usage(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.usage(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.usage(),
             Gpu::Intel(gpu) => gpu.usage(),
             Gpu::Other(gpu) => gpu.usage(),
@@ -383,6 +403,7 @@ impl Gpu {
     pub fn encode_usage(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.encode_usage(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.encode_usage(),
             Gpu::Intel(gpu) => gpu.encode_usage(),
             Gpu::Other(gpu) => gpu.encode_usage(),
@@ -392,6 +413,7 @@ impl Gpu {
     pub fn decode_usage(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.decode_usage(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.decode_usage(),
             Gpu::Intel(gpu) => gpu.decode_usage(),
             Gpu::Other(gpu) => gpu.decode_usage(),
@@ -401,6 +423,7 @@ impl Gpu {
     pub fn combined_media_engine(&self) -> Result<bool> {
         match self {
             Gpu::Amd(gpu) => gpu.combined_media_engine(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.combined_media_engine(),
             Gpu::Intel(gpu) => gpu.combined_media_engine(),
             Gpu::Other(gpu) => gpu.combined_media_engine(),
@@ -410,6 +433,7 @@ impl Gpu {
     pub fn used_vram(&self) -> Result<usize> {
         match self {
             Gpu::Amd(gpu) => gpu.used_vram(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.used_vram(),
             Gpu::Intel(gpu) => gpu.used_vram(),
             Gpu::Other(gpu) => gpu.used_vram(),
@@ -419,6 +443,7 @@ impl Gpu {
     pub fn total_vram(&self) -> Result<usize> {
         match self {
             Gpu::Amd(gpu) => gpu.total_vram(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.total_vram(),
             Gpu::Intel(gpu) => gpu.total_vram(),
             Gpu::Other(gpu) => gpu.total_vram(),
@@ -428,6 +453,7 @@ impl Gpu {
     pub fn temperature(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.temperature(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.temperature(),
             Gpu::Intel(gpu) => gpu.temperature(),
             Gpu::Other(gpu) => gpu.temperature(),
@@ -437,6 +463,7 @@ impl Gpu {
     pub fn power_usage(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.power_usage(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.power_usage(),
             Gpu::Intel(gpu) => gpu.power_usage(),
             Gpu::Other(gpu) => gpu.power_usage(),
@@ -446,6 +473,7 @@ impl Gpu {
     pub fn core_frequency(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.core_frequency(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.core_frequency(),
             Gpu::Intel(gpu) => gpu.core_frequency(),
             Gpu::Other(gpu) => gpu.core_frequency(),
@@ -455,6 +483,7 @@ impl Gpu {
     pub fn vram_frequency(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.vram_frequency(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.vram_frequency(),
             Gpu::Intel(gpu) => gpu.vram_frequency(),
             Gpu::Other(gpu) => gpu.vram_frequency(),
@@ -464,6 +493,7 @@ impl Gpu {
     pub fn power_cap(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.power_cap(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.power_cap(),
             Gpu::Intel(gpu) => gpu.power_cap(),
             Gpu::Other(gpu) => gpu.power_cap(),
@@ -473,6 +503,7 @@ impl Gpu {
     pub fn power_cap_max(&self) -> Result<f64> {
         match self {
             Gpu::Amd(gpu) => gpu.power_cap_max(),
+            #[cfg(feature = "nvidia")]
             Gpu::Nvidia(gpu) => gpu.power_cap_max(),
             Gpu::Intel(gpu) => gpu.power_cap_max(),
             Gpu::Other(gpu) => gpu.power_cap_max(),
@@ -3,6 +3,7 @@ use std::process::Command;
 use anyhow::{bail, Context, Result};
 use lazy_regex::{lazy_regex, Lazy, Regex};
 use log::debug;
+use process_data::pressure::{Pressure, PressureResource};
 
 use super::{FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK};
 
@@ -50,6 +51,7 @@ pub struct MemoryData {
     pub available_mem: usize,
     pub total_swap: usize,
     pub free_swap: usize,
+    pub pressure: Option<Pressure>,
 }
 
 impl MemoryData {
@@ -126,6 +128,7 @@ impl MemoryData {
             available_mem,
             total_swap,
             free_swap,
+            pressure: Pressure::system(PressureResource::Memory).ok(),
         })
     }
 }
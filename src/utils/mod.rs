@@ -8,6 +8,7 @@ use process_data::unix_as_millis;
 
 pub mod app;
 pub mod battery;
+pub mod container;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
@@ -16,13 +17,15 @@ pub mod network;
 pub mod npu;
 pub mod pci;
 pub mod process;
+pub mod process_log;
 pub mod settings;
+pub mod unit;
 pub mod units;
 
 const FLATPAK_SPAWN: &str = "/usr/bin/flatpak-spawn";
 
-static BOOT_TIMESTAMP: LazyLock<Option<i64>> = LazyLock::new(|| {
-    let unix_timestamp = (unix_as_millis() / 1000) as i64;
+static BOOT_TIMESTAMP_MILLIS: LazyLock<Option<u64>> = LazyLock::new(|| {
+    let unix_timestamp = unix_as_millis();
     std::fs::read_to_string("/proc/uptime")
         .context("unable to read /proc/uptime")
         .and_then(|procfs| {
@@ -37,7 +40,7 @@ static BOOT_TIMESTAMP: LazyLock<Option<i64>> = LazyLock::new(|| {
                 .parse::<f64>()
                 .context("unable to parse /proc/uptime")
         })
-        .map(|uptime_secs| unix_timestamp - uptime_secs as i64)
+        .map(|uptime_secs| unix_timestamp.saturating_sub((uptime_secs * 1000.0) as u64))
         .ok()
 });
 
@@ -77,13 +80,19 @@ pub fn flatpak_app_path() -> Result<String> {
 }
 
 pub fn boot_time() -> Result<DateTime> {
-    BOOT_TIMESTAMP
+    boot_timestamp_millis()
         .context("couldn't get boot timestamp")
         .and_then(|timestamp| {
-            DateTime::from_unix_local(timestamp).context("unable to get glib::DateTime")
+            DateTime::from_unix_local((timestamp / 1000) as i64)
+                .context("unable to get glib::DateTime")
         })
 }
 
+/// Returns when the system has been booted in milliseconds since the Unix epoch.
+pub fn boot_timestamp_millis() -> Option<u64> {
+    *BOOT_TIMESTAMP_MILLIS
+}
+
 pub fn read_uevent_contents<S: AsRef<str>>(contents: S) -> Result<HashMap<String, String>> {
     contents
         .as_ref()
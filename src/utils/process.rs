@@ -1,15 +1,33 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use config::LIBEXECDIR;
-use log::{debug, error, info};
-use process_data::{pci_slot::PciSlot, GpuUsageStats, Niceness, ProcessData};
+use log::{debug, error, info, warn};
+use process_data::{
+    cgroup::CgroupStats,
+    delta::ProcessDataUpdate,
+    environ::EnvironmentVariable,
+    memory_maps::MemoryMapping,
+    open_files::OpenFile,
+    pci_slot::PciSlot,
+    proc_connector::ProcessEvents,
+    protocol::{
+        Handshake, FEATURE_CGROUP_STATS, FEATURE_DELTA, FEATURE_MEMORY_MAPS, FEATURE_OPEN_FILES,
+        FEATURE_PROCESS_EVENTS, FEATURE_SELECT_FIELDS, PROTOCOL_VERSION, REQUEST_CGROUP_STATS,
+        REQUEST_DELTA, REQUEST_FULL, REQUEST_HANDSHAKE, REQUEST_MEMORY_MAPS, REQUEST_OPEN_FILES,
+        REQUEST_PROCESS_EVENTS, REQUEST_SELECT_FIELDS,
+    },
+    scanner::ProcessFields,
+    signal::signal_name,
+    GpuEngineClass, GpuUsageStats, IoPriority, Niceness, ProcessData, SchedulingPolicy, ThreadData,
+};
+use serde::{de::DeserializeOwned, Serialize};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     ffi::{OsStr, OsString},
+    fmt::Display,
     io::{Read, Write},
-    process::{ChildStdin, ChildStdout, Command, Stdio},
+    process::{ChildStdin, ChildStdout, Command, Output, Stdio},
     sync::{LazyLock, Mutex},
 };
-use strum_macros::Display;
 
 use gtk::{
     gio::{Icon, ThemedIcon},
@@ -19,7 +37,9 @@ use gtk::{
 use crate::config;
 
 use super::{
-    boot_time, FiniteOr, FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK, NUM_CPUS, TICK_RATE,
+    boot_time, boot_timestamp_millis,
+    settings::{MemoryMetric, SETTINGS},
+    FiniteOr, FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK, NUM_CPUS, TICK_RATE,
 };
 
 static OTHER_PROCESS: LazyLock<Mutex<(ChildStdin, ChildStdout)>> = LazyLock::new(|| {
@@ -57,6 +77,49 @@ static OTHER_PROCESS: LazyLock<Mutex<(ChildStdin, ChildStdout)>> = LazyLock::new
     Mutex::new((stdin, stdout))
 });
 
+static OTHER_PROCESS_PROTOCOL: LazyLock<OtherProcessProtocol> = LazyLock::new(|| {
+    let output = match Process::request_other_process(&[REQUEST_HANDSHAKE]) {
+        Ok(output) => output,
+        Err(e) => {
+            warn!("Unable to perform handshake with resources-processes, assuming legacy protocol: {e}");
+            return OtherProcessProtocol::Legacy;
+        }
+    };
+
+    // versions of resources-processes that predate the handshake answer every request with all process data, which
+    // can't be decoded as a handshake
+    match rmp_serde::from_slice::<Handshake>(&output) {
+        Ok(handshake) if handshake.is_compatible() => {
+            debug!(
+                "resources-processes speaks protocol version {} with features {:#x}",
+                handshake.version, handshake.features
+            );
+            OtherProcessProtocol::Versioned(handshake)
+        }
+        Ok(handshake) => {
+            error!(
+                "resources-processes speaks protocol version {} but Resources expects version {PROTOCOL_VERSION}, make sure both are from the same release",
+                handshake.version
+            );
+            OtherProcessProtocol::Incompatible(handshake)
+        }
+        Err(_) => {
+            warn!("resources-processes doesn't support the handshake, it might be outdated");
+            OtherProcessProtocol::Legacy
+        }
+    }
+});
+
+/// How we're able to talk to `resources-processes`, determined by a handshake the first time we need to talk to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OtherProcessProtocol {
+    /// `resources-processes` predates the handshake, so only `REQUEST_FULL` is available
+    Legacy,
+    Versioned(Handshake),
+    /// `resources-processes` speaks a protocol version we can't decode
+    Incompatible(Handshake),
+}
+
 /// Represents a process that can be found within procfs.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Process {
@@ -69,19 +132,46 @@ pub struct Process {
     pub read_bytes_last: Option<u64>,
     pub write_bytes_last: Option<u64>,
     pub gpu_usage_stats_last: BTreeMap<PciSlot, GpuUsageStats>,
+    /// Key: TID of the thread
+    pub thread_cpu_times_last: HashMap<libc::pid_t, u64>,
     pub display_name: String,
+    /// Highest memory usage that has been seen so far, see `memory_usage()`
+    pub memory_usage_peak: usize,
 }
 
 // TODO: Better name?
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProcessAction {
     TERM,
     STOP,
     KILL,
     CONT,
+    /// Sends an arbitrary signal, e.g. `SIGHUP` to make a daemon reload its configuration
+    Signal(libc::c_int),
+}
+
+impl Display for ProcessAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessAction::TERM => write!(f, "TERM"),
+            ProcessAction::STOP => write!(f, "STOP"),
+            ProcessAction::KILL => write!(f, "KILL"),
+            ProcessAction::CONT => write!(f, "CONT"),
+            ProcessAction::Signal(signal) => write!(f, "{}", signal_name(*signal)),
+        }
+    }
 }
 
 impl Process {
+    /// Returns the protocol version `resources-processes` speaks if it's too different from ours to talk to it at all,
+    /// e.g. because it's from another release.
+    pub fn incompatible_protocol_version() -> Option<u32> {
+        match *OTHER_PROCESS_PROTOCOL {
+            OtherProcessProtocol::Incompatible(handshake) => Some(handshake.version),
+            _ => None,
+        }
+    }
+
     /// Returns a `Vec` containing all currently running processes.
     ///
     /// # Errors
@@ -89,24 +179,189 @@ impl Process {
     /// Will return `Err` if there are problems traversing and
     /// parsing procfs
     pub fn all_data() -> Result<Vec<ProcessData>> {
-        let output = {
-            let mut process = OTHER_PROCESS.lock().unwrap();
-            let _ = process.0.write_all(b"\n");
-            let _ = process.0.flush();
+        if let OtherProcessProtocol::Incompatible(handshake) = *OTHER_PROCESS_PROTOCOL {
+            bail!(
+                "resources-processes speaks protocol version {} but Resources expects version {PROTOCOL_VERSION}",
+                handshake.version
+            );
+        }
+
+        let output = Self::request_other_process(&[REQUEST_FULL])?;
+
+        rmp_serde::from_slice(&output).with_context(|| {
+            if *OTHER_PROCESS_PROTOCOL == OtherProcessProtocol::Legacy {
+                "unable to decode process data, resources-processes is probably outdated"
+            } else {
+                "unable to decode process data"
+            }
+        })
+    }
+
+    /// Returns the changes to all currently running processes since the update with the sequence number
+    /// `base_sequence`. If `resources-processes` doesn't know about that update (e.g. because `base_sequence` is 0)
+    /// or doesn't support deltas at all, a full update is returned instead.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there are problems traversing and
+    /// parsing procfs
+    pub fn all_data_update(base_sequence: u64) -> Result<ProcessDataUpdate> {
+        let supports_delta = match *OTHER_PROCESS_PROTOCOL {
+            OtherProcessProtocol::Versioned(handshake) => handshake.supports(FEATURE_DELTA),
+            _ => false,
+        };
+
+        if !supports_delta {
+            return Self::all_data().map(|processes| ProcessDataUpdate::Full {
+                sequence: 0,
+                processes,
+            });
+        }
+
+        let mut request = vec![REQUEST_DELTA];
+        request.extend_from_slice(&base_sequence.to_le_bytes());
+
+        let output = Self::request_other_process(&request)?;
 
-            let mut len_bytes = [0_u8; (usize::BITS / 8) as usize];
+        rmp_serde::from_slice(&output).context("unable to decode process data update")
+    }
+
+    /// Returns the files, sockets, pipes and devices the process with the PID `pid` holds open.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `resources-processes` doesn't support this query or if the open files of the process
+    /// couldn't be read, e.g. because it belongs to another user
+    pub fn open_files(pid: libc::pid_t) -> Result<Vec<OpenFile>> {
+        Self::query_other_process(REQUEST_OPEN_FILES, FEATURE_OPEN_FILES, &pid.to_le_bytes())
+    }
+
+    /// Returns the memory mappings of the process with the PID `pid`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `resources-processes` doesn't support this query or if the memory mappings of the process
+    /// couldn't be read, e.g. because it belongs to another user
+    pub fn memory_maps(pid: libc::pid_t) -> Result<Vec<MemoryMapping>> {
+        Self::query_other_process(REQUEST_MEMORY_MAPS, FEATURE_MEMORY_MAPS, &pid.to_le_bytes())
+    }
+
+    /// Returns the environment variables of the process with the PID `pid`. If they can't be read due to missing
+    /// permissions, this asks for authentication using pkexec.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the environment couldn't be read even with elevated privileges or if the authentication
+    /// has been cancelled
+    pub fn environ(pid: libc::pid_t) -> Result<Vec<EnvironmentVariable>> {
+        let environ_path = if *IS_FLATPAK {
+            format!(
+                "{}/libexec/resources/resources-environ",
+                FLATPAK_APP_PATH.as_str()
+            )
+        } else {
+            format!("{LIBEXECDIR}/resources-environ")
+        };
+
+        let output = Self::maybe_pkexec_command_output(environ_path, [pid.to_string()])?;
+
+        let status_code = output.status.code().context("no status code?")?;
+        if status_code != 0 {
+            bail!("non-zero return code: {status_code}");
+        }
+
+        Ok(EnvironmentVariable::parse(output.stdout))
+    }
+
+    /// Returns the statistics of the given cgroups, keyed by their cgroups v2 path. Cgroups that don't exist (anymore)
+    /// are left out.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `resources-processes` doesn't support this query
+    pub fn cgroup_stats(cgroups: &[String]) -> Result<HashMap<String, CgroupStats>> {
+        Self::query_other_process(
+            REQUEST_CGROUP_STATS,
+            FEATURE_CGROUP_STATS,
+            &Self::arguments_payload(cgroups)?,
+        )
+    }
+
+    /// Tells `resources-processes` which of the optional parts of the process data it should collect from now on.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `resources-processes` doesn't support selecting fields, it keeps collecting everything in
+    /// that case
+    pub fn select_fields(fields: ProcessFields) -> Result<()> {
+        Self::query_other_process(
+            REQUEST_SELECT_FIELDS,
+            FEATURE_SELECT_FIELDS,
+            &Self::arguments_payload(&fields)?,
+        )
+    }
+
+    /// Returns the processes that have been started or have exited since the last call. The first call only
+    /// subscribes to these events and therefore usually returns none.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `resources-processes` doesn't support this query or lacks the privileges to subscribe to
+    /// process events
+    pub fn process_events() -> Result<ProcessEvents> {
+        Self::query_other_process(REQUEST_PROCESS_EVENTS, FEATURE_PROCESS_EVENTS, &[])
+    }
 
-            process.1.read_exact(&mut len_bytes)?;
+    /// Encodes `arguments` the way requests that need more than a PID expect them.
+    fn arguments_payload<T: Serialize + ?Sized>(arguments: &T) -> Result<Vec<u8>> {
+        let arguments = rmp_serde::to_vec(arguments)?;
 
-            let len = usize::from_le_bytes(len_bytes);
+        let mut payload = (arguments.len() as u32).to_le_bytes().to_vec();
+        payload.extend_from_slice(&arguments);
 
-            let mut output_bytes = vec![0; len];
-            process.1.read_exact(&mut output_bytes)?;
+        Ok(payload)
+    }
 
-            output_bytes
+    /// Performs an on-demand query, `payload` is sent right after the request byte.
+    fn query_other_process<T: DeserializeOwned>(
+        request_byte: u8,
+        feature: u64,
+        payload: &[u8],
+    ) -> Result<T> {
+        let supported = match *OTHER_PROCESS_PROTOCOL {
+            OtherProcessProtocol::Versioned(handshake) => handshake.supports(feature),
+            _ => false,
         };
 
-        Ok(rmp_serde::from_slice(&output)?)
+        if !supported {
+            bail!("resources-processes doesn't support this query, it might be outdated");
+        }
+
+        let mut request = vec![request_byte];
+        request.extend_from_slice(payload);
+
+        let output = Self::request_other_process(&request)?;
+
+        rmp_serde::from_slice::<std::result::Result<T, String>>(&output)
+            .context("unable to decode answer of resources-processes")?
+            .map_err(|error| anyhow!(error))
+    }
+
+    fn request_other_process(request: &[u8]) -> Result<Vec<u8>> {
+        let mut process = OTHER_PROCESS.lock().unwrap();
+        let _ = process.0.write_all(request);
+        let _ = process.0.flush();
+
+        let mut len_bytes = [0_u8; (u64::BITS / 8) as usize];
+
+        process.1.read_exact(&mut len_bytes)?;
+
+        let len = u64::from_le_bytes(len_bytes) as usize;
+
+        let mut output_bytes = vec![0; len];
+        process.1.read_exact(&mut output_bytes)?;
+
+        Ok(output_bytes)
     }
 
     pub fn from_process_data(process_data: ProcessData) -> Self {
@@ -152,7 +407,9 @@ impl Process {
             read_bytes_last,
             write_bytes_last,
             gpu_usage_stats_last: Default::default(),
+            thread_cpu_times_last: Default::default(),
             display_name,
+            memory_usage_peak: 0,
         }
     }
 
@@ -161,12 +418,24 @@ impl Process {
         command: S,
         args: I,
     ) -> Result<i32> {
+        Self::maybe_pkexec_command_output(command, args)?
+            .status
+            .code()
+            .context("no status code?")
+    }
+
+    /// Tries to run a command unprivileged and then privileged if permissions were missing, returning the output of
+    /// the last run
+    fn maybe_pkexec_command_output<S: AsRef<OsStr>, I: IntoIterator<Item = S>>(
+        command: S,
+        args: I,
+    ) -> Result<Output> {
         let args = args
             .into_iter()
             .map(|s| s.as_ref().to_os_string())
             .collect::<Vec<_>>();
 
-        let status_code = if *IS_FLATPAK {
+        let output = if *IS_FLATPAK {
             debug!(
                 "Executing command: {} --host {} {}",
                 FLATPAK_SPAWN,
@@ -179,9 +448,6 @@ impl Process {
                 .arg(command.as_ref())
                 .args(args.clone())
                 .output()?
-                .status
-                .code()
-                .context("no status code?")?
         } else {
             debug!(
                 "Executing command: {} {}",
@@ -189,16 +455,13 @@ impl Process {
                 args.join(&OsString::from(" ")).to_string_lossy()
             );
 
-            Command::new(command.as_ref())
-                .args(args.clone())
-                .output()?
-                .status
-                .code()
-                .context("no status code?")?
+            Command::new(command.as_ref()).args(args.clone()).output()?
         };
 
+        let status_code = output.status.code().context("no status code?")?;
+
         if status_code == libc::EPERM || status_code == libc::EACCES {
-            let pkexec_status_code = if *IS_FLATPAK {
+            let pkexec_output = if *IS_FLATPAK {
                 debug!(
                     "Received EPERM, executing command: {} --host pkexec --disable-internal-agent {} {}", 
                     FLATPAK_SPAWN,
@@ -210,9 +473,6 @@ impl Process {
                     .arg(command)
                     .args(args)
                     .output()?
-                    .status
-                    .code()
-                    .context("no status code?")?
             } else {
                 debug!(
                     "Received EPERM or EACCES, executing command: pkexec --disable-internal-agent {} {}", 
@@ -224,21 +484,22 @@ impl Process {
                     .arg(command)
                     .args(args)
                     .output()?
-                    .status
-                    .code()
-                    .context("no status code?")?
             };
 
-            Ok(pkexec_status_code)
+            Ok(pkexec_output)
         } else {
-            Ok(status_code)
+            Ok(output)
         }
     }
 
+    /// Changes the niceness and the affinity of this process and all of its threads. If `scheduling` is `Some`, their
+    /// scheduling policy and real-time priority are changed as well, the same goes for `io_priority`.
     pub fn adjust<I: IntoIterator<Item = bool>>(
         &self,
         niceness: Niceness,
         affinity: I,
+        scheduling: Option<(SchedulingPolicy, u32)>,
+        io_priority: Option<IoPriority>,
     ) -> Result<()> {
         let adjust_path = if *IS_FLATPAK {
             format!(
@@ -254,14 +515,36 @@ impl Process {
             .map(|b| if b { '1' } else { '0' })
             .collect::<String>();
 
-        let result = Self::maybe_pkexec_command(
-            adjust_path,
-            [
-                self.data.pid.to_string(),
-                niceness.to_string(),
-                adjust_string,
-            ],
-        );
+        let mut args = vec![
+            self.data.pid.to_string(),
+            niceness.to_string(),
+            adjust_string,
+        ];
+
+        if let Some((policy, priority)) = scheduling {
+            let raw_policy = policy
+                .as_raw()
+                .with_context(|| format!("scheduling policy {policy:?} can't be set"))?;
+
+            // only real-time policies have a priority, the kernel refuses anything else than 0 for the others
+            let priority = if policy.is_realtime() {
+                priority.clamp(1, 99)
+            } else {
+                0
+            };
+
+            args.push(raw_policy.to_string());
+            args.push(priority.to_string());
+        } else if io_priority.is_some() {
+            args.push(String::from("-"));
+            args.push(String::from("-"));
+        }
+
+        if let Some(io_priority) = io_priority {
+            args.push(io_priority.as_raw().to_string());
+        }
+
+        let result = Self::maybe_pkexec_command(adjust_path, args);
 
         if let Ok(return_code) = result {
             if return_code == 0 {
@@ -296,18 +579,18 @@ impl Process {
 
         if let Ok(return_code) = result {
             if return_code == 0 || return_code == 3 {
-                info!("Successfully {action_string}ed {}", self.data.pid);
+                info!("Successfully sent {action_string} to {}", self.data.pid);
                 Ok(())
             } else {
                 error!(
-                    "Couldn't {action_string} {}, return code: {return_code}",
+                    "Couldn't send {action_string} to {}, return code: {return_code}",
                     self.data.pid
                 );
                 bail!("non-zero return code: {return_code}")
             }
         } else if let Err(err) = result {
             error!(
-                "Unknown error while trying to {action_string} {}\n{err}\n{}",
+                "Unknown error while trying to send {action_string} to {}\n{err}\n{}",
                 self.data.pid,
                 err.backtrace()
             );
@@ -317,6 +600,27 @@ impl Process {
         }
     }
 
+    /// Returns whether this process runs in the cgroup with the given cgroups v2 path or in one of its descendants.
+    pub fn in_cgroup(&self, cgroup: &str) -> bool {
+        self.data.cgroup_path.as_deref().is_some_and(|cgroup_path| {
+            cgroup_path == cgroup
+                || cgroup_path
+                    .strip_prefix(cgroup)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
+    /// Returns the memory usage according to the memory metric chosen in the settings. Falls back to the resident
+    /// memory if `smaps_rollup` couldn't be read for this process.
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        match (SETTINGS.memory_metric(), self.data.smaps_rollup) {
+            (MemoryMetric::Proportional, Some(smaps_rollup)) => smaps_rollup.pss,
+            (MemoryMetric::Unique, Some(smaps_rollup)) => smaps_rollup.uss,
+            _ => self.data.memory_usage,
+        }
+    }
+
     #[must_use]
     pub fn cpu_time_ratio(&self) -> f32 {
         if self.cpu_time_last == 0 {
@@ -338,6 +642,24 @@ impl Process {
         }
     }
 
+    #[must_use]
+    pub fn thread_cpu_time_ratio(&self, thread: &ThreadData) -> f32 {
+        if let Some(cpu_time_last) = self.thread_cpu_times_last.get(&thread.tid) {
+            let delta_cpu_time = (thread.user_cpu_time.saturating_add(thread.system_cpu_time))
+                .saturating_sub(*cpu_time_last) as f32
+                * 1000.0;
+            let delta_time = self.data.timestamp.saturating_sub(self.timestamp_last);
+
+            (delta_cpu_time
+                / (delta_time
+                    .saturating_mul(*TICK_RATE as u64)
+                    .saturating_mul(*NUM_CPUS as u64)) as f32)
+                .finite_or_default()
+        } else {
+            0.0
+        }
+    }
+
     #[must_use]
     pub fn read_speed(&self) -> Option<f64> {
         if let (Some(read_bytes), Some(read_bytes_last)) =
@@ -372,79 +694,45 @@ impl Process {
         }
     }
 
+    /// Returns the fraction of time every engine of every GPU has been busy for this process since the last refresh.
+    ///
+    /// Key: PCI slot of the GPU, Value: Key is the name of the engine, value is its usage from 0.0 to 1.0
     #[must_use]
-    pub fn gpu_usage(&self) -> f32 {
-        let mut returned_gpu_usage = 0.0;
-        for (gpu, usage) in &self.data.gpu_usage_stats {
-            if let Some(old_usage) = self.gpu_usage_stats_last.get(gpu) {
-                let this_gpu_usage = if usage.nvidia {
-                    usage.gfx as f32 / 100.0
-                } else if old_usage.gfx == 0 {
-                    0.0
-                } else {
-                    ((usage.gfx.saturating_sub(old_usage.gfx) as f32)
-                        / (self.data.timestamp.saturating_sub(self.timestamp_last) as f32)
-                            .finite_or_default())
-                        / 1_000_000.0
-                };
-
-                if this_gpu_usage > returned_gpu_usage {
-                    returned_gpu_usage = this_gpu_usage;
-                }
-            }
-        }
+    pub fn gpu_engine_usage(&self) -> BTreeMap<PciSlot, BTreeMap<String, f32>> {
+        let elapsed_millis = self.data.timestamp.saturating_sub(self.timestamp_last);
+
+        self.data
+            .gpu_usage_stats
+            .iter()
+            .filter_map(|(gpu, usage)| {
+                self.gpu_usage_stats_last
+                    .get(gpu)
+                    .map(|old_usage| (*gpu, usage.engine_fractions(old_usage, elapsed_millis)))
+            })
+            .collect()
+    }
 
-        returned_gpu_usage
+    /// Returns the highest usage across all GPUs of the engines whose class satisfies `predicate`
+    fn gpu_class_usage<F: Fn(GpuEngineClass) -> bool>(&self, predicate: F) -> f32 {
+        self.gpu_engine_usage()
+            .values()
+            .map(|engines| engine_class_fraction(engines, &predicate))
+            .fold(0.0, f32::max)
     }
 
     #[must_use]
-    pub fn enc_usage(&self) -> f32 {
-        let mut returned_gpu_usage = 0.0;
-        for (gpu, usage) in &self.data.gpu_usage_stats {
-            if let Some(old_usage) = self.gpu_usage_stats_last.get(gpu) {
-                let this_gpu_usage = if usage.nvidia {
-                    usage.enc as f32 / 100.0
-                } else if old_usage.enc == 0 {
-                    0.0
-                } else {
-                    ((usage.enc.saturating_sub(old_usage.enc) as f32)
-                        / (self.data.timestamp.saturating_sub(self.timestamp_last) as f32)
-                            .finite_or_default())
-                        / 1_000_000.0
-                };
-
-                if this_gpu_usage > returned_gpu_usage {
-                    returned_gpu_usage = this_gpu_usage;
-                }
-            }
-        }
+    pub fn gpu_usage(&self) -> f32 {
+        self.gpu_class_usage(|class| class == GpuEngineClass::Graphics)
+    }
 
-        returned_gpu_usage
+    #[must_use]
+    pub fn enc_usage(&self) -> f32 {
+        self.gpu_class_usage(|class| class.is_encode())
     }
 
     #[must_use]
     pub fn dec_usage(&self) -> f32 {
-        let mut returned_gpu_usage = 0.0;
-        for (gpu, usage) in &self.data.gpu_usage_stats {
-            if let Some(old_usage) = self.gpu_usage_stats_last.get(gpu) {
-                let this_gpu_usage = if usage.nvidia {
-                    usage.dec as f32 / 100.0
-                } else if old_usage.dec == 0 {
-                    0.0
-                } else {
-                    ((usage.dec.saturating_sub(old_usage.dec) as f32)
-                        / (self.data.timestamp.saturating_sub(self.timestamp_last) as f32)
-                            .finite_or_default())
-                        / 1_000_000.0
-                };
-
-                if this_gpu_usage > returned_gpu_usage {
-                    returned_gpu_usage = this_gpu_usage;
-                }
-            }
-        }
-
-        returned_gpu_usage
+        self.gpu_class_usage(|class| class.is_decode())
     }
 
     #[must_use]
@@ -452,15 +740,36 @@ impl Process {
         self.data
             .gpu_usage_stats
             .values()
-            .map(|stats| stats.mem)
+            .map(GpuUsageStats::memory)
             .sum()
     }
 
+    /// Takes the current memory usage into account for `memory_usage_peak`.
+    pub fn update_memory_usage_peak(&mut self) {
+        self.memory_usage_peak = self.memory_usage_peak.max(self.memory_usage());
+    }
+
+    /// Returns the CPU time this process has consumed in user and kernel mode, in seconds.
+    #[must_use]
+    pub fn total_cpu_time(&self) -> f64 {
+        self.data
+            .user_cpu_time
+            .saturating_add(self.data.system_cpu_time) as f64
+            / *TICK_RATE as f64
+    }
+
     #[must_use]
     pub fn starttime(&self) -> f64 {
         self.data.starttime as f64 / *TICK_RATE as f64
     }
 
+    /// Returns when this process has been started in milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn start_timestamp(&self) -> Option<u64> {
+        boot_timestamp_millis()
+            .map(|boot_timestamp| boot_timestamp + (self.starttime() * 1000.0) as u64)
+    }
+
     pub fn running_since(&self) -> Result<GString> {
         boot_time()
             .and_then(|boot_time| {
@@ -480,3 +789,17 @@ impl Process {
         }
     }
 }
+
+/// Sums up the usage of the engines in `engines` whose class satisfies `predicate`, e.g. `gfx` and `compute` for
+/// graphics. Since several engines of a class can be busy at the same time, the result is capped at 1.0.
+pub fn engine_class_fraction<F: Fn(GpuEngineClass) -> bool>(
+    engines: &BTreeMap<String, f32>,
+    predicate: F,
+) -> f32 {
+    engines
+        .iter()
+        .filter(|(name, _)| predicate(GpuEngineClass::from_engine_name(name)))
+        .map(|(_, fraction)| fraction)
+        .sum::<f32>()
+        .clamp(0.0, 1.0)
+}
@@ -0,0 +1,538 @@
+//! A chronological log of the processes that have been started or have exited while Resources has been running.
+//!
+//! Starts and exits are noticed by comparing the periodic snapshots of all processes, which is always possible but
+//! misses processes that live shorter than the refresh interval and doesn't tell how a process has exited. If process
+//! events of the proc connector are available, they are used to fill in exit statuses and to log short-lived
+//! processes as well.
+
+use std::collections::{HashMap, VecDeque};
+
+use process_data::proc_connector::{ExitStatus, ProcessEventKind, ProcessEvents};
+
+/// The oldest entries are dropped once the log has grown beyond this many entries.
+pub const MAX_ENTRIES: usize = 10_000;
+
+/// Events of processes that haven't been logged after this many milliseconds are forgotten, e.g. forks of processes
+/// that have been seen in a snapshot and are thus logged without the help of events.
+const PENDING_TIMEOUT_MILLIS: u64 = 60_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessLogEventKind {
+    Started,
+    Exited {
+        /// `None` if the exit has only been noticed because the process has vanished
+        status: Option<ExitStatus>,
+        /// In milliseconds, `None` if it's unknown when the process has been started
+        lifetime: Option<u64>,
+        /// In bytes, `None` if the process has never been seen in a snapshot
+        peak_memory: Option<usize>,
+        /// In seconds, `None` if the process has never been seen in a snapshot
+        cpu_time: Option<f64>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessLogEntry {
+    /// Increases with every entry that is logged
+    pub id: u64,
+    /// When the process has been started or has exited, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub pid: libc::pid_t,
+    pub name: String,
+    /// Empty if the process has never been seen in a snapshot
+    pub commandline: String,
+    /// `None` if the process has never been seen in a snapshot
+    pub user: Option<String>,
+    /// Name of the app the process belongs to, `None` if it doesn't belong to one
+    pub app: Option<String>,
+    pub kind: ProcessLogEventKind,
+}
+
+/// What is known about a process when it's logged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoggedProcess {
+    pub pid: libc::pid_t,
+    pub name: String,
+    pub commandline: String,
+    pub user: Option<String>,
+    pub app: Option<String>,
+    /// When the process has been started, in milliseconds since the Unix epoch
+    pub started: Option<u64>,
+}
+
+/// What a process has consumed over its lifetime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessUsage {
+    /// In bytes
+    pub peak_memory: usize,
+    /// In seconds
+    pub cpu_time: f64,
+}
+
+/// What the process events have told us about a process that hasn't been logged yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PendingProcess {
+    name: Option<String>,
+    started: Option<u64>,
+    exited: Option<(u64, ExitStatus)>,
+    first_event: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct ProcessLog {
+    entries: VecDeque<ProcessLogEntry>,
+    next_id: u64,
+    /// Key: PID
+    pending: HashMap<libc::pid_t, PendingProcess>,
+    /// Processes that have been logged as exited before their exit event has arrived. Key: PID; Value: ID of the
+    /// entry and when it has been logged
+    exited_without_status: HashMap<libc::pid_t, (u64, u64)>,
+    /// IDs of the entries that have been amended by the latest call to `record_events()`
+    amended: Vec<u64>,
+}
+
+impl ProcessLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the entries that have been logged after the entry with the ID `id`, or all of them if `id` is `None`.
+    /// The entries are in the order they've been logged, which is not necessarily chronological because short-lived
+    /// processes are only logged after they've exited.
+    pub fn entries_after(&self, id: Option<u64>) -> impl Iterator<Item = &ProcessLogEntry> {
+        let skip = id.map_or(0, |id| self.entries.partition_point(|entry| entry.id <= id));
+        self.entries.iter().skip(skip)
+    }
+
+    /// Returns the ID of the oldest entry that is still in the log.
+    pub fn oldest_id(&self) -> Option<u64> {
+        self.entries.front().map(|entry| entry.id)
+    }
+
+    /// Returns the entry with the ID `id` if it's still in the log.
+    pub fn entry(&self, id: u64) -> Option<&ProcessLogEntry> {
+        let index = self.entries.partition_point(|entry| entry.id < id);
+        self.entries.get(index).filter(|entry| entry.id == id)
+    }
+
+    /// Returns the IDs of the entries that have been changed after they've been logged by the latest call to
+    /// `record_events()`, i.e. exits that have been logged before their exit status was known.
+    pub fn amended_ids(&self) -> &[u64] {
+        &self.amended
+    }
+
+    /// Remembers what has happened to processes according to the proc connector. This should be called before the
+    /// snapshot taken at the same time as the events is applied, so that exits of processes that have vanished from
+    /// that snapshot come with their exit status.
+    pub fn record_events(&mut self, events: ProcessEvents) {
+        self.amended.clear();
+
+        for event in events.events {
+            match event.kind {
+                // the PID has been reused, so any later exit belongs to the new process
+                ProcessEventKind::Fork { .. } => {
+                    self.exited_without_status.remove(&event.pid);
+                }
+                ProcessEventKind::Exit { status } => {
+                    // the process has vanished from a snapshot before its exit event arrived, so it's logged already
+                    if let Some((id, _)) = self.exited_without_status.remove(&event.pid) {
+                        self.amend_exit_status(id, status);
+                        continue;
+                    }
+                }
+                ProcessEventKind::Exec => (),
+            }
+
+            let pending = self
+                .pending
+                .entry(event.pid)
+                .or_insert_with(|| PendingProcess {
+                    first_event: event.timestamp,
+                    ..Default::default()
+                });
+
+            if let Some(comm) = event.comm {
+                pending.name = Some(comm);
+            }
+
+            match event.kind {
+                ProcessEventKind::Fork { .. } => pending.started = Some(event.timestamp),
+                ProcessEventKind::Exec => (),
+                ProcessEventKind::Exit { status } => {
+                    pending.exited = Some((event.timestamp, status));
+                }
+            }
+        }
+    }
+
+    /// Logs that `process` has appeared in a snapshot.
+    pub fn record_start(&mut self, process: LoggedProcess, now: u64) {
+        // the snapshot knows more about the process than its events, but an exit that has happened after the snapshot
+        // has been taken still needs to be remembered
+        if self
+            .pending
+            .get(&process.pid)
+            .is_some_and(|pending| pending.exited.is_none())
+        {
+            self.pending.remove(&process.pid);
+        }
+
+        let timestamp = process.started.unwrap_or(now);
+        self.push(timestamp, process, ProcessLogEventKind::Started);
+    }
+
+    /// Logs that `process` has vanished from a snapshot, using the exit status from its events if there are any.
+    pub fn record_exit(&mut self, process: LoggedProcess, usage: ProcessUsage, now: u64) {
+        let exited = self
+            .pending
+            .remove(&process.pid)
+            .and_then(|pending| pending.exited);
+
+        let timestamp = exited.map_or(now, |(timestamp, _)| timestamp);
+        let lifetime = process
+            .started
+            .map(|started| timestamp.saturating_sub(started));
+
+        // the exit event might still be on its way
+        if exited.is_none() {
+            self.exited_without_status
+                .insert(process.pid, (self.next_id, now));
+        }
+
+        self.push(
+            timestamp,
+            process,
+            ProcessLogEventKind::Exited {
+                status: exited.map(|(_, status)| status),
+                lifetime,
+                peak_memory: Some(usage.peak_memory),
+                cpu_time: Some(usage.cpu_time),
+            },
+        );
+    }
+
+    /// Logs the processes that have exited without ever being seen in a snapshot, i.e. those that have lived
+    /// shorter than the refresh interval. `is_running` tells whether a PID is part of the latest snapshot.
+    pub fn record_unseen_exits<F: Fn(libc::pid_t) -> bool>(&mut self, is_running: F, now: u64) {
+        let mut unseen: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(pid, pending)| pending.exited.is_some() && !is_running(**pid))
+            .map(|(pid, _)| *pid)
+            .collect();
+        unseen.sort_by_key(|pid| self.pending[pid].first_event);
+
+        for pid in unseen {
+            let Some(pending) = self.pending.remove(&pid) else {
+                continue;
+            };
+            let Some((exited, status)) = pending.exited else {
+                continue;
+            };
+
+            let process = LoggedProcess {
+                pid,
+                name: pending.name.unwrap_or_default(),
+                commandline: String::new(),
+                user: None,
+                app: None,
+                started: pending.started,
+            };
+
+            if let Some(started) = pending.started {
+                self.push(started, process.clone(), ProcessLogEventKind::Started);
+            }
+
+            self.push(
+                exited,
+                process,
+                ProcessLogEventKind::Exited {
+                    status: Some(status),
+                    lifetime: pending
+                        .started
+                        .map(|started| exited.saturating_sub(started)),
+                    peak_memory: None,
+                    cpu_time: None,
+                },
+            );
+        }
+
+        self.pending
+            .retain(|_, pending| now.saturating_sub(pending.first_event) < PENDING_TIMEOUT_MILLIS);
+        self.exited_without_status
+            .retain(|_, (_, logged)| now.saturating_sub(*logged) < PENDING_TIMEOUT_MILLIS);
+    }
+
+    fn amend_exit_status(&mut self, id: u64, exit_status: ExitStatus) {
+        let index = self.entries.partition_point(|entry| entry.id < id);
+        if let Some(ProcessLogEntry {
+            kind: ProcessLogEventKind::Exited { status, .. },
+            ..
+        }) = self.entries.get_mut(index).filter(|entry| entry.id == id)
+        {
+            *status = Some(exit_status);
+            self.amended.push(id);
+        }
+    }
+
+    fn push(&mut self, timestamp: u64, process: LoggedProcess, kind: ProcessLogEventKind) {
+        let entry = ProcessLogEntry {
+            id: self.next_id,
+            timestamp,
+            pid: process.pid,
+            name: process.name,
+            commandline: process.commandline,
+            user: process.user,
+            app: process.app,
+            kind,
+        };
+
+        self.next_id += 1;
+
+        self.entries.push_back(entry);
+        while self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use process_data::proc_connector::{ExitStatus, ProcessEvent, ProcessEventKind, ProcessEvents};
+
+    use super::{LoggedProcess, ProcessLog, ProcessLogEventKind, ProcessUsage, MAX_ENTRIES};
+
+    fn logged_process(pid: libc::pid_t, started: Option<u64>) -> LoggedProcess {
+        LoggedProcess {
+            pid,
+            name: String::from("cc1"),
+            commandline: String::from("cc1 main.c"),
+            user: Some(String::from("user")),
+            app: None,
+            started,
+        }
+    }
+
+    fn event(pid: libc::pid_t, timestamp: u64, kind: ProcessEventKind) -> ProcessEvent {
+        ProcessEvent {
+            pid,
+            comm: Some(String::from("cc1")),
+            timestamp,
+            kind,
+        }
+    }
+
+    const USAGE: ProcessUsage = ProcessUsage {
+        peak_memory: 1024,
+        cpu_time: 1.5,
+    };
+
+    #[test]
+    fn exits_without_events() {
+        let mut log = ProcessLog::new();
+
+        log.record_start(logged_process(100, Some(1_000)), 5_000);
+        log.record_exit(logged_process(100, Some(1_000)), USAGE, 6_000);
+
+        let entries: Vec<_> = log.entries_after(None).collect();
+        assert_eq!(2, entries.len());
+        assert_eq!(ProcessLogEventKind::Started, entries[0].kind);
+        assert_eq!(1_000, entries[0].timestamp);
+        assert_eq!(
+            ProcessLogEventKind::Exited {
+                status: None,
+                lifetime: Some(5_000),
+                peak_memory: Some(1024),
+                cpu_time: Some(1.5),
+            },
+            entries[1].kind
+        );
+        assert_eq!(6_000, entries[1].timestamp);
+    }
+
+    #[test]
+    fn exits_use_the_status_of_events() {
+        let mut log = ProcessLog::new();
+
+        log.record_events(ProcessEvents {
+            events: vec![event(
+                100,
+                5_500,
+                ProcessEventKind::Exit {
+                    status: ExitStatus::Exited(1),
+                },
+            )],
+            lost: 0,
+        });
+        log.record_exit(logged_process(100, Some(1_000)), USAGE, 6_000);
+        log.record_unseen_exits(|_| false, 6_000);
+
+        let entries: Vec<_> = log.entries_after(None).collect();
+        assert_eq!(1, entries.len());
+        assert_eq!(5_500, entries[0].timestamp);
+        assert_eq!(
+            ProcessLogEventKind::Exited {
+                status: Some(ExitStatus::Exited(1)),
+                lifetime: Some(4_500),
+                peak_memory: Some(1024),
+                cpu_time: Some(1.5),
+            },
+            entries[0].kind
+        );
+    }
+
+    #[test]
+    fn short_lived_processes_are_logged() {
+        let mut log = ProcessLog::new();
+
+        log.record_events(ProcessEvents {
+            events: vec![
+                event(200, 1_000, ProcessEventKind::Fork { parent_pid: 1 }),
+                event(200, 1_010, ProcessEventKind::Exec),
+                event(
+                    200,
+                    1_250,
+                    ProcessEventKind::Exit {
+                        status: ExitStatus::Signaled {
+                            signal: libc::SIGSEGV,
+                            core_dumped: true,
+                        },
+                    },
+                ),
+            ],
+            lost: 0,
+        });
+        log.record_unseen_exits(|_| false, 2_000);
+
+        let entries: Vec<_> = log.entries_after(None).collect();
+        assert_eq!(2, entries.len());
+        assert_eq!(ProcessLogEventKind::Started, entries[0].kind);
+        assert_eq!(1_000, entries[0].timestamp);
+        assert_eq!("cc1", entries[0].name);
+        assert_eq!(None, entries[0].user);
+        assert_eq!(
+            ProcessLogEventKind::Exited {
+                status: Some(ExitStatus::Signaled {
+                    signal: libc::SIGSEGV,
+                    core_dumped: true,
+                }),
+                lifetime: Some(250),
+                peak_memory: None,
+                cpu_time: None,
+            },
+            entries[1].kind
+        );
+    }
+
+    #[test]
+    fn exits_after_the_snapshot_wait_for_the_next_one() {
+        let mut log = ProcessLog::new();
+
+        // the process has exited after it has been seen in a snapshot
+        log.record_events(ProcessEvents {
+            events: vec![
+                event(300, 1_000, ProcessEventKind::Fork { parent_pid: 1 }),
+                event(
+                    300,
+                    1_900,
+                    ProcessEventKind::Exit {
+                        status: ExitStatus::Exited(0),
+                    },
+                ),
+            ],
+            lost: 0,
+        });
+        log.record_start(logged_process(300, Some(1_000)), 2_000);
+        log.record_unseen_exits(|pid| pid == 300, 2_000);
+        assert_eq!(1, log.entries_after(None).count());
+
+        // it's gone in the next snapshot
+        log.record_exit(logged_process(300, Some(1_000)), USAGE, 3_000);
+        log.record_unseen_exits(|_| false, 3_000);
+
+        let entries: Vec<_> = log.entries_after(None).collect();
+        assert_eq!(2, entries.len());
+        assert_eq!(1_900, entries[1].timestamp);
+        assert!(matches!(
+            entries[1].kind,
+            ProcessLogEventKind::Exited {
+                status: Some(ExitStatus::Exited(0)),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn late_exit_events_amend_the_logged_exit() {
+        let mut log = ProcessLog::new();
+
+        // the process has vanished from the snapshot before its exit event has been received
+        log.record_start(logged_process(400, Some(1_000)), 1_000);
+        log.record_exit(logged_process(400, Some(1_000)), USAGE, 2_000);
+        log.record_unseen_exits(|_| false, 2_000);
+
+        log.record_events(ProcessEvents {
+            events: vec![event(
+                400,
+                1_900,
+                ProcessEventKind::Exit {
+                    status: ExitStatus::Exited(3),
+                },
+            )],
+            lost: 0,
+        });
+        log.record_unseen_exits(|_| false, 3_000);
+
+        let entries: Vec<_> = log.entries_after(None).collect();
+        assert_eq!(2, entries.len());
+        assert_eq!(
+            ProcessLogEventKind::Exited {
+                status: Some(ExitStatus::Exited(3)),
+                lifetime: Some(1_000),
+                peak_memory: Some(1024),
+                cpu_time: Some(1.5),
+            },
+            entries[1].kind
+        );
+        assert_eq!(&[entries[1].id], log.amended_ids());
+        assert_eq!(Some(entries[1]), log.entry(entries[1].id));
+
+        // a reused PID doesn't amend the old entry
+        log.record_events(ProcessEvents {
+            events: vec![
+                event(400, 4_000, ProcessEventKind::Fork { parent_pid: 1 }),
+                event(
+                    400,
+                    4_100,
+                    ProcessEventKind::Exit {
+                        status: ExitStatus::Exited(0),
+                    },
+                ),
+            ],
+            lost: 0,
+        });
+        log.record_unseen_exits(|_| false, 5_000);
+
+        assert!(log.amended_ids().is_empty());
+        assert_eq!(4, log.entries_after(None).count());
+    }
+
+    #[test]
+    fn old_entries_are_dropped() {
+        let mut log = ProcessLog::new();
+
+        for pid in 0..(MAX_ENTRIES as libc::pid_t + 10) {
+            log.record_start(logged_process(pid, None), 1_000);
+        }
+
+        assert_eq!(Some(10), log.oldest_id());
+        assert_eq!(MAX_ENTRIES, log.entries_after(None).count());
+
+        let newest: Vec<_> = log
+            .entries_after(Some(MAX_ENTRIES as u64 + 7))
+            .map(|entry| entry.id)
+            .collect();
+        assert_eq!(vec![MAX_ENTRIES as u64 + 8, MAX_ENTRIES as u64 + 9], newest);
+    }
+}
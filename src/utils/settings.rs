@@ -143,6 +143,18 @@ pub enum SidebarMeterType {
     Graph,
 }
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, EnumString, Display, Hash, FromRepr)]
+pub enum MemoryMetric {
+    /// Resident memory minus shared memory, calculated from statm
+    #[default]
+    Resident,
+    /// Proportional set size, shared memory is divided between all processes using it
+    Proportional,
+    /// Unique set size, only memory that is private to a process
+    Unique,
+}
+
 #[derive(Clone, Debug, Hash)]
 pub struct Settings(gio::Settings);
 
@@ -244,6 +256,26 @@ impl Settings {
         })
     }
 
+    pub fn memory_metric(&self) -> MemoryMetric {
+        MemoryMetric::from_str(self.string("memory-metric").as_str()).unwrap_or_default()
+    }
+
+    pub fn set_memory_metric(&self, value: MemoryMetric) -> Result<(), glib::error::BoolError> {
+        self.set_string("memory-metric", &value.to_string())
+    }
+
+    pub fn connect_memory_metric<F: Fn(MemoryMetric) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("memory-metric"), move |settings, _key| {
+            f(
+                MemoryMetric::from_str(settings.string("memory-metric").as_str())
+                    .unwrap_or_default(),
+            );
+        })
+    }
+
     // the following three functions are kept for compatibility reasons and for not having an oddly named function
     // called "set_is_maximized" generated by the macro
     pub fn maximized(&self) -> bool {
@@ -296,6 +328,75 @@ impl Settings {
         )
     }
 
+    pub fn services_sort_by_ascending(&self) -> SortType {
+        if self.boolean("services-sort-by-ascending") {
+            SortType::Ascending
+        } else {
+            SortType::Descending
+        }
+    }
+
+    pub fn set_services_sort_by_ascending(
+        &self,
+        value: SortType,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_boolean(
+            "services-sort-by-ascending",
+            matches!(value, SortType::Ascending),
+        )
+    }
+
+    pub fn connect_services_sort_by_ascending<F: Fn(SortType) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("services-sort-by-ascending"), move |settings, _key| {
+            let sort_type = if settings.boolean("services-sort-by-ascending") {
+                SortType::Ascending
+            } else {
+                SortType::Descending
+            };
+
+            f(sort_type);
+        })
+    }
+
+    pub fn containers_sort_by_ascending(&self) -> SortType {
+        if self.boolean("containers-sort-by-ascending") {
+            SortType::Ascending
+        } else {
+            SortType::Descending
+        }
+    }
+
+    pub fn set_containers_sort_by_ascending(
+        &self,
+        value: SortType,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_boolean(
+            "containers-sort-by-ascending",
+            matches!(value, SortType::Ascending),
+        )
+    }
+
+    pub fn connect_containers_sort_by_ascending<F: Fn(SortType) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(
+            Some("containers-sort-by-ascending"),
+            move |settings, _key| {
+                let sort_type = if settings.boolean("containers-sort-by-ascending") {
+                    SortType::Ascending
+                } else {
+                    SortType::Descending
+                };
+
+                f(sort_type);
+            },
+        )
+    }
+
     pub fn apps_sort_by_ascending(&self) -> SortType {
         if self.boolean("apps-sort-by-ascending") {
             SortType::Ascending
@@ -331,7 +432,13 @@ impl Settings {
 
     int_settings!(window_width, window_height);
 
-    uint_settings!(graph_data_points, apps_sort_by, processes_sort_by);
+    uint_settings!(
+        graph_data_points,
+        apps_sort_by,
+        processes_sort_by,
+        containers_sort_by,
+        services_sort_by
+    );
 
     bool_settings!(
         show_search_on_start,
@@ -368,6 +475,12 @@ impl Settings {
         processes_show_system_cpu_time,
         processes_show_priority,
         processes_show_swap,
+        processes_show_state,
+        processes_show_threads,
+        processes_show_process_group,
+        processes_show_session,
+        processes_show_tty,
+        processes_show_kernel_thread,
         show_logical_cpus,
         show_graph_grids,
         normalize_cpu_usage,
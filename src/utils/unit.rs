@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use process_data::cgroup::SystemdUnit;
+
+use super::{app::AppsContext, process::Process};
+
+/// A process of a unit together with its depth in the unit's process tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessTreeNode {
+    pub depth: usize,
+    pub pid: libc::pid_t,
+    pub display_name: String,
+}
+
+/// Represents a systemd unit (a service or a scope) that has at least one running process. Its resource usage is
+/// taken from the kernel's accounting of the unit's cgroup whenever it's available.
+#[derive(Debug, Clone)]
+pub struct Unit {
+    processes: Vec<libc::pid_t>,
+    pub unit: SystemdUnit,
+}
+
+impl Unit {
+    pub fn new(unit: SystemdUnit) -> Self {
+        Self {
+            processes: Vec::new(),
+            unit,
+        }
+    }
+
+    pub fn add_process(&mut self, pid: libc::pid_t) {
+        self.processes.push(pid);
+    }
+
+    pub fn clear_processes(&mut self) {
+        self.processes.clear();
+    }
+
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        !self.processes.is_empty()
+    }
+
+    pub fn running_processes(&self) -> usize {
+        self.processes.len()
+    }
+
+    pub fn processes_iter<'a>(&'a self, apps: &'a AppsContext) -> impl Iterator<Item = &Process> {
+        apps.processes_iter()
+            .filter(move |process| self.processes.contains(&process.data.pid))
+    }
+
+    /// Returns the processes of the unit in depth-first order, starting with the processes whose parent isn't part of
+    /// the unit (usually the main process).
+    pub fn process_tree(&self, apps: &AppsContext) -> Vec<ProcessTreeNode> {
+        let mut children: HashMap<libc::pid_t, Vec<&Process>> = HashMap::new();
+        let mut roots = Vec::new();
+
+        for process in self.processes_iter(apps) {
+            if self.processes.contains(&process.data.parent_pid) {
+                children
+                    .entry(process.data.parent_pid)
+                    .or_default()
+                    .push(process);
+            } else {
+                roots.push(process);
+            }
+        }
+
+        let mut tree = Vec::with_capacity(self.processes.len());
+
+        let mut stack: Vec<(usize, &Process)> = roots.into_iter().map(|root| (0, root)).collect();
+        stack.sort_by_key(|(_, process)| std::cmp::Reverse(process.data.pid));
+
+        while let Some((depth, process)) = stack.pop() {
+            tree.push(ProcessTreeNode {
+                depth,
+                pid: process.data.pid,
+                display_name: process.display_name.clone(),
+            });
+
+            if let Some(children) = children.get_mut(&process.data.pid) {
+                children.sort_by_key(|child| std::cmp::Reverse(child.data.pid));
+                stack.extend(children.iter().map(|child| (depth + 1, *child)));
+            }
+        }
+
+        tree
+    }
+
+    #[must_use]
+    pub fn cpu_time_ratio(&self, apps: &AppsContext) -> f32 {
+        apps.cgroups_cpu_time_ratio(
+            std::slice::from_ref(&self.unit.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn memory_usage(&self, apps: &AppsContext) -> usize {
+        apps.cgroups_memory_usage(
+            std::slice::from_ref(&self.unit.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn read_speed(&self, apps: &AppsContext) -> f64 {
+        apps.cgroups_read_speed(
+            std::slice::from_ref(&self.unit.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn read_total(&self, apps: &AppsContext) -> u64 {
+        apps.cgroups_read_total(
+            std::slice::from_ref(&self.unit.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn write_speed(&self, apps: &AppsContext) -> f64 {
+        apps.cgroups_write_speed(
+            std::slice::from_ref(&self.unit.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+
+    #[must_use]
+    pub fn write_total(&self, apps: &AppsContext) -> u64 {
+        apps.cgroups_write_total(
+            std::slice::from_ref(&self.unit.cgroup),
+            self.processes_iter(apps),
+        )
+    }
+}